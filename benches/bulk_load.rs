@@ -0,0 +1,47 @@
+// Loads random keys into a heap through `insert` and through a `bulk_insert` guard, then drains it with
+// `extract_min`, and prints how long loading and draining took.
+//
+//     cargo bench --bench bulk_load
+
+extern crate fibonacci_heap;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use fibonacci_heap::FibonacciHeap;
+
+const ENTRIES: u64 = 1_000_000;
+
+// Drains the heap and returns how long it took
+fn drain(mut heap: FibonacciHeap<u64, u64>) -> Duration {
+    let start = Instant::now();
+    while let Some(entry) = heap.extract_min() {
+        black_box(entry);
+    }
+    start.elapsed()
+}
+
+fn main() {
+    // A multiplicative hash spreads the keys without pulling in a random number generator
+    let entries: Vec<(u64, u64)> = (0..ENTRIES).map(|i| (i.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 40, i)).collect();
+    
+    for _ in 0..2 {
+        let start = Instant::now();
+        let mut heap = FibonacciHeap::new();
+        for &(key, value) in &entries {
+            heap.insert(key, value);
+        }
+        let loaded = start.elapsed();
+        let drained = drain(heap);
+        println!("     insert: loaded in {:>7.1} ms, drained in {:>7.1} ms", loaded.as_secs_f64() * 1e3,
+                 drained.as_secs_f64() * 1e3);
+        
+        let start = Instant::now();
+        let mut heap = FibonacciHeap::new();
+        heap.bulk_insert().extend(entries.iter().cloned());
+        let loaded = start.elapsed();
+        let drained = drain(heap);
+        println!("bulk_insert: loaded in {:>7.1} ms, drained in {:>7.1} ms", loaded.as_secs_f64() * 1e3,
+                 drained.as_secs_f64() * 1e3);
+    }
+}
@@ -0,0 +1,88 @@
+// Runs Dijkstra on a large random graph, relaxing the edges of each settled node with one `decrease_keys` call or with
+// a `decrease_key` call per edge, and prints how long each took.
+//
+//     cargo bench --bench decrease_keys
+
+extern crate fibonacci_heap;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use fibonacci_heap::FibonacciHeap;
+
+const NODES: usize = 100_000;
+const EDGES_PER_NODE: usize = 32;
+
+fn random_graph() -> Vec<Vec<(usize, u64)>> {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    
+    (0..NODES)
+        .map(|_| (0..EDGES_PER_NODE).map(|_| (next() as usize % NODES, 1 + next() % 1000)).collect())
+        .collect()
+}
+
+// Returns the cost of every node and the time spent decreasing keys
+fn shortest_paths(graph: &[Vec<(usize, u64)>], batched: bool) -> (Vec<u64>, Duration) {
+    let mut costs = vec![u64::MAX; graph.len()];
+    let mut settled = vec![false; graph.len()];
+    let mut heap = FibonacciHeap::with_capacity(graph.len());
+    let mut updates = Vec::new();
+    let mut decreasing = Duration::ZERO;
+    
+    costs[0] = 0;
+    heap.insert(0, 0);
+    while let Some((cost, node)) = heap.extract_min() {
+        settled[node] = true;
+        
+        for &(next, weight) in &graph[node] {
+            let next_cost = cost + weight;
+            if settled[next] || next_cost >= costs[next] {
+                continue;
+            }
+            
+            if costs[next] == u64::MAX {
+                heap.insert(next_cost, next);
+            } else {
+                updates.push((next, next_cost));
+            }
+            costs[next] = next_cost;
+        }
+        
+        if updates.is_empty() {
+            continue;
+        }
+        
+        let start = Instant::now();
+        if batched {
+            black_box(heap.decrease_keys(updates.drain(..)));
+        } else {
+            for (next, next_cost) in updates.drain(..) {
+                let _ = black_box(heap.decrease_key(next, next_cost));
+            }
+        }
+        decreasing += start.elapsed();
+    }
+    
+    (costs, decreasing)
+}
+
+fn main() {
+    let graph = random_graph();
+    
+    for &batched in &[false, true, false, true] {
+        let start = Instant::now();
+        let (costs, decreasing) = black_box(shortest_paths(&graph, batched));
+        let elapsed = start.elapsed();
+        
+        let reached = costs.iter().filter(|&&cost| cost != u64::MAX).count();
+        println!("{:>7}: {:>7.1} ms in total, {:>6.1} ms decreasing keys, {} nodes reached",
+                 if batched { "batched" } else { "looped" }, elapsed.as_secs_f64() * 1e3,
+                 decreasing.as_secs_f64() * 1e3, reached);
+    }
+}
@@ -0,0 +1,53 @@
+// Finds the shortest paths from one node of a large sparse graph with `dijkstra` and with petgraph's own, which pushes a
+// node again for every cheaper path instead of decreasing its key, and prints how long each took.
+//
+//     cargo bench --bench dijkstra --features petgraph
+
+extern crate fibonacci_heap;
+extern crate petgraph;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use fibonacci_heap::dijkstra;
+use petgraph::algo;
+use petgraph::graph::{DiGraph, NodeIndex};
+
+const NODES: u64 = 1_000_000;
+const EDGES: u64 = 4_000_000;
+
+fn main() {
+    let mut state = 0x9e37_79b9_7f4a_7c15u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    
+    let mut graph: DiGraph<(), u64> = DiGraph::with_capacity(NODES as usize, EDGES as usize);
+    for _ in 0..NODES {
+        graph.add_node(());
+    }
+    for _ in 0..EDGES {
+        let a = NodeIndex::new((next() % NODES) as usize);
+        let b = NodeIndex::new((next() % NODES) as usize);
+        graph.add_edge(a, b, next() % 1000);
+    }
+    
+    let start = NodeIndex::new(0);
+    for _ in 0..3 {
+        let begin = Instant::now();
+        let costs = black_box(dijkstra(&graph, start, None, |edge| *edge.weight()));
+        let fibonacci = begin.elapsed();
+        
+        let begin = Instant::now();
+        let expected = black_box(algo::dijkstra(&graph, start, None, |edge| *edge.weight()));
+        let binary = begin.elapsed();
+        
+        assert_eq!(costs.len(), expected.len());
+        assert!(expected.iter().all(|(node, cost)| costs.get(node) == Some(cost)));
+        println!("{} nodes and {} edges, {} reached: dijkstra {:>7.1} ms, petgraph {:>7.1} ms", NODES, EDGES,
+                 costs.len(), fibonacci.as_secs_f64() * 1e3, binary.as_secs_f64() * 1e3);
+    }
+}
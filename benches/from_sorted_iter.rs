@@ -0,0 +1,31 @@
+// Builds heaps from entries that are already in ascending key order, with `from_sorted_iter` and with `collect`, and
+// prints how long each took.
+//
+//     cargo bench --bench from_sorted_iter
+
+extern crate fibonacci_heap;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use fibonacci_heap::FibonacciHeap;
+
+const ENTRIES: u64 = 1_000_000;
+
+fn main() {
+    let entries: Vec<(u64, u64)> = (0..ENTRIES).map(|i| (i / 4, i)).collect();
+    
+    for _ in 0..2 {
+        let start = Instant::now();
+        let heap: FibonacciHeap<_, _> = FibonacciHeap::from_sorted_iter(entries.iter().cloned());
+        let elapsed = start.elapsed();
+        black_box(&heap);
+        println!("from_sorted_iter: {:>7.1} ms for {} entries", elapsed.as_secs_f64() * 1e3, heap.len());
+        
+        let start = Instant::now();
+        let heap: FibonacciHeap<_, _> = entries.iter().cloned().collect();
+        let elapsed = start.elapsed();
+        black_box(&heap);
+        println!("         collect: {:>7.1} ms for {} entries", elapsed.as_secs_f64() * 1e3, heap.len());
+    }
+}
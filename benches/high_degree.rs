@@ -0,0 +1,40 @@
+// Extracts the minimum of a heap whose root has every other entry as its child, so extract_min promotes all of them
+// to roots before consolidating, and prints how long it took.
+//
+//     cargo bench --bench high_degree
+
+extern crate fibonacci_heap;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use fibonacci_heap::{FibonacciHeap, HeapSnapshot, SnapshotNode};
+
+const CHILDREN: usize = 1_000_000;
+
+fn star() -> FibonacciHeap<usize, usize> {
+    let mut nodes = vec![SnapshotNode {
+        key: 0,
+        value: 0,
+        degree: CHILDREN,
+        marked: false,
+        children: (1..=CHILDREN).collect()
+    }];
+    nodes.extend((1..=CHILDREN).map(|i| {
+        SnapshotNode { key: i, value: i, degree: 0, marked: false, children: Vec::new() }
+    }));
+    
+    FibonacciHeap::from_snapshot(HeapSnapshot { nodes, roots: vec![0] }).unwrap()
+}
+
+fn main() {
+    for _ in 0..3 {
+        let mut heap = star();
+        
+        let start = Instant::now();
+        black_box(heap.extract_min());
+        let elapsed = start.elapsed();
+        println!("extract_min promoting {} children: {:>7.1} ms, max degree {} after", CHILDREN,
+                 elapsed.as_secs_f64() * 1e3, heap.stats().max_degree);
+    }
+}
@@ -0,0 +1,36 @@
+// Empties heaps of random keys with `into_sorted_vec` and with a loop of `extract_min`, and prints how long each took.
+//
+//     cargo bench --bench into_sorted_vec
+
+extern crate fibonacci_heap;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use fibonacci_heap::FibonacciHeap;
+
+const ENTRIES: u64 = 1_000_000;
+
+fn main() {
+    // A multiplicative hash spreads the keys without pulling in a random number generator
+    let entries: Vec<(u64, u64)> = (0..ENTRIES).map(|i| (i.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 40, i)).collect();
+    
+    for _ in 0..2 {
+        let heap: FibonacciHeap<_, _> = entries.iter().cloned().collect();
+        let start = Instant::now();
+        let sorted = heap.into_sorted_vec();
+        let elapsed = start.elapsed();
+        black_box(&sorted);
+        println!("into_sorted_vec: {:>7.1} ms for {} entries", elapsed.as_secs_f64() * 1e3, sorted.len());
+        
+        let mut heap: FibonacciHeap<_, _> = entries.iter().cloned().collect();
+        let start = Instant::now();
+        let mut sorted = Vec::with_capacity(heap.len());
+        while let Some(entry) = heap.extract_min() {
+            sorted.push(entry);
+        }
+        let elapsed = start.elapsed();
+        black_box(&sorted);
+        println!("    extract_min: {:>7.1} ms for {} entries", elapsed.as_secs_f64() * 1e3, sorted.len());
+    }
+}
@@ -0,0 +1,39 @@
+// Merges 1,000 sorted streams of different lengths with `kway_merge` and with a sort of all their items, and prints how
+// long each took.
+//
+//     cargo bench --bench kway_merge
+
+extern crate fibonacci_heap;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use fibonacci_heap::kway_merge;
+
+const STREAMS: u64 = 1_000;
+const LONGEST: u64 = 2_000;
+
+fn main() {
+    // Streams that step by different amounts, so they overlap and share items
+    let streams: Vec<Vec<u64>> = (0..STREAMS).map(|i| {
+        let len = i.wrapping_mul(0x9e37_79b9) % LONGEST;
+        (0..len).map(|j| j * (i % 7 + 1)).collect()
+    }).collect();
+    let items: usize = streams.iter().map(Vec::len).sum();
+    
+    for _ in 0..3 {
+        let start = Instant::now();
+        let merged = black_box(kway_merge(streams.iter().map(|stream| stream.iter().cloned()).collect()).count());
+        let merge = start.elapsed();
+        
+        let start = Instant::now();
+        let mut all: Vec<u64> = streams.iter().flatten().cloned().collect();
+        all.sort();
+        black_box(&all);
+        let sort = start.elapsed();
+        
+        assert_eq!(merged, items);
+        println!("{} items from {} streams: kway_merge {:>6.1} ms, sort {:>6.1} ms", items, STREAMS,
+                 merge.as_secs_f64() * 1e3, sort.as_secs_f64() * 1e3);
+    }
+}
@@ -0,0 +1,38 @@
+// Fills a heap with random keys, prints how many bytes each entry takes in the node storage and in the lookup, and how
+// long filling and emptying the heap took.
+//
+//     cargo bench --bench memory_per_entry
+
+extern crate fibonacci_heap;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use fibonacci_heap::FibonacciHeap;
+
+const ENTRIES: u64 = 1_000_000;
+
+fn main() {
+    for _ in 0..2 {
+        let start = Instant::now();
+        let mut heap = FibonacciHeap::with_capacity(ENTRIES as usize);
+        for i in 0..ENTRIES {
+            // A multiplicative hash spreads the keys without pulling in a random number generator
+            heap.insert(i.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 40, i);
+        }
+        let filled = start.elapsed();
+        
+        let usage = heap.memory_usage();
+        let per_entry = |bytes: usize| bytes as f64 / ENTRIES as f64;
+        let (nodes, lookup, total) = (per_entry(usage.nodes), per_entry(usage.lookup), per_entry(usage.total));
+        println!("nodes {:>5.1} B, lookup {:>5.1} B, total {:>5.1} B per entry", nodes, lookup, total);
+        
+        let start = Instant::now();
+        while let Some(entry) = heap.extract_min() {
+            black_box(entry);
+        }
+        let emptied = start.elapsed();
+        let (filled, emptied) = (filled.as_secs_f64() * 1e3, emptied.as_secs_f64() * 1e3);
+        println!("filled in {:>7.1} ms, emptied in {:>7.1} ms", filled, emptied);
+    }
+}
@@ -0,0 +1,59 @@
+// Runs millions of mixed inserts, extractions and key decreases, the operations a Dijkstra run spends its time in, and
+// prints how long they took.
+//
+//     cargo bench --bench mixed_operations
+
+extern crate fibonacci_heap;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use fibonacci_heap::FibonacciHeap;
+
+const VALUES: u64 = 1 << 18;
+const OPERATIONS: usize = 4_000_000;
+
+fn main() {
+    for _ in 0..3 {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        
+        let mut keys = vec![None; VALUES as usize];
+        let mut heap = FibonacciHeap::new();
+        let start = Instant::now();
+        
+        for _ in 0..OPERATIONS {
+            let value = next() % VALUES;
+            match next() % 10 {
+                // Only new values are inserted, so every operation does the same work on any version of the heap
+                0..=3 => {
+                    if keys[value as usize].is_none() {
+                        let key = next() % (1 << 40);
+                        heap.insert(key, value);
+                        keys[value as usize] = Some(key);
+                    }
+                },
+                4..=6 => {
+                    if let Some((_, value)) = black_box(heap.extract_min()) {
+                        keys[value as usize] = None;
+                    }
+                },
+                _ => {
+                    if let Some(key) = keys[value as usize] {
+                        let key = key / 2;
+                        let _ = black_box(heap.decrease_key(value, key));
+                        keys[value as usize] = Some(key);
+                    }
+                }
+            }
+        }
+        
+        let elapsed = start.elapsed();
+        println!("{} operations: {:>7.1} ms, {} entries left", OPERATIONS, elapsed.as_secs_f64() * 1e3, heap.len());
+    }
+}
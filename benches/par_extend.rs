@@ -0,0 +1,39 @@
+// Builds heaps of random keys with `collect` and with `par_iter().collect()` on thread pools of growing size, and
+// prints how long each took.
+//
+//     cargo bench --bench par_extend --features rayon
+
+extern crate fibonacci_heap;
+extern crate rayon;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use fibonacci_heap::FibonacciHeap;
+use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+use rayon::ThreadPoolBuilder;
+
+const ENTRIES: u64 = 5_000_000;
+
+fn main() {
+    // A multiplicative hash spreads the keys without pulling in a random number generator
+    let entries: Vec<(u64, u64)> = (0..ENTRIES).map(|i| (i.wrapping_mul(0x9e37_79b9_7f4a_7c15) >> 24, i)).collect();
+    
+    for _ in 0..2 {
+        let start = Instant::now();
+        let heap: FibonacciHeap<_, _> = entries.iter().cloned().collect();
+        let elapsed = start.elapsed();
+        black_box(&heap);
+        println!("sequential: {:>7.1} ms for {} entries", elapsed.as_secs_f64() * 1e3, heap.len());
+        drop(heap);
+        
+        for &threads in &[1, 2, 4, 8] {
+            let pool = ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            let start = Instant::now();
+            let heap: FibonacciHeap<_, _> = pool.install(|| entries.par_iter().cloned().collect());
+            let elapsed = start.elapsed();
+            black_box(&heap);
+            println!("{:>2} threads: {:>7.1} ms for {} entries", threads, elapsed.as_secs_f64() * 1e3, heap.len());
+        }
+    }
+}
@@ -0,0 +1,58 @@
+// Times each of the hot operations on its own: inserting, decreasing every key once and extracting every entry, where
+// extract_min does the consolidating. Prints the time per operation. Only calls that every version of the heap has
+// are used, so the numbers compare across versions.
+//
+//     cargo bench --bench per_operation
+
+extern crate fibonacci_heap;
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use fibonacci_heap::FibonacciHeap;
+
+const ENTRIES: u64 = 200_000;
+
+fn per_operation(elapsed: Duration) -> f64 {
+    elapsed.as_secs_f64() * 1e9 / ENTRIES as f64
+}
+
+fn main() {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let keys: Vec<u64> = (0..ENTRIES).map(|_| (1 << 40) + next() % (1 << 40)).collect();
+    let lower: Vec<u64> = keys.iter().map(|&key| key - next() % (1 << 40)).collect();
+    
+    for _ in 0..3 {
+        let mut heap = FibonacciHeap::new();
+        
+        let start = Instant::now();
+        for value in 0..ENTRIES {
+            heap.insert(keys[value as usize], value);
+        }
+        let inserting = start.elapsed();
+        
+        // Links the roots into trees first, so decreasing keys cuts nodes from their parents
+        let min = heap.extract_min();
+        let start = Instant::now();
+        for value in 0..ENTRIES {
+            let _ = black_box(heap.decrease_key(value, lower[value as usize]));
+        }
+        let decreasing = start.elapsed();
+        
+        let start = Instant::now();
+        while let Some(entry) = heap.extract_min() {
+            black_box(entry);
+        }
+        let extracting = start.elapsed();
+        
+        black_box(min);
+        println!("insert {:>6.1} ns, decrease_key {:>6.1} ns, extract_min {:>7.1} ns", per_operation(inserting),
+                 per_operation(decreasing), per_operation(extracting));
+    }
+}
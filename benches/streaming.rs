@@ -0,0 +1,35 @@
+// Streams events through a heap of fixed size, extracting the earliest event and inserting a later one on every step,
+// and prints how long it took with and without a limit on the free node slots.
+//
+//     cargo bench --bench streaming
+
+extern crate fibonacci_heap;
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use fibonacci_heap::FibonacciHeap;
+
+const EVENTS: u64 = 10_000;
+const STEPS: u64 = 4_000_000;
+
+fn main() {
+    for &limit in &[usize::MAX, 64, 64, usize::MAX] {
+        let mut heap = FibonacciHeap::with_capacity(EVENTS as usize);
+        heap.set_pool_limit(limit);
+        for i in 0..EVENTS {
+            heap.insert(i.wrapping_mul(0x9e37_79b9) % EVENTS, i);
+        }
+        
+        let start = Instant::now();
+        for i in EVENTS..EVENTS + STEPS {
+            let (time, _) = black_box(heap.extract_min()).unwrap();
+            heap.insert(time + i.wrapping_mul(0x9e37_79b9) % EVENTS, i);
+        }
+        
+        let elapsed = start.elapsed();
+        let limit = if limit == usize::MAX { "none".to_string() } else { limit.to_string() };
+        println!("{} steps with pool limit {:>4}: {:>7.1} ms, {} events left", STEPS, limit,
+                 elapsed.as_secs_f64() * 1e3, heap.len());
+    }
+}
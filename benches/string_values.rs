@@ -0,0 +1,66 @@
+// Times inserting, decreasing every key once through a handle and extracting every entry, once with u64 values and
+// once with 100 byte `String` values. Each value is hashed when it is inserted and never again, so decreasing keys
+// costs the same with both, and extracting only pays for the larger nodes and for freeing the strings.
+//
+//     cargo bench --bench string_values
+
+extern crate fibonacci_heap;
+
+use std::hash::Hash;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use fibonacci_heap::FibonacciHeap;
+
+const ENTRIES: u64 = 200_000;
+
+fn per_operation(elapsed: Duration) -> f64 {
+    elapsed.as_secs_f64() * 1e9 / ENTRIES as f64
+}
+
+fn run<V, F>(name: &str, keys: &[u64], lower: &[u64], value: F)
+    where V: Eq + Hash,
+          F: Fn(u64) -> V
+{
+    let values: Vec<V> = (0..ENTRIES).map(value).collect();
+    let mut heap = FibonacciHeap::new();
+    
+    let start = Instant::now();
+    let handles: Vec<_> = values.into_iter().zip(keys).map(|(value, &key)| heap.push_with_handle(key, value)).collect();
+    let inserting = start.elapsed();
+    
+    // Links the roots into trees first, so decreasing keys cuts nodes from their parents
+    let min = heap.extract_min();
+    let start = Instant::now();
+    for (&handle, &key) in handles.iter().zip(lower) {
+        let _ = black_box(heap.decrease_key_handle(handle, key));
+    }
+    let decreasing = start.elapsed();
+    
+    let start = Instant::now();
+    while let Some(entry) = heap.extract_min() {
+        black_box(entry);
+    }
+    let extracting = start.elapsed();
+    
+    black_box(min);
+    println!("{:>6}: insert {:>6.1} ns, decrease_key {:>6.1} ns, extract_min {:>7.1} ns", name,
+             per_operation(inserting), per_operation(decreasing), per_operation(extracting));
+}
+
+fn main() {
+    let mut state = 0x2545_f491_4f6c_dd1d_u64;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let keys: Vec<u64> = (0..ENTRIES).map(|_| (1 << 40) + next() % (1 << 40)).collect();
+    let lower: Vec<u64> = keys.iter().map(|&key| key - next() % (1 << 40)).collect();
+    
+    for _ in 0..2 {
+        run("u64", &keys, &lower, |i| i);
+        run("String", &keys, &lower, |i| format!("{:0>100}", i));
+    }
+}
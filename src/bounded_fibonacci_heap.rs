@@ -0,0 +1,142 @@
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use super::compare::MaxComparator;
+use super::error::DecreaseKeyError;
+use super::fibonacci_heap::{FibonacciHeap, Handle};
+
+/// A `FibonacciHeap` that keeps at most a fixed number of entries, those with the smallest keys.
+///
+/// Once the heap is full, inserting an entry evicts the entry with the largest key, or rejects the new entry if its key
+/// is no smaller than that. A second heap keeps a clone of every key with a handle to its entry, largest key first, so
+/// checking against the largest key runs in O(1) time and evicting it in O(log n) amortized time for the bound n.
+pub struct BoundedFibonacciHeap<K, V> {
+    heap: FibonacciHeap<K, V>,
+    // The handles are never invalidated, as the pool of the heap is not limited and it is never shrunk
+    largest: FibonacciHeap<K, Handle, MaxComparator>,
+    bound: usize
+}
+
+impl<K, V> BoundedFibonacciHeap<K, V>
+    where K: Ord + Clone,
+          V: Eq + Hash
+{
+    /// Creates a new empty `BoundedFibonacciHeap` that keeps at most `bound` entries.
+    pub fn new(bound: usize) -> BoundedFibonacciHeap<K, V> {
+        BoundedFibonacciHeap {
+            heap: FibonacciHeap::with_capacity(bound),
+            largest: FibonacciHeap::with_comparator(MaxComparator),
+            bound
+        }
+    }
+    
+    /// Returns the largest number of entries the heap keeps.
+    pub fn bound(&self) -> usize {
+        self.bound
+    }
+    
+    /// Inserts the value into the heap with priority key.
+    ///
+    /// Returns the entry that does not fit, either the evicted entry with the largest key or the new entry itself if
+    /// its key is no smaller than the largest key of a full heap. A value already in the heap has its priority changed
+    /// to key and nothing is evicted.
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if self.heap.len() < self.bound || self.heap.contains(&value) {
+            self.push(key, value);
+            return None;
+        }
+        
+        match self.largest.minimum() {
+            Some((max, _)) if key < *max => {},
+            _ => return Some((key, value))
+        }
+        
+        let evicted = self.largest.extract_min().and_then(|(_, handle)| self.heap.delete_handle(handle));
+        self.push(key, value);
+        
+        evicted
+    }
+    
+    // Inserts into both heaps, a value already in the heap keeps its entry and so its handle
+    fn push(&mut self, key: K, value: V) {
+        let handle = self.heap.push_with_handle(key.clone(), value);
+        self.largest.insert(key, handle);
+    }
+    
+    /// Peeks at the minimum of the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn minimum(&self) -> Option<(&K, &V)> {
+        self.heap.minimum()
+    }
+    
+    /// Peeks at the entry with the largest key, the next to be evicted.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn find_max(&self) -> Option<(&K, &V)> {
+        self.largest.minimum().and_then(|(_, &handle)| self.heap.get_handle(handle))
+    }
+    
+    /// Returns the number of values in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    
+    /// Returns `true` if the heap contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    
+    /// Returns `true` if the value is in the heap.
+    pub fn contains(&self, value: &V) -> bool {
+        self.heap.contains(value)
+    }
+    
+    /// Looks up the current priority of the value.
+    ///
+    /// Returns `None` if the value is not in the heap.
+    pub fn get_priority(&self, value: &V) -> Option<&K> {
+        self.heap.get_priority(value)
+    }
+    
+    /// Extracts the minimum of the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn extract_min(&mut self) -> Option<(K, V)> {
+        let handle = self.heap.minimum().and_then(|(_, value)| self.heap.handle(value))?;
+        self.largest.delete(&handle);
+        
+        self.heap.delete_handle(handle)
+    }
+    
+    /// Decreases the priority of the value to the key.
+    ///
+    /// Returns the previous priority, or `Err` holding the rejected key if the value is not in the heap or if the key is
+    /// greater than the current priority of the value. The number of entries does not change, so nothing is evicted.
+    pub fn decrease_key(&mut self, value: V, key: K) -> Result<K, DecreaseKeyError<K>> {
+        let handle = match self.heap.handle(&value) {
+            Some(handle) => handle,
+            None => return Err(DecreaseKeyError::NotFound(key))
+        };
+        
+        let old_key = self.heap.decrease_key_handle(handle, key.clone())?;
+        self.largest.change_priority(&handle, key).expect("every entry is in both heaps");
+        
+        Ok(old_key)
+    }
+    
+    /// Deletes the value from the heap.
+    ///
+    /// Returns the priority the value had or `None` if the value is not in the heap.
+    pub fn delete(&mut self, value: &V) -> Option<K> {
+        let handle = self.heap.handle(value)?;
+        self.largest.delete(&handle);
+        
+        self.heap.delete_handle(handle).map(|(key, _)| key)
+    }
+    
+    /// Consumes the heap into a `Vec` of its entries in ascending key order.
+    pub fn into_sorted_vec(self) -> Vec<(K, V)> {
+        self.heap.into_sorted_vec()
+    }
+}
@@ -0,0 +1,57 @@
+use core::cmp::Ordering;
+
+/// An ordering of keys, used by a `FibonacciHeap` to decide which key is the smallest.
+pub trait Compare<K> {
+    /// Compares two keys, the heap extracts the key that compares as `Less` first.
+    fn compare(&self, a: &K, b: &K) -> Ordering;
+}
+
+/// Orders keys by their `Ord` implementation, smallest first. This is the comparator of `FibonacciHeap::new`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MinComparator;
+
+impl<K> Compare<K> for MinComparator
+    where K: Ord
+{
+    fn compare(&self, a: &K, b: &K) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+/// Orders keys by their `Ord` implementation, largest first. This is the comparator of `FibonacciMaxHeap`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MaxComparator;
+
+impl<K> Compare<K> for MaxComparator
+    where K: Ord
+{
+    fn compare(&self, a: &K, b: &K) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+/// Orders keys with a closure, as built by `FibonacciHeap::new_by`.
+#[derive(Clone, Copy, Debug)]
+pub struct FnComparator<F>(pub F);
+
+impl<K, F> Compare<K> for FnComparator<F>
+    where F: Fn(&K, &K) -> Ordering
+{
+    fn compare(&self, a: &K, b: &K) -> Ordering {
+        (self.0)(a, b)
+    }
+}
+
+/// Orders keys by the sort key a closure extracts from them, as built by `FibonacciHeap::new_by_key`.
+#[derive(Clone, Copy, Debug)]
+pub struct KeyComparator<F>(pub F);
+
+impl<K, T, F> Compare<K> for KeyComparator<F>
+    where F: Fn(&K) -> T,
+          T: Ord
+{
+    fn compare(&self, a: &K, b: &K) -> Ordering {
+        (self.0)(a).cmp(&(self.0)(b))
+    }
+}
+
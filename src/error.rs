@@ -0,0 +1,332 @@
+use core::error::Error;
+use core::fmt;
+
+/// The error returned by `FibonacciHeap::try_insert` when the value is already in the heap.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OccupiedError<K, V> {
+    /// The key that was not inserted.
+    pub key: K,
+    /// The value that was not inserted.
+    pub value: V,
+    /// The priority the value already has in the heap.
+    pub existing_key: K
+}
+
+impl<K, V> fmt::Display for OccupiedError<K, V>
+    where K: fmt::Debug,
+          V: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} is already in the heap with priority {:?}, rejected key {:?}",
+               self.value, self.existing_key, self.key)
+    }
+}
+
+impl<K, V> Error for OccupiedError<K, V>
+    where K: fmt::Debug,
+          V: fmt::Debug
+{ }
+
+/// The error returned by `FibonacciHeap::decrease_key`, holding the key that was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecreaseKeyError<K> {
+    /// The value is not in the heap.
+    NotFound(K),
+    /// The key is greater than the current priority of the value.
+    KeyGreaterThanCurrent(K)
+}
+
+impl<K> DecreaseKeyError<K> {
+    /// Returns the key that was rejected.
+    pub fn into_key(self) -> K {
+        match self {
+            DecreaseKeyError::NotFound(key) => key,
+            DecreaseKeyError::KeyGreaterThanCurrent(key) => key
+        }
+    }
+}
+
+impl<K> fmt::Display for DecreaseKeyError<K>
+    where K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecreaseKeyError::NotFound(ref key) => write!(f, "the value is not in the heap, rejected key {:?}", key),
+            DecreaseKeyError::KeyGreaterThanCurrent(ref key) => {
+                write!(f, "key {:?} would not decrease the current priority of the value", key)
+            }
+        }
+    }
+}
+
+impl<K> Error for DecreaseKeyError<K>
+    where K: fmt::Debug
+{ }
+
+/// The error returned by `FibonacciHeap::decrease_key_by`, holding the delta that was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecreaseKeyByError<K> {
+    /// The value is not in the heap.
+    NotFound(K),
+    /// Subtracting the delta would not make the priority smaller.
+    NotDecreased(K),
+    /// Subtracting the delta from the current priority overflowed.
+    Overflow(K)
+}
+
+impl<K> DecreaseKeyByError<K> {
+    /// Returns the delta that was rejected.
+    pub fn into_delta(self) -> K {
+        match self {
+            DecreaseKeyByError::NotFound(delta) => delta,
+            DecreaseKeyByError::NotDecreased(delta) => delta,
+            DecreaseKeyByError::Overflow(delta) => delta
+        }
+    }
+}
+
+impl<K> fmt::Display for DecreaseKeyByError<K>
+    where K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DecreaseKeyByError::NotFound(ref delta) => {
+                write!(f, "the value is not in the heap, rejected delta {:?}", delta)
+            },
+            DecreaseKeyByError::NotDecreased(ref delta) => {
+                write!(f, "subtracting {:?} would not decrease the current priority of the value", delta)
+            },
+            DecreaseKeyByError::Overflow(ref delta) => {
+                write!(f, "subtracting {:?} from the current priority of the value overflows", delta)
+            }
+        }
+    }
+}
+
+impl<K> Error for DecreaseKeyByError<K>
+    where K: fmt::Debug
+{ }
+
+/// The error returned by `FibonacciMaxHeap::increase_key`, holding the key that was rejected.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum IncreaseKeyError<K> {
+    /// The value is not in the heap.
+    NotFound(K),
+    /// The key is less than the current priority of the value.
+    KeyLessThanCurrent(K)
+}
+
+impl<K> IncreaseKeyError<K> {
+    /// Returns the key that was rejected.
+    pub fn into_key(self) -> K {
+        match self {
+            IncreaseKeyError::NotFound(key) => key,
+            IncreaseKeyError::KeyLessThanCurrent(key) => key
+        }
+    }
+}
+
+impl<K> fmt::Display for IncreaseKeyError<K>
+    where K: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            IncreaseKeyError::NotFound(ref key) => write!(f, "the value is not in the heap, rejected key {:?}", key),
+            IncreaseKeyError::KeyLessThanCurrent(ref key) => {
+                write!(f, "key {:?} would not increase the current priority of the value", key)
+            }
+        }
+    }
+}
+
+impl<K> Error for IncreaseKeyError<K>
+    where K: fmt::Debug
+{ }
+
+/// The error returned by the operations of a `FibonacciHeap` that find a value but do not hand back a rejected key,
+/// such as `increase_key` and `change_priority`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HeapError {
+    /// The value is not in the heap.
+    NotFound,
+    /// The key is less than the current priority of the value, so it cannot be an increase.
+    KeyLessThanCurrent,
+    /// The key is greater than the current priority of the value, so it cannot be a decrease. Only returned by
+    /// `FibonacciMaxHeap::decrease_key`.
+    KeyGreaterThanCurrent
+}
+
+impl fmt::Display for HeapError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            HeapError::NotFound => write!(f, "the value is not in the heap"),
+            HeapError::KeyLessThanCurrent => write!(f, "the key would not increase the current priority of the value"),
+            HeapError::KeyGreaterThanCurrent => {
+                write!(f, "the key would not decrease the current priority of the value")
+            }
+        }
+    }
+}
+
+impl Error for HeapError { }
+
+/// The error returned by `FibonacciHeap::from_snapshot` when the snapshot does not describe a valid heap.
+///
+/// Each variant but `Undecodable` holds the position in `HeapSnapshot::nodes` of a node that breaks the structure.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SnapshotError {
+    /// A root or child refers to a position past the end of the nodes.
+    IndexOutOfRange(usize),
+    /// The node is reached more than once, as a root or as the child of another node.
+    NodeRepeated(usize),
+    /// The node is not a root and no node has it as a child.
+    Unreachable(usize),
+    /// The node has a degree different from its number of children.
+    DegreeMismatch(usize),
+    /// The key of the child comes before the key of the parent.
+    HeapOrder {
+        parent: usize,
+        child: usize
+    },
+    /// The value of the node is also the value of an earlier node.
+    DuplicateValue(usize),
+    /// The bytes given to `FibonacciHeap::from_bytes` do not encode a snapshot.
+    Undecodable
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SnapshotError::IndexOutOfRange(index) => write!(f, "node {} does not exist in the snapshot", index),
+            SnapshotError::NodeRepeated(index) => write!(f, "node {} appears more than once in the snapshot", index),
+            SnapshotError::Unreachable(index) => write!(f, "node {} is neither a root nor a child of any node", index),
+            SnapshotError::DegreeMismatch(index) => {
+                write!(f, "node {} has a degree different from its number of children", index)
+            },
+            SnapshotError::HeapOrder { parent, child } => {
+                write!(f, "node {} has a key that comes before the key of its parent {}", child, parent)
+            },
+            SnapshotError::DuplicateValue(index) => write!(f, "node {} has the value of an earlier node", index),
+            SnapshotError::Undecodable => write!(f, "the bytes do not encode a heap snapshot")
+        }
+    }
+}
+
+impl Error for SnapshotError { }
+
+/// The error returned by `FibonacciHeap::try_reserve` when the space could not be reserved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TryReserveError {
+    /// The requested capacity is larger than the heap can address.
+    CapacityOverflow,
+    /// The allocator could not provide the memory.
+    AllocError
+}
+
+impl fmt::Display for TryReserveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TryReserveError::CapacityOverflow => write!(f, "the requested capacity exceeds the maximum of the heap"),
+            TryReserveError::AllocError => write!(f, "the memory allocator could not reserve the space")
+        }
+    }
+}
+
+impl Error for TryReserveError { }
+
+/// The error returned by `FibonacciHeap::try_push` when there was no space for a new entry, holding the entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TryPushError<K, V> {
+    /// The key that was not inserted.
+    pub key: K,
+    /// The value that was not inserted.
+    pub value: V,
+    /// Why the space for the entry could not be had.
+    pub error: TryReserveError
+}
+
+impl<K, V> fmt::Display for TryPushError<K, V>
+    where K: fmt::Debug,
+          V: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} with priority {:?} could not be inserted, {}", self.value, self.key, self.error)
+    }
+}
+
+impl<K, V> Error for TryPushError<K, V>
+    where K: fmt::Debug,
+          V: fmt::Debug
+{ }
+
+/// The invariant of a `FibonacciHeap` found broken by `FibonacciHeap::check_invariants`.
+///
+/// Nodes are named by their position in `HeapSnapshot::nodes` of `FibonacciHeap::to_snapshot`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InvariantViolation {
+    /// The links of the node do not form the list it is in, or lead back to it more than once. `None` if the root list
+    /// itself is broken.
+    BrokenLink(Option<usize>),
+    /// The node has a degree different from the number of children linked to it.
+    DegreeMismatch {
+        node: usize,
+        expected: usize,
+        found: usize
+    },
+    /// The key of the child comes before the key of the parent.
+    HeapOrderViolated {
+        parent: usize,
+        child: usize
+    },
+    /// The node is a root but is marked as having lost a child.
+    MarkedRoot(usize),
+    /// The heap holds a different number of values than its size. `found` is the number of values in the lookup, the
+    /// node storage or the trees, whichever differs first.
+    SizeMismatch {
+        expected: usize,
+        found: usize
+    },
+    /// The node is stored in the heap but is not reached from the roots.
+    UnreachableEntry(usize),
+    /// Looking up the value of the node does not find the node.
+    LookupMismatch(usize),
+    /// The tracked minimum is not a root with the smallest key. Holds a smallest root, or `None` if the heap has no
+    /// roots but still tracks a minimum.
+    MinNotMinimal(Option<usize>),
+    /// The tracked maximum is not a node with the largest key. Holds a largest node, or `None` if the heap has no nodes
+    /// but still tracks a maximum.
+    MaxNotMaximal(Option<usize>)
+}
+
+impl fmt::Display for InvariantViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            InvariantViolation::BrokenLink(Some(node)) => write!(f, "the links of node {} are broken", node),
+            InvariantViolation::BrokenLink(None) => write!(f, "the root list is broken"),
+            InvariantViolation::DegreeMismatch { node, expected, found } => {
+                write!(f, "node {} has degree {} but {} linked children", node, expected, found)
+            },
+            InvariantViolation::HeapOrderViolated { parent, child } => {
+                write!(f, "node {} has a key that comes before the key of its parent {}", child, parent)
+            },
+            InvariantViolation::MarkedRoot(node) => write!(f, "root {} is marked", node),
+            InvariantViolation::SizeMismatch { expected, found } => {
+                write!(f, "the heap has size {} but holds {} values", expected, found)
+            },
+            InvariantViolation::UnreachableEntry(node) => write!(f, "node {} is not reachable from the roots", node),
+            InvariantViolation::LookupMismatch(node) => {
+                write!(f, "the value of node {} is not found by the lookup", node)
+            },
+            InvariantViolation::MinNotMinimal(Some(node)) => {
+                write!(f, "the minimum is not a smallest root, root {} is", node)
+            },
+            InvariantViolation::MinNotMinimal(None) => write!(f, "the heap is empty but has a minimum"),
+            InvariantViolation::MaxNotMaximal(Some(node)) => {
+                write!(f, "the maximum is not a largest node, node {} is", node)
+            },
+            InvariantViolation::MaxNotMaximal(None) => write!(f, "the heap is empty but has a maximum")
+        }
+    }
+}
+
+impl Error for InvariantViolation { }
@@ -0,0 +1,80 @@
+#[cfg(feature = "std")]
+use alloc::boxed::Box;
+#[cfg(feature = "std")]
+use alloc::sync::Arc;
+#[cfg(feature = "std")]
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use core::mem;
+#[cfg(feature = "std")]
+use std::sync::{Mutex, PoisonError};
+
+use super::fibonacci_heap::Handle;
+
+/// A structural step taken by a `FibonacciHeap`, as passed to the recorder set with `FibonacciHeap::set_recorder`.
+///
+/// Entries are named by the `Handle` that `push_with_handle` would have returned for them, so no key or value is
+/// cloned. While an entry is in the heap, `FibonacciHeap::get_handle` finds it from the handle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum HeapEvent {
+    /// The entry was added as a root of its own.
+    NodeInserted(Handle),
+    /// Consolidation linked the root `child` under the root `parent`.
+    RootLinked {
+        child: Handle,
+        parent: Handle
+    },
+    /// The entry was cut from its parent and became a root.
+    NodeCut(Handle),
+    /// Every child of the entry became a root, as its key grew or it is about to be removed. Not recorded for an entry
+    /// without children.
+    ChildrenPromoted(Handle),
+    /// The entry lost a child and was marked.
+    NodeMarked(Handle),
+    /// The mark of the entry was cleared, as it became a root or a child again.
+    NodeUnmarked(Handle),
+    /// The minimum is now the entry, or `None` once the heap is empty.
+    MinChanged(Option<Handle>),
+    /// The entry was removed from the heap.
+    NodeExtracted(Handle),
+    /// The whole heap was emptied or rebuilt at once, by `drain`, `map_priorities` or a meld, so every earlier handle
+    /// should be looked up again.
+    Reset
+}
+
+/// A recorder that keeps every event in a `Vec`.
+///
+/// Clones of the log share the same events, so one clone can be handed to the heap through `recorder` while another
+/// reads the events. Only available with the `std` feature.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default)]
+pub struct EventLog {
+    events: Arc<Mutex<Vec<HeapEvent>>>
+}
+
+#[cfg(feature = "std")]
+impl EventLog {
+    /// Creates a new empty `EventLog`.
+    pub fn new() -> EventLog {
+        EventLog::default()
+    }
+    
+    /// Returns a recorder for `FibonacciHeap::set_recorder` that appends to this log.
+    pub fn recorder(&self) -> Box<dyn FnMut(HeapEvent) + Send + Sync> {
+        let events = self.events.clone();
+        
+        Box::new(move |event| events.lock().unwrap_or_else(PoisonError::into_inner).push(event))
+    }
+    
+    /// Returns a copy of the events recorded so far, in the order they happened.
+    pub fn events(&self) -> Vec<HeapEvent> {
+        self.events.lock().unwrap_or_else(PoisonError::into_inner).clone()
+    }
+    
+    /// Removes and returns the events recorded so far, in the order they happened.
+    pub fn take(&self) -> Vec<HeapEvent> {
+        let mut events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        
+        mem::take(&mut *events)
+    }
+}
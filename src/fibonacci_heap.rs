@@ -1,239 +1,3254 @@
-use std::collections::HashMap;
-use std::hash::Hash;
+use alloc::boxed::Box;
+use alloc::collections::BinaryHeap;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::cmp;
+use core::cmp::Ordering;
+use core::fmt;
+use core::fmt::Write;
+use core::hash::Hash;
+use core::iter;
+use core::iter::FromIterator;
+use core::mem;
+use core::ops::{AddAssign, Deref, DerefMut, Index};
+use core::sync::atomic::{self, AtomicUsize};
+#[cfg(all(feature = "instrument", target_has_atomic = "64"))]
+use core::sync::atomic::AtomicU64;
+#[cfg(feature = "std")]
+use std::thread;
 
-use super::fibonacci_node::FibonacciNodeType;
-use super::fibonacci_node::FibNode;
+use super::compare::{Compare, FnComparator, KeyComparator, MinComparator};
+use super::error::{
+    DecreaseKeyByError, DecreaseKeyError, HeapError, InvariantViolation, OccupiedError, SnapshotError, TryPushError,
+    TryReserveError
+};
+use super::event::HeapEvent;
+use super::fibonacci_node::FibonacciNode;
+use super::fibonacci_node::FibonacciNodes;
+use super::fibonacci_node::NodeList;
+use super::key::CheckedSub;
+use super::lookup::Lookup;
+use super::snapshot::{HeapSnapshot, SnapshotNode};
+
+// Adds one to a counter of the instrument feature, without the feature this expands to nothing
+macro_rules! count {
+    ($heap:expr, $counter:ident) => {
+        #[cfg(feature = "instrument")]
+        $heap.counters.$counter.fetch_add(1, atomic::Ordering::Relaxed);
+    }
+}
+
+// Emits a trace event with the tracing feature, without the feature this expands to nothing
+macro_rules! trace_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!($($arg)*);
+    }
+}
 
 /// Struct that represents the [Fibonacci Heap](http://en.wikipedia.org/wiki/Fibonacci_heap) data structure.
 ///
 /// Algorithms for this are as seen in the [Introduction to Algorithms](http://en.wikipedia.org/wiki/Introduction_to_Algorithms) by Thomas H. Cormen, Charles E. Leiserson, Ronald L. Rivest, and Clifford Stein.
 ///
-/// The key, K, is the priority used to order the heap. The value, V, is the data associated with the key.
-pub struct FibonacciHeap<K, V> {
-    // Hashmap for O(1) retrieval of nodes
-    hash_map: HashMap<V, FibonacciNodeType<K, V>>,
-    // Roots is a HashMap instead of a list for O(1) removal and insertion of root nodes
-    roots: Option<HashMap<V, FibonacciNodeType<K, V>>>,
-    min: Option<FibonacciNodeType<K, V>>,
-    size: i32
+/// The key, K, is the priority used to order the heap. The value, V, is the data associated with the key. The
+/// comparator, C, decides the order of the keys, by default the key that is smallest by `Ord` is the minimum.
+///
+/// Nodes live in a `Vec` and link to each other by index, so the heap holds no `Rc` or raw pointers. It is `Send` and
+/// `Sync` whenever K, V and C are, and can be moved into another thread or shared between threads for reading.
+///
+/// The shape of the trees, and which of several entries with equal keys is extracted first, only depend on the
+/// operations done on the heap. Values are hashed to find them again, but no decision depends on the order of the
+/// hashes, so the same operations give the same results in every run.
+pub struct FibonacciHeap<K, V, C = MinComparator> {
+    // Every node of the heap, nodes and the maps below refer to nodes by their index in here
+    nodes: FibonacciNodes<K, V>,
+    // Finds the node of a value in O(1), without keeping a second copy of the value
+    hash_map: Lookup,
+    // Circular list threaded through the left and right links of the roots, for O(1) insertion, removal and melding
+    roots: NodeList,
+    min: Option<usize>,
+    // Tracked alongside min until the node holding it is removed or decreased, then looked for again by find_max
+    max: Max,
+    size: usize,
+    // Scratch space of consolidate, indexed by degree and kept between calls so extract_min does not allocate it
+    degrees: Vec<Option<usize>>,
+    // Most free node slots kept for reuse before the nodes are compacted, set by set_pool_limit
+    pool_limit: usize,
+    // Set by assert_monotone, None unless the heap checks that keys come out in order
+    monotone: Option<Monotone<K>>,
+    #[cfg(feature = "instrument")]
+    counters: Counters,
+    // Set by set_recorder, called with every structural step
+    recorder: Option<Box<dyn FnMut(HeapEvent) + Send + Sync>>,
+    compare: C
+}
+
+// The last key extracted in monotone mode, with the Clone of K captured when the mode was turned on
+#[derive(Clone)]
+struct Monotone<K> {
+    clone: fn(&K) -> K,
+    last: Option<K>
+}
+
+impl<K> Monotone<K> {
+    fn check<C>(&self, compare: &C, key: &K, message: &str)
+        where C: Compare<K>
+    {
+        if let Some(ref last) = self.last {
+            assert!(compare.compare(key, last) != Ordering::Less, "{}", message);
+        }
+    }
+    
+    fn extract<C>(&mut self, compare: &C, key: &K)
+        where C: Compare<K>
+    {
+        self.check(compare, key, "a key smaller than the last extracted key was extracted");
+        self.last = Some((self.clone)(key));
+    }
+}
+
+// The node with the largest key, Some(None) for an empty heap and None while it is not known. find_max stores what it
+// finds through a shared reference, so the index is kept in an atomic, with two indices no node can have as markers
+struct Max(AtomicUsize);
+
+const MAX_UNKNOWN: usize = usize::MAX;
+const MAX_EMPTY: usize = usize::MAX - 1;
+
+impl Max {
+    fn new(max: Option<usize>) -> Max {
+        Max(AtomicUsize::new(max.unwrap_or(MAX_EMPTY)))
+    }
+    
+    fn known(&self) -> Option<Option<usize>> {
+        match self.0.load(atomic::Ordering::Relaxed) {
+            MAX_UNKNOWN => None,
+            MAX_EMPTY => Some(None),
+            x => Some(Some(x))
+        }
+    }
+    
+    fn is(&self, x: usize) -> bool {
+        self.known() == Some(Some(x))
+    }
+    
+    fn set(&self, max: Option<usize>) {
+        self.0.store(max.unwrap_or(MAX_EMPTY), atomic::Ordering::Relaxed);
+    }
+    
+    fn forget(&self) {
+        self.0.store(MAX_UNKNOWN, atomic::Ordering::Relaxed);
+    }
+}
+
+impl Clone for Max {
+    fn clone(&self) -> Max {
+        Max(AtomicUsize::new(self.0.load(atomic::Ordering::Relaxed)))
+    }
 }
 
 impl<K, V> FibonacciHeap<K, V>
-    where K: Clone + Eq + Ord,
-          V: Clone + Eq + Hash
+    where K: Ord,
+          V: Eq + Hash
 {
     /// Creates a new empty `FibonacciHeap`.
     pub fn new() -> FibonacciHeap<K, V> {
+        FibonacciHeap::with_comparator(MinComparator)
+    }
+    
+    /// Creates a new empty `FibonacciHeap` with space for at least `capacity` values.
+    ///
+    /// The capacity only saves reallocating and rehashing while the heap fills up, the heap still grows past it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the capacity is more than `u32::MAX`, the most values a heap can hold. Inserting past that panics
+    /// too.
+    pub fn with_capacity(capacity: usize) -> FibonacciHeap<K, V> {
+        FibonacciHeap::with_capacity_and_comparator(capacity, MinComparator)
+    }
+}
+
+impl<K, V, F> FibonacciHeap<K, V, FnComparator<F>>
+    where V: Eq + Hash,
+          F: Fn(&K, &K) -> Ordering
+{
+    /// Creates a new empty `FibonacciHeap` that orders keys with the closure.
+    ///
+    /// The minimum is the key that compares as `Less` than every other key, so a reversed comparison gives a max-heap.
+    pub fn new_by(f: F) -> FibonacciHeap<K, V, FnComparator<F>> {
+        FibonacciHeap::with_comparator(FnComparator(f))
+    }
+}
+
+impl<K, V, F, T> FibonacciHeap<K, V, KeyComparator<F>>
+    where V: Eq + Hash,
+          F: Fn(&K) -> T,
+          T: Ord
+{
+    /// Creates a new empty `FibonacciHeap` that orders keys by the sort key the closure extracts from them.
+    pub fn new_by_key(f: F) -> FibonacciHeap<K, V, KeyComparator<F>> {
+        FibonacciHeap::with_comparator(KeyComparator(f))
+    }
+}
+
+impl<K, V, C> FibonacciHeap<K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    /// Creates a new empty `FibonacciHeap` that orders keys with the comparator.
+    pub fn with_comparator(compare: C) -> FibonacciHeap<K, V, C> {
+        FibonacciHeap::with_capacity_and_comparator(0, compare)
+    }
+    
+    /// Creates a new empty `FibonacciHeap` that orders keys with the comparator, with space for at least `capacity`
+    /// values.
+    pub fn with_capacity_and_comparator(capacity: usize, compare: C) -> FibonacciHeap<K, V, C> {
         FibonacciHeap{
-            hash_map: HashMap::new(),
-            roots: Some(HashMap::new()),
+            nodes: FibonacciNodes::with_capacity(capacity),
+            hash_map: Lookup::with_capacity(capacity),
+            roots: NodeList::new(),
             min: None,
-            size: 0
+            max: Max::new(None),
+            size: 0,
+            degrees: Vec::new(),
+            pool_limit: usize::MAX,
+            monotone: None,
+            #[cfg(feature = "instrument")]
+            counters: Counters::default(),
+            recorder: None,
+            compare
         }
     }
     
-    /// Inserts the value into the heap with priority key.
-    pub fn insert(&mut self, key: K, value: V) -> () {
-        let node: FibonacciNodeType<K, V> = FibNode::new(key, value.clone());
-        self.hash_map.insert(value, node.clone());
-        let min = self.min.clone();
-        
-        match min {
-            Some(ref m) => {
-                self.roots.as_mut().unwrap().insert(node.get_value(), node.clone());
-                if node.get_key() < m.get_key() {
-                    self.min = Some(node.clone());
+    /// Returns the comparator that orders the keys of the heap.
+    pub fn comparator(&self) -> &C {
+        &self.compare
+    }
+    
+    /// Returns the totals of the elementary steps the heap has taken since it was created or the counters were reset.
+    #[cfg(feature = "instrument")]
+    pub fn counters(&self) -> &Counters {
+        &self.counters
+    }
+    
+    /// Sets every counter back to zero.
+    #[cfg(feature = "instrument")]
+    pub fn reset_counters(&mut self) {
+        self.counters = Counters::default();
+    }
+    
+    /// Sets the recorder that is called with every structural step the heap takes, in the order it takes them.
+    ///
+    /// This replaces any earlier recorder. A clone of the heap starts without a recorder. `EventLog` provides a
+    /// recorder that collects the events in a `Vec`.
+    pub fn set_recorder(&mut self, recorder: Box<dyn FnMut(HeapEvent) + Send + Sync>) {
+        self.recorder = Some(recorder);
+    }
+    
+    /// Removes the recorder and returns it, or `None` if no recorder was set.
+    pub fn remove_recorder(&mut self) -> Option<Box<dyn FnMut(HeapEvent) + Send + Sync>> {
+        self.recorder.take()
+    }
+    
+    /// Turns on or off checking that keys leave the heap in ascending order.
+    ///
+    /// While this is on, the heap keeps a copy of the last key it extracted. This catches a priority being lowered
+    /// after its value was extracted, as happens with a broken `Ord` or a Dijkstra that decreases the key of a settled
+    /// node. Each insertion, extraction and decrease costs one more comparison. Keys equal to the last extracted one
+    /// are accepted. Turning the mode on again forgets the last key.
+    ///
+    /// # Panics
+    ///
+    /// While the mode is on, panics if an extraction would return a key smaller than the last extracted one, or if a
+    /// key below it is inserted or a key is decreased below it.
+    pub fn assert_monotone(&mut self, enabled: bool)
+        where K: Clone
+    {
+        self.monotone = if enabled { Some(Monotone { clone: K::clone, last: None }) } else { None };
+    }
+    
+    /// Builds a heap from the entries in O(n) time.
+    ///
+    /// As with repeated `insert`, a value that appears more than once keeps its last priority. The entries are
+    /// consolidated into trees right away, so the first `extract_min` does not have to link all of them.
+    pub fn from_entries(entries: Vec<(K, V)>) -> FibonacciHeap<K, V, C>
+        where C: Default
+    {
+        let mut heap = FibonacciHeap::with_capacity_and_comparator(entries.len(), C::default());
+        
+        for (key, value) in entries {
+            match heap.hash_map.get(&heap.nodes, &value) {
+                // Every node is still a root of its own, so its key can simply be replaced
+                Some(x) => heap.nodes[x].key = key,
+                None => {
+                    heap.add_root(key, value);
                 }
-            },
-            None => {
-                self.roots = Some(HashMap::new());
-                self.roots.as_mut().unwrap().insert(node.get_value(), node.clone());
-                self.min = Some(node.clone());
             }
         }
         
-        self.size = self.size + 1;
+        heap.consolidate();
+        // A replaced key may have been the largest, so the maximum is only looked for once it is asked for
+        heap.max.forget();
+        heap.assert_invariants();
+        
+        heap
     }
     
-    /// Peeks at the minimum of the heap.
+    /// Builds a heap from entries that are already in ascending key order.
     ///
-    /// Returns `None` if the heap is empty.
-    pub fn minimum(&self) -> Option<(K, V)> {
-        match self.min {
-            Some(ref m) => Some((m.get_key().clone(), m.get_value().clone(),)),
-            None => None 
+    /// The entries are linked into binomial trees as they arrive, each earlier entry becoming the parent of later ones,
+    /// so each key is only compared with the one before it and the first entry is the minimum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value appears more than once or if the keys are not in ascending order.
+    pub fn from_sorted_iter<I>(iter: I) -> FibonacciHeap<K, V, C>
+        where I: IntoIterator<Item = (K, V)>,
+              C: Default
+    {
+        let iter = iter.into_iter();
+        let mut heap = FibonacciHeap::with_capacity_and_comparator(iter.size_hint().0, C::default());
+        // The tree of each rank built so far, merged like the digits of a binary counter
+        let mut trees: Vec<Option<usize>> = Vec::new();
+        // The last entry so far, which is the maximum
+        let mut last = None;
+        
+        for (position, (key, value)) in iter.enumerate() {
+            // Linking a smaller key under a larger one would break the heap order for good, so this is always checked
+            if let Some(m) = last {
+                assert!(!heap.less(&key, &heap.nodes[m].key),
+                        "input of from_sorted_iter is not in ascending order, entry {} is smaller than the one before",
+                        position);
+            }
+            
+            // The value is hashed once for the check and the insertion
+            let hash = heap.hash_map.hash(&value);
+            assert!(heap.hash_map.get_hashed(&heap.nodes, hash, &value).is_none(),
+                    "value is in the input of from_sorted_iter more than once");
+            let node = heap.nodes.insert(FibonacciNode::new(key, value));
+            heap.hash_map.insert_hashed(&mut heap.nodes, node, hash);
+            heap.size += 1;
+            
+            // An earlier root is never larger than anything in a later tree, so it becomes the parent
+            let mut carry = node;
+            let mut rank = 0;
+            while let Some(Some(tree)) = trees.get(rank).cloned() {
+                trees[rank] = None;
+                heap.heap_link(carry, tree);
+                carry = tree;
+                rank += 1;
+            }
+            
+            if rank == trees.len() {
+                trees.push(Some(carry));
+            } else {
+                trees[rank] = Some(carry);
+            }
+            
+            if heap.min.is_none() {
+                heap.min = Some(node);
+            }
+            last = Some(node);
+        }
+        heap.max.set(last);
+        
+        for tree in trees.into_iter().flatten() {
+            heap.push_root(tree);
         }
+        heap.assert_invariants();
+        
+        heap
     }
     
-    // pub fn union(&mut self, other: FibonacciHeap<K, V>) -> () {
-    //   
-    // }
-    
-    /// Exctracts the minimum of the heap.
+    /// Rebuilds a heap with exactly the trees recorded in the snapshot, including which nodes are marked.
     ///
-    /// Returns `None` if the heap is empty.
-    pub fn extract_min(&mut self) -> Option<(K, V)> {
-        let z = self.min.clone();
-        let mut result = None;
-        
-        match z {
-            Some(z) => {
-                let mut children = z.get_children();
-                for child in &mut children {
-                    child.set_parent(None);
-                    self.roots.as_mut().unwrap().insert(child.get_value(), child.clone());
+    /// The snapshot is checked before anything is built: every node must be reached exactly once from the roots, have
+    /// as many children as its degree, come no later than its children in key order and hold a value of its own.
+    /// Returns `Err` describing the first node that breaks one of these rules.
+    pub fn from_snapshot(snapshot: HeapSnapshot<K, V>) -> Result<FibonacciHeap<K, V, C>, SnapshotError>
+        where C: Default
+    {
+        let mut heap = FibonacciHeap::with_comparator(C::default());
+        let HeapSnapshot { nodes, roots } = snapshot;
+        
+        // Parent of each node once the walk has reached it, None inside for a root
+        let mut parents: Vec<Option<Option<usize>>> = vec![None; nodes.len()];
+        let mut stack = Vec::with_capacity(roots.len());
+        
+        for &root in &roots {
+            match parents.get(root) {
+                None => return Err(SnapshotError::IndexOutOfRange(root)),
+                Some(&Some(_)) => return Err(SnapshotError::NodeRepeated(root)),
+                Some(&None) => { }
+            }
+            
+            parents[root] = Some(None);
+            stack.push(root);
+        }
+        
+        while let Some(x) = stack.pop() {
+            let node = &nodes[x];
+            if node.degree != node.children.len() {
+                return Err(SnapshotError::DegreeMismatch(x));
+            }
+            
+            for &child in &node.children {
+                match parents.get(child) {
+                    None => return Err(SnapshotError::IndexOutOfRange(child)),
+                    Some(&Some(_)) => return Err(SnapshotError::NodeRepeated(child)),
+                    Some(&None) => { }
                 }
-
-                self.roots.as_mut().unwrap().remove(&z.get_value());
-    
-                {            
-                    if self.roots.as_mut().unwrap().is_empty() {
-                        self.min = None;
-                    } else {
-                        let mut new_min = None;
-                        for value in self.roots.as_mut().unwrap().values() {
-                            new_min = Some(value.clone());
-                        }
-                        
-                        self.min = new_min;
-                        self.consolidate();
-                    }
+                
+                if heap.less(&nodes[child].key, &node.key) {
+                    return Err(SnapshotError::HeapOrder { parent: x, child });
                 }
                 
-                self.hash_map.remove(&z.get_value());
-                self.size = self.size -1;
-                result = Some(( z.get_key(), z.get_value() ));
-            },
-            None => { }
+                parents[child] = Some(Some(x));
+                stack.push(child);
+            }
         }
         
-        result
+        if let Some(x) = parents.iter().position(|parent| parent.is_none()) {
+            return Err(SnapshotError::Unreachable(x));
+        }
+        let parents: Vec<Option<usize>> = parents.into_iter().flatten().collect();
+        
+        // Nodes are stored at their position in the snapshot, so the children lists can be linked as they are
+        heap.nodes = FibonacciNodes::with_capacity(nodes.len());
+        heap.hash_map.reserve(nodes.len(), &heap.nodes);
+        let mut children = Vec::with_capacity(nodes.len());
+        for (x, (node, parent)) in nodes.into_iter().zip(parents).enumerate() {
+            if heap.hash_map.get(&heap.nodes, &node.value).is_some() {
+                return Err(SnapshotError::DuplicateValue(x));
+            }
+            
+            let mut entry = FibonacciNode::new(node.key, node.value);
+            // A root has no parent to lose a child for, so its mark means nothing
+            entry.marked = node.marked && parent.is_some();
+            entry.set_parent(parent);
+            let index = heap.nodes.insert(entry);
+            debug_assert_eq!(index, x);
+            
+            heap.hash_map.insert(&mut heap.nodes, index);
+            heap.size += 1;
+            children.push(node.children);
+        }
+        
+        for (x, children) in children.into_iter().enumerate() {
+            for child in children {
+                heap.nodes.push_child(x, child);
+            }
+        }
+        
+        for root in roots {
+            heap.push_root(root);
+            heap.update_bounds(root, root);
+        }
+        // Only the roots were compared, the largest key may be anywhere in the trees
+        heap.max.forget();
+        heap.assert_invariants();
+        
+        Ok(heap)
     }
     
-    /// Decreases the priority of the value to the key.
+    /// Inserts the value into the heap with priority key.
+    ///
+    /// If the value is already in the heap its entry is replaced, that is its priority is changed to key. A value is
+    /// never in the heap more than once.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.insert_node(key, value);
+        self.assert_invariants();
+    }
+    
+    /// Returns a guard for inserting many entries at once.
+    ///
+    /// The minimum and maximum are only brought up to date when the guard is dropped, so inserting through the guard
+    /// compares each new key with the smallest and largest new keys rather than with the heap. The guard borrows the
+    /// heap, so nothing can be extracted until the batch is finished. Dropping the guard finishes the batch, also while
+    /// unwinding from a panic.
+    pub fn bulk_insert(&mut self) -> BulkInsert<'_, K, V, C> {
+        BulkInsert {
+            heap: self,
+            batch: None
+        }
+    }
+    
+    /// Inserts the value into the heap with priority key and returns a handle to its entry.
+    ///
+    /// The handle stays valid until the entry is removed or moved by `shrink_to_fit`, after which the `_handle` methods
+    /// return `None` or `Err` for it. Melding with `union` or `append` moves the nodes of the other heap, so the
+    /// handles of that heap no longer find its entries, while the handles of the heap melded into stay valid. A handle
+    /// never finds an entry of any other heap, including a clone of its own.
     ///
-    /// Returns `Err` if the value is not in the heap or if the key is greater than the current priority of the value.
-    pub fn decrease_key(&mut self, value: V, key: K) -> Result<(), ()> {
-        let x;
+    /// As with `insert`, a value already in the heap has its entry replaced and the handle refers to that entry.
+    pub fn push_with_handle(&mut self, key: K, value: V) -> Handle {
+        let index = self.insert_node(key, value);
+        self.assert_invariants();
         
-        {
-            let hash_node = self.hash_map.get(&value);
-            
-            if hash_node.is_none() {
-                return Err(()); 
-            } else {
-                x = hash_node.unwrap().clone();
-            }
+        self.handle_of(index)
+    }
+    
+    // Inserts as insert does and returns the node that holds the value
+    fn insert_node(&mut self, key: K, value: V) -> usize {
+        let hash = self.hash_map.hash(&value);
+        if let Some(x) = self.hash_map.get_hashed(&self.nodes, hash, &value) {
+            self.change_node(x, key);
+            trace_event!(len = self.size, replaced = true, "insert");
+            return x;
         }
         
-        if key > x.get_key() {
-            return Err(());
+        self.insert_vacant(key, value, hash)
+    }
+    
+    // Inserts a value that is not in the heap, whose hash is already known, and returns its node
+    fn insert_vacant(&mut self, key: K, value: V, hash: u64) -> usize {
+        let node = self.add_hashed_root(key, value, hash);
+        self.update_bounds(node, node);
+        trace_event!(len = self.size, replaced = false, "insert");
+        
+        node
+    }
+    
+    // Adds a new value as a root of its own, leaving the minimum and maximum for the caller to update
+    fn add_root(&mut self, key: K, value: V) -> usize {
+        let hash = self.hash_map.hash(&value);
+        self.add_hashed_root(key, value, hash)
+    }
+    
+    fn add_hashed_root(&mut self, key: K, value: V, hash: u64) -> usize {
+        if let Some(ref monotone) = self.monotone {
+            monotone.check(&self.compare, &key, "a key below the last extracted key was inserted");
         }
+        let node = self.nodes.insert(FibonacciNode::new(key, value));
+        self.hash_map.insert_hashed(&mut self.nodes, node, hash);
+        self.push_root(node);
+        self.size += 1;
+        self.record(|heap| HeapEvent::NodeInserted(heap.handle_of(node)));
         
-        x.set_key(key);
+        node
+    }
         
-        let y = x.get_parent();
+    // Makes lo the minimum and hi the maximum if they are beyond the current ones
+    fn update_bounds(&mut self, lo: usize, hi: usize) {
+        let replace = match self.min {
+            Some(m) => self.less(&self.nodes[lo].key, &self.nodes[m].key),
+            None => true
+        };
         
-        match y {
-            Some(y_some) => {
-                if x.get_key() < y_some.get_key() {
-                    self.cut(x.clone(), y_some.clone());
-                    self.cascading_cut(y_some.clone());
-                }
-            },
-            None => { }
+        if replace {
+            self.set_min(Some(lo));
         }
         
-        if x.get_key() < self.min.clone().unwrap().get_key() {
-            self.min = Some(x);
+        // A maximum that is not known stays so, it is looked for among every key including hi
+        let replace = match self.max.known() {
+            Some(Some(m)) => self.less(&self.nodes[m].key, &self.nodes[hi].key),
+            Some(None) => true,
+            None => false
+        };
+        
+        if replace {
+            self.max.set(Some(hi));
+        }
+    }
+    
+    /// Gets the entry of the value for in-place inspection and update of its priority.
+    ///
+    /// The value is hashed and looked up once, whichever way the entry is used afterwards. A vacant entry keeps the
+    /// hash to insert the value with.
+    pub fn entry(&mut self, value: V) -> Entry<'_, K, V, C> {
+        let hash = self.hash_map.hash(&value);
+        match self.hash_map.get_hashed(&self.nodes, hash, &value) {
+            Some(node) => Entry::Occupied(OccupiedEntry { heap: self, node }),
+            None => Entry::Vacant(VacantEntry { heap: self, value, hash })
+        }
+    }
+    
+    /// Inserts the value into the heap with priority key if the value is not already in the heap.
+    ///
+    /// Returns `Err` holding the rejected key and value along with the existing priority if the value is already in
+    /// the heap, in which case the heap is left untouched.
+    pub fn try_insert(&mut self, key: K, value: V) -> Result<(), OccupiedError<K, V>>
+        where K: Clone
+    {
+        if let Some(x) = self.hash_map.get(&self.nodes, &value) {
+            return Err(OccupiedError {
+                key,
+                value,
+                existing_key: self.nodes[x].key.clone()
+            });
         }
         
+        self.insert(key, value);
+        
         Ok(())
     }
     
-    // pub fn delete(&mut self, value: V) -> () {
-    //    
-    // }
+    /// Inserts the value with priority key, or changes its priority to key if it is already in the heap.
+    ///
+    /// Returns the previous priority, or `None` if the value was newly inserted.
+    pub fn insert_or_update(&mut self, key: K, value: V) -> Option<K> {
+        match self.entry(value) {
+            Entry::Occupied(mut entry) => Some(entry.set_priority(key)),
+            Entry::Vacant(entry) => {
+                entry.insert(key);
+                None
+            }
+        }
+    }
+    
+    /// Pushes the value onto the heap with priority key.
+    ///
+    /// This is the same as `insert`, named to match `BinaryHeap::push`.
+    pub fn push(&mut self, key: K, value: V) {
+        self.insert(key, value);
+    }
     
-    fn consolidate(&mut self) -> () {
-        let base: f64 = (1.0 + 5.0f64.sqrt())/2.0;
-        let log_n = (self.size as f64).log(base) as usize + 1;
-        let mut array: Vec<Option<FibonacciNodeType<K, V>>> = (0..log_n).map(|_| None).collect();
-        
-        let roots = self.roots.take().unwrap();
-            
-        for (_, root) in roots {
-            let mut x = root.clone();
-            let mut d = x.rank();
-            loop {
-                if array[d].clone().is_none() { 
-                    break;
-                }
-                
-                let mut y = array[d].clone().unwrap();
-                if x.get_key() > y.get_key() {
-                    let n = x.clone();
-                    x = y.clone();
-                    y = n;
-                }
-                
-                self.heap_link(y.clone(), x.clone());
-                array[d] = None;
-                d = d + 1;
+    /// Inserts the value into the heap with priority key as `insert` does, returning an error rather than aborting if
+    /// the memory for a new entry cannot be had.
+    ///
+    /// Space for the entry is reserved with `try_reserve` first, so on `Err` the heap is left untouched and the key
+    /// and value are handed back. A value already in the heap only has its priority changed, which never allocates.
+    pub fn try_push(&mut self, key: K, value: V) -> Result<(), TryPushError<K, V>> {
+        let hash = self.hash_map.hash(&value);
+        if let Some(x) = self.hash_map.get_hashed(&self.nodes, hash, &value) {
+            self.change_node(x, key);
+            trace_event!(len = self.size, replaced = true, "insert");
+        } else {
+            if let Err(error) = self.try_reserve(1) {
+                return Err(TryPushError { key, value, error });
             }
-            array[d] = Some(x.clone());
+            self.insert_vacant(key, value, hash);
         }
+        self.assert_invariants();
         
-        self.min = None;
-        self.roots = Some(HashMap::new());
+        Ok(())
+    }
+    
+    /// Peeks at the minimum of the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn minimum(&self) -> Option<(&K, &V)> {
+        self.min.map(|m| {
+            let node = &self.nodes[m];
+            (&node.key, &node.value)
+        })
+    }
+    
+    /// Returns the entry with the smallest key, or `None` if the heap is empty.
+    ///
+    /// This is the same as `minimum`, named to match `BinaryHeap::peek`. Unlike `BinaryHeap` this is a
+    /// min-heap.
+    pub fn peek(&self) -> Option<(&K, &V)> {
+        self.minimum()
+    }
+    
+    /// Returns a guard giving mutable access to the key of the minimum, or `None` if the heap is empty.
+    ///
+    /// If the key is changed through the guard, the heap is restored when the guard is dropped, whether the key got
+    /// smaller or larger. This costs about as much as an `extract_min`. Dropping the guard without changing the key
+    /// leaves the heap untouched.
+    pub fn peek_mut(&mut self) -> Option<PeekMut<'_, K, V, C>> {
+        let node = self.min?;
         
-        for i in 0..log_n {
-            let min = self.min.clone();
-            let i_root = array[i].clone();
-            
-            if i_root.is_none() {
-                continue;
+        Some(PeekMut {
+            heap: self,
+            node,
+            changed: false
+        })
+    }
+    
+    /// Peeks at the maximum of the heap.
+    ///
+    /// Returns `None` if the heap is empty. The maximum is kept up to date as entries are inserted or their keys grow,
+    /// so this usually runs in O(1) time. Removing or decreasing the entry that is the maximum only forgets it, and the
+    /// next call looks through every entry in O(n) time to find it again.
+    pub fn find_max(&self) -> Option<(&K, &V)> {
+        self.max_index().map(|m| {
+            let node = &self.nodes[m];
+            (&node.key, &node.value)
+        })
+    }
+    
+    /// Returns the entry with the largest key, or `None` if the heap is empty.
+    ///
+    /// This is the same as `find_max`.
+    pub fn peek_max(&self) -> Option<(&K, &V)> {
+        self.find_max()
+    }
+    
+    /// Removes the entry with the largest key and returns it, or `None` if the heap is empty.
+    ///
+    /// As with `find_max`, this looks through every entry in O(n) time if the maximum is not known, which it never is
+    /// right after a previous `extract_max`.
+    pub fn extract_max(&mut self) -> Option<(K, V)> {
+        let x = self.max_index()?;
+        let entry = self.delete_node(x);
+        self.trim_pool();
+        self.assert_invariants();
+        
+        Some(entry)
+    }
+    
+    /// Returns the number of values the node storage can hold without reallocating.
+    ///
+    /// Removed entries leave their space in the storage until `shrink_to_fit` compacts it, so this does not drop when
+    /// the heap shrinks.
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+    
+    /// Reserves space for at least `additional` more values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the heap would need room for more than `u32::MAX` values.
+    pub fn reserve(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+        self.hash_map.reserve(additional, &self.nodes);
+    }
+    
+    /// Tries to reserve space for at least `additional` more values, returning an error rather than aborting if the
+    /// memory cannot be had.
+    ///
+    /// Once this succeeds, inserting `additional` new values does not allocate. `try_push` inserts a single value this
+    /// way. Asking for room for more than `u32::MAX` values fails with `TryReserveError::CapacityOverflow`.
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        self.nodes.try_reserve(additional)?;
+        self.hash_map.try_reserve(additional, &self.nodes)
+    }
+    
+    /// Gives back as much unused memory as possible, so the capacity is close to the length afterwards.
+    ///
+    /// The entries at the end of the node storage are moved into the space of removed ones, in O(n) time. The trees
+    /// and every key and value stay as they are, but handles to the moved entries no longer find them.
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_nodes_to(0);
+        self.hash_map.shrink_to_fit(&self.nodes);
+        self.degrees.clear();
+        self.degrees.shrink_to_fit();
+        self.assert_invariants();
+    }
+    
+    /// Returns the most node slots that removed entries leave free for reuse, `usize::MAX` unless set by
+    /// `set_pool_limit`.
+    pub fn pool_limit(&self) -> usize {
+        self.pool_limit
+    }
+    
+    /// Sets the most node slots that removed entries leave free for reuse.
+    ///
+    /// Inserting takes a free slot before it grows the node storage, so a stream of inserts and extractions does not
+    /// allocate once it has warmed up. The free slots are kept up to this limit, by default without bound. A removal
+    /// that leaves more free compacts the nodes as `shrink_to_fit` does and gives back the storage beyond the length
+    /// plus the limit, so handles to the moved entries no longer find them. Compacting takes O(n) time, which is at
+    /// most once every `limit` removals.
+    pub fn set_pool_limit(&mut self, limit: usize) {
+        self.pool_limit = limit;
+        self.trim_pool();
+        self.assert_invariants();
+    }
+    
+    // Compacts the nodes once removals leave more free slots than the pool limit
+    fn trim_pool(&mut self) {
+        if self.nodes.free_len() > self.pool_limit {
+            self.shrink_nodes_to(self.size.saturating_add(self.pool_limit));
+        }
+    }
+    
+    // Moves nodes into the free slots and gives back the node storage beyond capacity, following the moves everywhere
+    // the heap keeps an index
+    fn shrink_nodes_to(&mut self, capacity: usize) {
+        let moves = self.nodes.shrink_to(capacity);
+        for &(from, to) in &moves {
+            self.hash_map.relocate(&self.nodes, from, to);
+            self.roots.relocate(from, to);
+            if self.min == Some(from) {
+                self.min = Some(to);
             }
-            
-            if min.is_none() {
-                self.roots.as_mut().unwrap().insert(i_root.clone().unwrap().get_value(), i_root.clone().unwrap());
-                self.min = i_root;
-            } else {
-                self.roots.as_mut().unwrap().insert(i_root.clone().unwrap().get_value(), i_root.clone().unwrap());
-                if i_root.clone().unwrap().get_key() < min.unwrap().get_key() {
-                    self.min = i_root;
-                }
+            if self.max.is(from) {
+                self.max.set(Some(to));
             }
         }
+        if !moves.is_empty() {
+            self.record(|_| HeapEvent::Reset);
+        }
+    }
+    
+    /// Estimates the memory the heap has allocated, in bytes.
+    ///
+    /// This counts the capacity of the node storage and the lookup, not only the part in use, so it drops after
+    /// `shrink_to_fit`. Memory owned by the keys and values themselves, such as the buffer of a `String`, is not
+    /// included, `memory_usage_with` can add it.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        self.memory_usage_with(|_, _| 0)
+    }
+    
+    /// Estimates the memory the heap has allocated as `memory_usage` does, adding the bytes the closure reports for
+    /// each entry.
+    pub fn memory_usage_with<F>(&self, mut f: F) -> MemoryUsage
+        where F: FnMut(&K, &V) -> usize
+    {
+        let nodes = self.nodes.allocation_size();
+        let lookup = self.hash_map.allocation_size();
+        let entries = self.iter().map(|(key, value)| f(key, value)).sum();
+        let scratch = self.degrees.capacity() * mem::size_of::<Option<usize>>();
+        
+        MemoryUsage {
+            nodes,
+            lookup,
+            entries,
+            total: nodes + lookup + entries + scratch
+        }
     }
     
-    fn heap_link(&mut self, y: FibonacciNodeType<K, V>, x: FibonacciNodeType<K, V>) -> () {
-        // No need to remove from roots as self.roots has been consumed and will be replaced anyway
-        x.add_child(y.clone());
-        y.set_parent(Some(x.clone()));
-        y.set_marked(false);
+    /// Returns `true` if the value is in the heap.
+    ///
+    /// The value may be any borrowed form of `V`, as for the lookups of a `HashMap`, and the same holds for the other
+    /// methods that find a value by reference.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+        where V: Borrow<Q>,
+              Q: Eq + Hash + ?Sized
+    {
+        self.hash_map.get(&self.nodes, value).is_some()
     }
     
-    fn cut(&mut self, x: FibonacciNodeType<K, V>, y: FibonacciNodeType<K, V>) -> () {
-        y.remove_child(x.clone());
-        self.roots.as_mut().unwrap().insert(x.get_value(), x.clone());
-        x.set_parent(None);
-        x.set_marked(false);
+    /// Looks up the current priority of the value.
+    ///
+    /// Returns `None` if the value is not in the heap.
+    pub fn get_priority<Q>(&self, value: &Q) -> Option<&K>
+        where V: Borrow<Q>,
+              Q: Eq + Hash + ?Sized
+    {
+        self.hash_map.get(&self.nodes, value).map(|node| &self.nodes[node].key)
     }
     
-    fn cascading_cut(&mut self, y: FibonacciNodeType<K, V>) -> () {
-        let z = y.get_parent();
+    /// Returns the value in the heap that is equal to the given one, for modification.
+    ///
+    /// Returns `None` if the value is not in the heap. The value is found by its hash, so it must not be changed in a
+    /// way that changes its hash or what it is equal to. As with the keys of a `HashMap`, the heap would then no longer
+    /// find it.
+    pub fn get_value_mut<Q>(&mut self, value: &Q) -> Option<&mut V>
+        where V: Borrow<Q>,
+              Q: Eq + Hash + ?Sized
+    {
+        let x = self.hash_map.get(&self.nodes, value)?;
         
-        match z {
-            Some(z_some) => {
-                if !y.is_marked() {
-                    y.set_marked(true);
-                } else {
-                    self.cut(y, z_some.clone());
-                    self.cascading_cut(z_some.clone())
-                }
-            },
-            None => { }
-        }
+        Some(&mut self.nodes[x].value)
+    }
+    
+    /// Looks up the entry the handle refers to.
+    ///
+    /// Returns `None` if the entry is no longer in the heap.
+    pub fn get_handle(&self, handle: Handle) -> Option<(&K, &V)> {
+        self.handle_node(handle).map(|x| (&self.nodes[x].key, &self.nodes[x].value))
+    }
+    
+    /// Returns a handle to the entry of the value.
+    ///
+    /// Returns `None` if the value is not in the heap.
+    pub fn handle<Q>(&self, value: &Q) -> Option<Handle>
+        where V: Borrow<Q>,
+              Q: Eq + Hash + ?Sized
+    {
+        self.hash_map.get(&self.nodes, value).map(|x| self.handle_of(x))
+    }
+    
+    /// Melds `other` into this heap.
+    ///
+    /// The root lists are concatenated without consolidating, so this runs in O(1) amortized time plus the cost of
+    /// moving the nodes and lookup entries of `other` into this heap. As with `insert`, an entry of `other`
+    /// replaces the entry of this heap if the value is in both heaps. The heaps are expected to order keys the same way,
+    /// the comparator of this heap is kept.
+    pub fn union(&mut self, mut other: FibonacciHeap<K, V, C>) {
+        self.append(&mut other);
     }
+    
+    /// Moves every entry of `other` into this heap, leaving `other` empty.
+    ///
+    /// The nodes are moved as with `union` rather than re-inserted, so `decrease_key` keeps working for them and the
+    /// handles of this heap stay valid. Both heaps keep their own comparator. `other` keeps its allocations for reuse,
+    /// unless this heap has never held an entry, which then takes the storage of `other` over in O(1) time.
+    pub fn append(&mut self, other: &mut FibonacciHeap<K, V, C>) {
+        // No handle into this heap can exist yet, so the storage of both heaps can simply be swapped
+        if self.nodes.slot_count() == 0 {
+            self.nodes.swap_nodes(&mut other.nodes);
+            mem::swap(&mut self.hash_map, &mut other.hash_map);
+            mem::swap(&mut self.roots, &mut other.roots);
+            mem::swap(&mut self.min, &mut other.min);
+            mem::swap(&mut self.max, &mut other.max);
+            mem::swap(&mut self.size, &mut other.size);
+            other.record(|_| HeapEvent::Reset);
+            self.record(|_| HeapEvent::Reset);
+            self.assert_invariants();
+            
+            return;
+        }
+        
+        // Nodes of this heap whose value is also in other, found by looking up the values of the smaller heap
+        let duplicates: Vec<usize> = if self.hash_map.len() < other.hash_map.len() {
+            self.nodes.iter().filter(|(_, node)| other.contains(&node.value)).map(|(x, _)| x).collect()
+        } else {
+            other.nodes.iter().filter_map(|(_, node)| self.hash_map.get(&self.nodes, &node.value)).collect()
+        };
+        for x in duplicates {
+            self.delete_node(x);
+        }
+        
+        let roots = mem::take(&mut other.roots);
+        let min = other.min.take();
+        let max = other.max.known();
+        other.max.set(None);
+        let size = mem::replace(&mut other.size, 0);
+        let mapping = self.nodes.append(&mut other.nodes);
+        
+        self.hash_map.reserve(other.hash_map.len(), &self.nodes);
+        for node in other.hash_map.iter() {
+            self.hash_map.insert(&mut self.nodes, mapping[node]);
+        }
+        other.hash_map.clear();
+        other.record(|_| HeapEvent::Reset);
+        self.roots.append(&mut self.nodes, roots.remap(&mapping));
+        
+        if let Some(other_min) = min.map(|m| mapping[m]) {
+            let replace = match self.min {
+                Some(m) => self.less(&self.nodes[other_min].key, &self.nodes[m].key),
+                None => true
+            };
+            
+            if replace {
+                self.min = Some(other_min);
+            }
+        }
+        
+        match (self.max.known(), max) {
+            (Some(Some(m)), Some(Some(other_max))) => {
+                if self.less(&self.nodes[m].key, &self.nodes[mapping[other_max]].key) {
+                    self.max.set(Some(mapping[other_max]));
+                }
+            },
+            (Some(None), Some(other_max)) => self.max.set(other_max.map(|m| mapping[m])),
+            (Some(_), Some(None)) | (None, _) => {},
+            (Some(_), None) => self.max.forget()
+        }
+        
+        self.size += size;
+        self.record(|_| HeapEvent::Reset);
+        self.assert_invariants();
+    }
+    
+    /// Melds every heap of the iterator into a single heap.
+    ///
+    /// Each heap is melded as with `union`, so the cost depends on the number of heaps and the entries moved between
+    /// them rather than on re-inserting every entry. If a value is in several heaps, the entry with the smallest key
+    /// is kept, and of equal keys the one from the first of the heaps. The result has a new comparator made by
+    /// `Default`, which also decides which key is smaller.
+    pub fn union_all<I>(heaps: I) -> FibonacciHeap<K, V, C>
+        where I: IntoIterator<Item = FibonacciHeap<K, V, C>>,
+              C: Default
+    {
+        let mut result = FibonacciHeap::with_comparator(C::default());
+        
+        for mut heap in heaps {
+            // Entries of heap that lose to the entry of their value in result, found by looking up the values of the
+            // smaller heap. union keeps the rest over those of result
+            let losers: Vec<usize> = if result.hash_map.len() < heap.hash_map.len() {
+                result.nodes.iter()
+                    .filter_map(|(_, node)| {
+                        let y = heap.hash_map.get(&heap.nodes, &node.value)?;
+                        if result.less(&heap.nodes[y].key, &node.key) { None } else { Some(y) }
+                    })
+                    .collect()
+            } else {
+                heap.nodes.iter()
+                    .filter(|(_, node)| match result.hash_map.get(&result.nodes, &node.value) {
+                        Some(x) => !result.less(&node.key, &result.nodes[x].key),
+                        None => false
+                    })
+                    .map(|(y, _)| y)
+                    .collect()
+            };
+            for y in losers {
+                heap.delete_node(y);
+            }
+            
+            result.union(heap);
+        }
+        
+        result
+    }
+    
+    /// Exctracts the minimum of the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn extract_min(&mut self) -> Option<(K, V)> {
+        let z = self.min?;
+        self.check_extracted(z);
+        let entry = self.remove_min(z);
+        trace_event!(len = self.size, roots = self.roots.len(), "extract_min");
+        self.trim_pool();
+        self.assert_invariants();
+        
+        Some(entry)
+    }
+    
+    /// Removes the entry with the smallest key and returns it, or `None` if the heap is empty.
+    ///
+    /// This is the same as `extract_min`, named to match `BinaryHeap::pop`. Unlike `BinaryHeap` this is a
+    /// min-heap.
+    pub fn pop(&mut self) -> Option<(K, V)> {
+        self.extract_min()
+    }
+    
+    /// Extracts the `n` smallest entries in ascending order, or every entry if the heap holds fewer than `n`.
+    ///
+    /// The result is the same as `n` calls to `extract_min`, but the remaining trees are only consolidated once.
+    pub fn pop_batch(&mut self, n: usize) -> Vec<(K, V)> {
+        let n = cmp::min(n, self.len());
+        
+        if n == 0 {
+            return Vec::new();
+        }
+        
+        let removed = self.smallest_nodes(n);
+        if let Some(&last) = removed.last() {
+            self.check_extracted(removed[0]);
+            self.check_extracted(last);
+        }
+        let entries = self.remove_nodes(removed);
+        self.trim_pool();
+        self.assert_invariants();
+        
+        entries
+    }
+        
+    /// Extracts every entry whose key is equal to the minimum's key.
+    ///
+    /// Returns an empty `Vec` if the heap is empty. The remaining trees are consolidated once.
+    pub fn pop_all_min(&mut self) -> Vec<(K, V)> {
+        let min = match self.min {
+            Some(min) => min,
+            None => return Vec::new()
+        };
+            
+        // Nothing is smaller than the minimum, so every ancestor of a node with the minimum's key has the same key
+        let mut removed: Vec<usize> = self.roots.iter(&self.nodes)
+            .filter(|&root| self.equal(&self.nodes[root].key, &self.nodes[min].key))
+            .collect();
+        let mut i = 0;
+        while i < removed.len() {
+            let x = removed[i];
+            removed.extend(self.nodes[x].children.iter(&self.nodes)
+                .filter(|&child| self.equal(&self.nodes[child].key, &self.nodes[min].key)));
+            i += 1;
+        }
+        
+        self.check_extracted(min);
+        let entries = self.remove_nodes(removed);
+        self.trim_pool();
+        self.assert_invariants();
+        
+        entries
+    }
+    
+    /// Returns the `n` smallest entries in ascending order, or every entry if the heap holds fewer than `n`.
+    ///
+    /// The heap is left untouched. Only the trees that can hold one of the `n` smallest entries are visited, so this
+    /// runs in O(r + n log n) for a heap with r roots.
+    pub fn smallest_n(&self, n: usize) -> Vec<(&K, &V)> {
+        self.smallest_nodes(n).into_iter()
+            .map(|x| (&self.nodes[x].key, &self.nodes[x].value))
+            .collect()
+    }
+    
+    /// Returns an iterator over every entry of the heap in ascending key order.
+    ///
+    /// The heap is left untouched. Entries are found lazily with a best-first walk from the roots, so taking the first
+    /// k entries runs in O(r + k log(r + k)) time for a heap with r roots.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.sorted_nodes().map(move |x| (&self.nodes[x].key, &self.nodes[x].value))
+    }
+    
+    /// Returns an iterator over the root of every tree of the heap in no particular order.
+    ///
+    /// Roots are only linked together by `extract_min` and the other operations that consolidate, so after plain
+    /// inserts every entry is a root of its own.
+    pub fn roots(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.roots.iter(&self.nodes).map(move |root| (&self.nodes[root].key, &self.nodes[root].value))
+    }
+    
+    /// Returns the number of trees in the heap.
+    pub fn num_roots(&self) -> usize {
+        self.roots.len()
+    }
+    
+    /// Measures the shape of the trees in a single pass over the nodes.
+    ///
+    /// After inserts alone every node is a root of degree 0. Consolidation keeps the degree of every node below
+    /// log<sub>φ</sub>(n) + 1 for a heap of n nodes.
+    pub fn stats(&self) -> HeapStats {
+        let mut degrees = Vec::new();
+        let mut marked = 0;
+        
+        for (_, node) in self.nodes.iter() {
+            let degree = node.rank();
+            if degree >= degrees.len() {
+                degrees.resize(degree + 1, 0);
+            }
+            degrees[degree] += 1;
+            
+            if node.marked {
+                marked += 1;
+            }
+        }
+        
+        HeapStats {
+            roots: self.roots.len(),
+            max_degree: degrees.len().saturating_sub(1),
+            degrees,
+            marked,
+            nodes: self.nodes.len()
+        }
+    }
+    
+    /// Records the exact shape of the heap: every node with its key, value, degree, mark and children, and the roots.
+    ///
+    /// Serializing the entries loses the trees, so a heap rebuilt from them does different work in its next
+    /// operations. A heap restored from the snapshot with `from_snapshot` has the same trees as this one.
+    pub fn to_snapshot(&self) -> HeapSnapshot<K, V>
+        where K: Clone,
+              V: Clone
+    {
+        let mut positions = vec![usize::MAX; self.nodes.slot_count()];
+        for (position, (index, _)) in self.nodes.iter().enumerate() {
+            positions[index] = position;
+        }
+        
+        let nodes = self.nodes.iter()
+            .map(|(_, node)| SnapshotNode {
+                key: node.key.clone(),
+                value: node.value.clone(),
+                degree: node.rank(),
+                marked: node.marked,
+                children: node.children.iter(&self.nodes).map(|child| positions[child]).collect()
+            })
+            .collect();
+        let roots = self.roots.iter(&self.nodes).map(|root| positions[root]).collect();
+        
+        HeapSnapshot { nodes, roots }
+    }
+    
+    /// Walks every tree of the heap in preorder, calling the closure for each entry with its place in the tree.
+    ///
+    /// Trees are visited one after another from their root, and children in the order they were linked. This order
+    /// only depends on the operations done on the heap, so it can be used to compare structures.
+    pub fn visit<F>(&self, mut f: F)
+        where F: FnMut(&K, &V, VisitInfo<'_, K>)
+    {
+        // Only debug builds check that no node is reached twice, which would mean the links form a cycle
+        let mut visited = if cfg!(debug_assertions) { vec![false; self.nodes.slot_count()] } else { Vec::new() };
+        let mut stack: Vec<(usize, usize)> = self.roots.iter(&self.nodes).map(|root| (root, 0)).collect();
+        stack.reverse();
+        
+        while let Some((x, depth)) = stack.pop() {
+            if cfg!(debug_assertions) {
+                assert!(!visited[x], "node is reachable more than once");
+                visited[x] = true;
+            }
+            
+            let node = &self.nodes[x];
+            f(&node.key, &node.value, VisitInfo {
+                depth,
+                degree: node.rank(),
+                marked: node.marked,
+                parent_key: node.parent().map(|parent| &self.nodes[parent].key)
+            });
+            
+            stack.extend(node.children.iter_rev(&self.nodes).map(|child| (child, depth + 1)));
+        }
+    }
+    
+    /// Renders the forest of the heap as a Graphviz DOT digraph.
+    ///
+    /// This is `write_dot` into a `String`.
+    pub fn to_dot(&self) -> String
+        where K: fmt::Debug,
+              V: fmt::Debug
+    {
+        let mut dot = String::new();
+        self.write_dot(&mut dot).expect("writing to a String does not fail");
+        
+        dot
+    }
+    
+    /// Writes the forest of the heap as a Graphviz DOT digraph.
+    ///
+    /// Every entry is a node labeled with its key and value as `Debug` prints them, with an edge to each of its
+    /// children. Marked nodes are filled gray, the roots are drawn side by side and a dashed edge from `min` points at
+    /// the minimum. Nodes are numbered in the order `visit` reaches them, so the same heap always gives the same
+    /// output.
+    pub fn write_dot<W>(&self, out: &mut W) -> fmt::Result
+        where K: fmt::Debug,
+              V: fmt::Debug,
+              W: fmt::Write
+    {
+        writeln!(out, "digraph FibonacciHeap {{")?;
+        
+        let mut ids = vec![0; self.nodes.slot_count()];
+        let mut next = 0;
+        let mut stack: Vec<(usize, Option<usize>)> = self.roots.iter_rev(&self.nodes)
+            .map(|root| (root, None))
+            .collect();
+        while let Some((x, parent)) = stack.pop() {
+            ids[x] = next;
+            next += 1;
+            
+            let node = &self.nodes[x];
+            write!(out, "    n{} [label=\"", ids[x])?;
+            write!(DotEscape(&mut *out), "{:?}: {:?}", node.key, node.value)?;
+            write!(out, "\"")?;
+            if node.marked {
+                write!(out, ", style=filled, fillcolor=gray")?;
+            }
+            writeln!(out, "];")?;
+            
+            if let Some(parent) = parent {
+                writeln!(out, "    n{} -> n{};", ids[parent], ids[x])?;
+            }
+            
+            stack.extend(node.children.iter_rev(&self.nodes).map(|child| (child, Some(x))));
+        }
+        
+        if !self.roots.is_empty() {
+            write!(out, "    {{ rank=same;")?;
+            for root in self.roots.iter(&self.nodes) {
+                write!(out, " n{};", ids[root])?;
+            }
+            writeln!(out, " }}")?;
+        }
+        
+        if let Some(m) = self.min {
+            writeln!(out, "    min [shape=plaintext];")?;
+            writeln!(out, "    min -> n{} [style=dashed];", ids[m])?;
+        }
+        
+        writeln!(out, "}}")
+    }
+    
+    /// Draws every tree of the heap as an indented tree for reading in a terminal.
+    ///
+    /// Each entry is a line with its key and value as `Debug` prints them, under its parent. Marked entries end with
+    /// `*`, the minimum starts with `min→` and a last line gives the length and the number of roots.
+    pub fn dump(&self) -> String
+        where K: fmt::Debug,
+              V: fmt::Debug
+    {
+        self.dump_truncated(usize::MAX)
+    }
+    
+    /// Draws the trees as `dump` does, stopping after `max_nodes` entries.
+    ///
+    /// The entries left out are counted on a line of their own before the summary.
+    pub fn dump_truncated(&self, max_nodes: usize) -> String
+        where K: fmt::Debug,
+              V: fmt::Debug
+    {
+        let mut out = String::new();
+        // For each ancestor of the current entry below the roots, whether it is the last child of its parent
+        let mut lasts: Vec<bool> = Vec::new();
+        let mut stack: Vec<(usize, usize, bool)> = self.roots.iter_rev(&self.nodes)
+            .map(|root| (root, 0, false))
+            .collect();
+        let mut shown = 0;
+        
+        while let Some((x, depth, last)) = stack.pop() {
+            if shown == max_nodes {
+                break;
+            }
+            shown += 1;
+            
+            if depth > 0 {
+                lasts.truncate(depth - 1);
+                for &done in &lasts {
+                    out.push_str(if done { "    " } else { "│   " });
+                }
+                out.push_str(if last { "└── " } else { "├── " });
+                lasts.push(last);
+            } else if self.min == Some(x) {
+                out.push_str("min→ ");
+            }
+            
+            let node = &self.nodes[x];
+            write!(out, "{:?}: {:?}", node.key, node.value).expect("writing to a String does not fail");
+            if node.marked {
+                out.push('*');
+            }
+            out.push('\n');
+            
+            // The last child is pushed first, so it is the last one popped
+            stack.extend(node.children.iter_rev(&self.nodes).enumerate().map(|(i, child)| (child, depth + 1, i == 0)));
+        }
+        
+        if shown < self.size {
+            writeln!(out, "… {} more", self.size - shown).expect("writing to a String does not fail");
+        }
+        write!(out, "len: {}, roots: {}", self.size, self.roots.len()).expect("writing to a String does not fail");
+        
+        out
+    }
+    
+    /// Checks the structure of the heap and returns the first invariant found broken.
+    ///
+    /// Every node is visited, so this runs in O(n) time. The heap only breaks through a comparator, `Eq` or `Hash` that
+    /// is inconsistent or panics, or through a bug in this crate. The `debug-invariants` feature runs this check after
+    /// every operation that modifies the heap.
+    pub fn check_invariants(&self) -> Result<(), InvariantViolation> {
+        let nodes = &self.nodes;
+        let live = |x: usize| nodes.generation(x).is_some();
+        // Only computed once something is broken
+        let position = |x: usize| nodes.iter().take_while(|&(index, _)| index < x).count();
+        
+        // Follows the right links from the head, checking the left links on the way back, and counts the nodes
+        let walk = |list: &NodeList, owner: Option<usize>| -> Result<usize, InvariantViolation> {
+            let head = match list.first() {
+                Some(head) => head,
+                None => return Ok(0)
+            };
+            if !live(head) {
+                return Err(InvariantViolation::BrokenLink(owner.map(position)));
+            }
+            
+            let mut x = head;
+            let mut len = 0;
+            loop {
+                let right = nodes[x].right();
+                if !live(right) || nodes[right].left() != x || len == nodes.len() {
+                    return Err(InvariantViolation::BrokenLink(Some(position(x))));
+                }
+                
+                len += 1;
+                x = right;
+                if x == head {
+                    return Ok(len);
+                }
+            }
+        };
+        
+        if walk(&self.roots, None)? != self.roots.len() {
+            return Err(InvariantViolation::BrokenLink(None));
+        }
+        
+        let mut reached = vec![false; nodes.slot_count()];
+        let mut count = 0;
+        let mut stack: Vec<(usize, Option<usize>)> = self.roots.iter(nodes).map(|root| (root, None)).collect();
+        while let Some((x, parent)) = stack.pop() {
+            let node = &nodes[x];
+            if reached[x] || node.parent() != parent {
+                return Err(InvariantViolation::BrokenLink(Some(position(x))));
+            }
+            reached[x] = true;
+            count += 1;
+            
+            match parent {
+                Some(y) if self.less(&node.key, &nodes[y].key) => {
+                    return Err(InvariantViolation::HeapOrderViolated { parent: position(y), child: position(x) });
+                },
+                None if node.marked => return Err(InvariantViolation::MarkedRoot(position(x))),
+                _ => { }
+            }
+            
+            let found = walk(&node.children, Some(x))?;
+            if found != node.rank() {
+                return Err(InvariantViolation::DegreeMismatch { node: position(x), expected: node.rank(), found });
+            }
+            
+            stack.extend(node.children.iter(nodes).map(|child| (child, Some(x))));
+        }
+        
+        for &found in &[self.hash_map.len(), nodes.len()] {
+            if found != self.size {
+                return Err(InvariantViolation::SizeMismatch { expected: self.size, found });
+            }
+        }
+        
+        // Every node reached is stored and reached once, so once the storage holds size nodes and count is less than
+        // that, some stored node is not reached
+        for (x, node) in nodes.iter() {
+            if !reached[x] {
+                return Err(InvariantViolation::UnreachableEntry(position(x)));
+            } else if self.hash_map.get(nodes, &node.value) != Some(x) {
+                return Err(InvariantViolation::LookupMismatch(position(x)));
+            }
+        }
+        debug_assert_eq!(count, self.size);
+        
+        let smallest = self.roots.iter(nodes).min_by(|&a, &b| self.compare.compare(&nodes[a].key, &nodes[b].key));
+        let minimal = match (self.min, smallest) {
+            (Some(m), Some(r)) => reached[m] && nodes[m].parent().is_none() && !self.less(&nodes[r].key, &nodes[m].key),
+            (m, r) => m.is_none() && r.is_none()
+        };
+        if !minimal {
+            return Err(InvariantViolation::MinNotMinimal(smallest.map(position)));
+        }
+        
+        // A maximum that is not known has nothing to check until find_max looks for it
+        if let Some(max) = self.max.known() {
+            let largest = self.max_node();
+            let maximal = match (max, largest) {
+                (Some(m), Some(x)) => live(m) && !self.less(&nodes[m].key, &nodes[x].key),
+                (m, x) => m.is_none() && x.is_none()
+            };
+            if !maximal {
+                return Err(InvariantViolation::MaxNotMaximal(largest.map(position)));
+            }
+        }
+        
+        Ok(())
+    }
+    
+    /// Returns an iterator over the key of every entry in no particular order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+    
+    /// Returns an iterator over every value in the heap in no particular order.
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+    
+    /// Removes every entry from the heap, keeping its allocations for reuse.
+    pub fn clear(&mut self) {
+        self.drain();
+        self.assert_invariants();
+    }
+    
+    /// Removes every entry from the heap and returns them in no particular order.
+    ///
+    /// The heap is empty as soon as this is called, keeping its allocations for reuse. Entries the iterator has not
+    /// reached when it is dropped are dropped with it, and if the iterator is leaked they are leaked but the heap is
+    /// still empty.
+    pub fn drain(&mut self) -> Drain<'_, K, V> {
+        let remaining = self.len();
+        
+        self.hash_map.clear();
+        self.roots = NodeList::new();
+        self.min = None;
+        self.max.set(None);
+        self.size = 0;
+        self.record(|_| HeapEvent::Reset);
+        
+        Drain {
+            slots: self.nodes.drain(),
+            remaining
+        }
+    }
+    
+    /// Removes every entry from the heap and returns them in ascending key order.
+    ///
+    /// Each entry is extracted as the iterator reaches it. Unlike `drain_below`, the entries the iterator has not reached
+    /// when it is dropped are removed as well, so the heap is empty afterwards either way.
+    pub fn drain_sorted(&mut self) -> DrainSorted<'_, K, V, C> {
+        DrainSorted {
+            heap: self
+        }
+    }
+    
+    /// Consumes the heap into a `Vec` of its entries in ascending key order.
+    ///
+    /// Rather than extracting the minimum over and over, the nodes are moved out in no particular order and sorted
+    /// once, which runs in O(n log n) time without any consolidation.
+    pub fn into_sorted_vec(mut self) -> Vec<(K, V)> {
+        let mut entries = Vec::with_capacity(self.len());
+        entries.extend(self.drain());
+        
+        let compare = &self.compare;
+        entries.sort_by(|a, b| compare.compare(&a.0, &b.0));
+        
+        entries
+    }
+    
+    /// Consumes the heap into a `Vec` of its entries in no particular order.
+    ///
+    /// The nodes are moved out as they are, nothing is cloned or consolidated.
+    pub fn into_vec(mut self) -> Vec<(K, V)> {
+        self.drain().collect()
+    }
+    
+    /// Consumes the heap into a `Vec` of its values in no particular order.
+    pub fn into_values(mut self) -> Vec<V> {
+        self.drain().map(|(_, value)| value).collect()
+    }
+    
+    /// Extracts the minimum only if the predicate holds for it.
+    ///
+    /// The predicate is called with references to the minimum's key and value before anything is modified. Returns
+    /// `None` if the heap is empty or the predicate returns `false`.
+    pub fn pop_if<F>(&mut self, f: F) -> Option<(K, V)>
+        where F: FnOnce(&K, &V) -> bool
+    {
+        match self.minimum() {
+            Some((key, value)) if f(key, value) => { },
+            _ => return None
+        }
+        
+        self.extract_min()
+    }
+    
+    /// Extracts entries in ascending order for as long as the minimum key is smaller than the bound.
+    ///
+    /// Each entry is extracted as the iterator reaches it, so dropping the iterator early leaves the rest in the heap.
+    pub fn drain_below<'a>(&'a mut self, bound: &'a K) -> impl Iterator<Item = (K, V)> + 'a {
+        iter::from_fn(move || {
+            match self.minimum() {
+                Some((key, _)) if self.less(key, bound) => { },
+                _ => return None
+            }
+            
+            self.extract_min()
+        })
+    }
+    
+    /// Extracts entries in ascending order for as long as the minimum key is smaller than or equal to the bound.
+    ///
+    /// Each entry is extracted as the iterator reaches it, so dropping the iterator early leaves the rest in the heap.
+    pub fn drain_at_or_below<'a>(&'a mut self, bound: &'a K) -> impl Iterator<Item = (K, V)> + 'a {
+        iter::from_fn(move || {
+            match self.minimum() {
+                Some((key, _)) if !self.less(bound, key) => { },
+                _ => return None
+            }
+            
+            self.extract_min()
+        })
+    }
+    
+    /// Inserts the value with priority key and then extracts the minimum, in one operation.
+    ///
+    /// If the key is no larger than the current minimum the pair is handed straight back without touching the heap.
+    pub fn push_pop(&mut self, key: K, value: V) -> (K, V) {
+        let smaller = match self.min {
+            Some(m) => self.less(&self.nodes[m].key, &key),
+            None => false
+        };
+        
+        if self.contains(&value) {
+            // The existing entry has to be replaced first, exactly as insert would
+            self.insert(key, value);
+            return self.extract_min().expect("heap is not empty");
+        } else if !smaller {
+            if let Some(ref mut monotone) = self.monotone {
+                monotone.extract(&self.compare, &key);
+            }
+            return (key, value);
+        }
+        
+        self.pop_push(key, value).expect("heap has a minimum")
+    }
+    
+    /// Extracts the minimum and then inserts the value with priority key, in one operation.
+    ///
+    /// Returns the old minimum, or `None` if the heap was empty. If the key is no larger than the old minimum the new
+    /// entry takes over the old minimum's node and no consolidation is needed.
+    pub fn pop_push(&mut self, key: K, value: V) -> Option<(K, V)> {
+        let m = match self.min {
+            // The new entry can only take over the minimum's node if its value has no other node
+            Some(m) if !self.less(&self.nodes[m].key, &key) && (self.nodes[m].value == value || !self.contains(&value)) => m,
+            _ => {
+                let min = self.extract_min();
+                self.insert(key, value);
+                return min;
+            }
+        };
+        
+        self.check_extracted(m);
+        
+        // Every child of m is at least as large as its old key, so the heap property still holds
+        let old_key = mem::replace(&mut self.nodes[m].key, key);
+        self.hash_map.remove(&self.nodes, m);
+        let old_value = mem::replace(&mut self.nodes[m].value, value);
+        self.hash_map.insert(&mut self.nodes, m);
+        // The old entry is gone, so handles to it must not find the new one
+        let old = self.handle_of(m);
+        self.nodes.renew(m);
+        self.record(|_| HeapEvent::NodeExtracted(old));
+        self.record(|heap| HeapEvent::NodeInserted(heap.handle_of(m)));
+        self.record(|heap| HeapEvent::MinChanged(Some(heap.handle_of(m))));
+        
+        if self.max.is(m) && self.less(&self.nodes[m].key, &old_key) {
+            self.max.forget();
+        }
+        self.assert_invariants();
+        
+        Some((old_key, old_value))
+    }
+    
+    /// Replaces the minimum with the value at priority key.
+    ///
+    /// Returns the old minimum, or `None` if the heap was empty in which case this is a plain `insert`. This is the same
+    /// as `pop_push`.
+    pub fn replace_min(&mut self, key: K, value: V) -> Option<(K, V)> {
+        self.pop_push(key, value)
+    }
+    
+    /// Decreases the priority of the value to the key.
+    ///
+    /// Returns the previous priority, or `Err` holding the rejected key if the value is not in the heap or if the key is
+    /// greater than the current priority of the value.
+    pub fn decrease_key(&mut self, value: V, key: K) -> Result<K, DecreaseKeyError<K>> {
+        let (x, key) = self.decrease_target(&value, key)?;
+        let old_key = self.decrease_to(x, key);
+        self.assert_invariants();
+        
+        Ok(old_key)
+    }
+    
+    /// Decreases the priority of each value to its key, in order.
+    ///
+    /// Returns the result `decrease_key` would have returned for each update. The new minimum is only looked for once,
+    /// after every update has been applied. A cut that would cascade is held back until then as well, so a chain
+    /// of marked ancestors is walked once however many of its nodes lose a child, and not at all if it became a root.
+    pub fn decrease_keys<I>(&mut self, updates: I) -> Vec<Result<K, DecreaseKeyError<K>>>
+        where I: IntoIterator<Item = (V, K)>
+    {
+        let updates = updates.into_iter();
+        let mut results = Vec::with_capacity(updates.size_hint().0);
+        // Keys only ever get smaller here, so the smallest decreased node so far stays a candidate
+        let mut candidate: Option<usize> = None;
+        // Marked parents that lost another child, which have to be cut once the updates are done
+        let mut cascades = Vec::new();
+        
+        for (value, key) in updates {
+            let (x, key) = match self.decrease_target(&value, key) {
+                Ok(target) => target,
+                Err(error) => {
+                    results.push(Err(error));
+                    continue;
+                }
+            };
+            
+            let (old_key, parent) = self.decrease_and_cut(x, key);
+            results.push(Ok(old_key));
+            
+            match parent {
+                Some(y) if self.nodes[y].marked => cascades.push(y),
+                Some(y) => self.cascading_cut(y),
+                None => { }
+            }
+            
+            let replace = match candidate {
+                Some(c) => self.less(&self.nodes[x].key, &self.nodes[c].key),
+                None => true
+            };
+            
+            if replace {
+                candidate = Some(x);
+            }
+        }
+        
+        // A parent queued twice, or cut by an earlier cascade or its own decrease, is a root by now and is skipped
+        for &y in &cascades {
+            if let Some(z) = self.nodes[y].parent() {
+                count!(self, cascading_cuts);
+                self.cut(y, z);
+                self.cascading_cut(z);
+            }
+        }
+        trace_event!(len = self.size, updates = results.len(), cascades = cascades.len(), "decrease_keys");
+        
+        if let (Some(c), Some(m)) = (candidate, self.min) {
+            if self.less(&self.nodes[c].key, &self.nodes[m].key) {
+                self.set_min(Some(c));
+            }
+        }
+        self.assert_invariants();
+        
+        results
+    }
+    
+    /// Decreases the priority of the value by delta.
+    ///
+    /// Returns the new priority, or `Err` holding the delta if the value is not in the heap, if the delta would not make
+    /// the priority smaller or if the subtraction overflows.
+    pub fn decrease_key_by(&mut self, value: &V, delta: K) -> Result<K, DecreaseKeyByError<K>>
+        where K: CheckedSub + Clone
+    {
+        let x = match self.hash_map.get(&self.nodes, value) {
+            Some(hash_node) => hash_node,
+            None => return Err(DecreaseKeyByError::NotFound(delta))
+        };
+        
+        let key = match self.nodes[x].key.checked_sub(&delta) {
+            Some(key) => key,
+            None => return Err(DecreaseKeyByError::Overflow(delta))
+        };
+        
+        if !self.less(&key, &self.nodes[x].key) {
+            return Err(DecreaseKeyByError::NotDecreased(delta));
+        }
+        
+        self.decrease_to(x, key.clone());
+        self.assert_invariants();
+        
+        Ok(key)
+    }
+    
+    /// Decreases the priority of the entry the handle refers to to the key.
+    ///
+    /// Returns the previous priority, or `Err` holding the rejected key if the entry is no longer in the heap or if the
+    /// key is greater than its current priority.
+    pub fn decrease_key_handle(&mut self, handle: Handle, key: K) -> Result<K, DecreaseKeyError<K>> {
+        let x = match self.handle_node(handle) {
+            Some(x) => x,
+            None => return Err(DecreaseKeyError::NotFound(key))
+        };
+        
+        if self.less(&self.nodes[x].key, &key) {
+            return Err(DecreaseKeyError::KeyGreaterThanCurrent(key));
+        }
+        
+        let old_key = self.decrease_to(x, key);
+        self.assert_invariants();
+        
+        Ok(old_key)
+    }
+    
+    /// Increases the priority of the value to the key.
+    ///
+    /// Returns `Err(HeapError::NotFound)` if the value is not in the heap, or `Err(HeapError::KeyLessThanCurrent)` if
+    /// the key is less than the current priority of the value.
+    pub fn increase_key(&mut self, value: &V, key: K) -> Result<(), HeapError> {
+        let x = match self.hash_map.get(&self.nodes, value) {
+            Some(hash_node) => hash_node,
+            None => return Err(HeapError::NotFound)
+        };
+        
+        if self.less(&key, &self.nodes[x].key) {
+            return Err(HeapError::KeyLessThanCurrent);
+        } else if self.equal(&key, &self.nodes[x].key) {
+            return Ok(());
+        }
+        
+        self.increase_node(x, key);
+        self.assert_invariants();
+        
+        Ok(())
+    }
+    
+    /// Changes the priority of the value to the key, whether the key is smaller or larger than the current priority.
+    ///
+    /// Returns the previous priority or `Err(HeapError::NotFound)` if the value is not in the heap.
+    pub fn change_priority<Q>(&mut self, value: &Q, key: K) -> Result<K, HeapError>
+        where V: Borrow<Q>,
+              Q: Eq + Hash + ?Sized
+    {
+        let x = match self.hash_map.get(&self.nodes, value) {
+            Some(hash_node) => hash_node,
+            None => return Err(HeapError::NotFound)
+        };
+        
+        let old_key = self.change_node(x, key);
+        self.assert_invariants();
+        
+        Ok(old_key)
+    }
+    
+    /// Changes the priority of the value to the key computed by the closure from the current priority.
+    ///
+    /// The closure runs before anything is modified, so a panic inside it leaves the heap as it was. Returns `Err` if
+    /// the value is not in the heap.
+    pub fn update_key_with<F>(&mut self, value: &V, f: F) -> Result<(), HeapError>
+        where F: FnOnce(&K) -> K
+    {
+        let x = match self.hash_map.get(&self.nodes, value) {
+            Some(hash_node) => hash_node,
+            None => return Err(HeapError::NotFound)
+        };
+        
+        let key = f(&self.nodes[x].key);
+        self.change_node(x, key);
+        self.assert_invariants();
+        
+        Ok(())
+    }
+    
+    /// Adds delta to the priority of every value in the heap.
+    ///
+    /// The heap is rebuilt in O(n) time as with `map_priorities`. Adding the same delta does not keep the order of
+    /// every comparator, such as one comparing absolute values, nor of keys that wrap around when they overflow, so
+    /// the trees cannot be kept as they are. For keys whose order the delta does keep, `OffsetFibonacciHeap` adds it
+    /// in O(1) time instead.
+    pub fn add_to_all_keys(&mut self, delta: K)
+        where K: AddAssign + Clone
+    {
+        self.map_priorities(|key, _| {
+            let mut key = key.clone();
+            key += delta.clone();
+            key
+        });
+    }
+    
+    /// Retains only the entries for which the predicate returns `true`.
+    ///
+    /// Every other entry is removed and the remaining trees are consolidated once. If the predicate returns `true`
+    /// for every entry the heap is not modified at all.
+    pub fn retain<F>(&mut self, mut f: F)
+        where F: FnMut(&K, &V) -> bool
+    {
+        self.remove_where(|key, value| !f(key, value));
+        self.trim_pool();
+        self.assert_invariants();
+    }
+        
+    /// Moves every entry for which the predicate returns `true` into a new heap and returns it.
+    ///
+    /// The remaining trees of this heap are consolidated once, the moved entries are each their own tree in the new heap.
+    pub fn split_off<F>(&mut self, f: F) -> FibonacciHeap<K, V, C>
+        where F: FnMut(&K, &V) -> bool,
+              C: Clone
+    {
+        let mut other = FibonacciHeap::with_comparator(self.compare.clone());
+        
+        for (key, value) in self.remove_where(f) {
+            other.insert(key, value);
+        }
+        self.trim_pool();
+        self.assert_invariants();
+        
+        other
+    }
+    
+    /// Replaces the priority of every value with the key computed by the closure from the current key and the value.
+    ///
+    /// Rather than moving each entry separately, the heap is rebuilt in O(n) time with every entry as its own tree. The
+    /// trees are taken apart before the closure is called, so if it panics the heap is left valid, with the keys
+    /// computed so far.
+    pub fn map_priorities<F>(&mut self, mut f: F)
+        where F: FnMut(&K, &V) -> K
+    {
+        let mut indices = Vec::with_capacity(self.nodes.len());
+        for (index, node) in self.nodes.iter_mut() {
+            node.marked = false;
+            node.set_parent(None);
+            node.children = NodeList::new();
+            indices.push(index);
+        }
+        
+        self.roots = NodeList::new();
+        for index in indices {
+            self.push_root(index);
+        }
+        
+        let rebuild = Rebuild { heap: self };
+        for (_, node) in rebuild.heap.nodes.iter_mut() {
+            node.key = f(&node.key, &node.value);
+        }
+    }
+    
+    /// Replaces the value of an entry with a new value, keeping its priority and its place in the heap. Handles to the
+    /// entry stay valid.
+    ///
+    /// Returns `Err` holding the new value if the old value is not in the heap or if the new value already is.
+    pub fn rekey_value(&mut self, old: &V, new: V) -> Result<(), V> {
+        let x = match self.hash_map.get(&self.nodes, old) {
+            Some(hash_node) => hash_node,
+            None => return Err(new)
+        };
+        
+        if *old != new && self.contains(&new) {
+            return Err(new);
+        }
+        
+        // The node has to be looked up by its old value to be removed from the lookup table
+        self.hash_map.remove(&self.nodes, x);
+        self.nodes[x].value = new;
+        self.hash_map.insert(&mut self.nodes, x);
+        self.assert_invariants();
+        
+        Ok(())
+    }
+    
+    /// Deletes the value from the heap.
+    ///
+    /// Returns the priority the value had or `None` if the value is not in the heap.
+    pub fn delete<Q>(&mut self, value: &Q) -> Option<K>
+        where V: Borrow<Q>,
+              Q: Eq + Hash + ?Sized
+    {
+        let x = self.hash_map.get(&self.nodes, value)?;
+        let (key, _) = self.delete_node(x);
+        self.trim_pool();
+        self.assert_invariants();
+        
+        Some(key)
+    }
+    
+    /// Deletes the entry the handle refers to from the heap.
+    ///
+    /// Returns the entry or `None` if it is no longer in the heap.
+    pub fn delete_handle(&mut self, handle: Handle) -> Option<(K, V)> {
+        let x = self.handle_node(handle)?;
+        let entry = self.delete_node(x);
+        self.trim_pool();
+        self.assert_invariants();
+        
+        Some(entry)
+    }
+    
+    // Removes the root z, which is the minimum or a node being deleted, and returns its entry. The minimum is left as
+    // it was until consolidating finds the new one, so the recorder only hears of a minimum that changed
+    fn remove_min(&mut self, z: usize) -> (K, V) {
+        // The children are unlinked from z and linked into the root list by index, nothing is cloned or hashed
+        self.promote_children(z);
+        
+        self.roots.remove(&mut self.nodes, z);
+        self.record(|heap| HeapEvent::NodeExtracted(heap.handle_of(z)));
+        
+        if self.roots.is_empty() {
+            self.set_min(None);
+        } else {
+            self.consolidate();
+        }
+        
+        self.hash_map.remove(&self.nodes, z);
+        self.size -= 1;
+        
+        let node = self.nodes.remove(z);
+        
+        if self.max.is(z) {
+            // No remaining key is larger than the removed one, so if the new minimum has the same key all of them do
+            match self.min {
+                Some(m) if self.equal(&self.nodes[m].key, &node.key) => self.max.set(Some(m)),
+                Some(_) => self.max.forget(),
+                None => self.max.set(None)
+            }
+        }
+        
+        (node.key, node.value)
+    }
+    
+    fn delete_node(&mut self, x: usize) -> (K, V) {
+        if let Some(y) = self.nodes[x].parent() {
+            self.cut(x, y);
+            self.cascading_cut(y);
+        }
+        
+        // x is now a root, and removing it as if its key was minus infinity leaves the other roots to consolidate
+        self.remove_min(x)
+    }
+    
+    // Finds the node the handle refers to, if it is still in the heap
+    fn handle_node(&self, handle: Handle) -> Option<usize> {
+        match self.nodes.generation(handle.index) {
+            Some(generation) if handle.heap == self.nodes.id() && generation == handle.generation => Some(handle.index),
+            _ => None
+        }
+    }
+    
+    // Finds the node to decrease to key, handing key back if it is not a valid decrease
+    fn decrease_target(&self, value: &V, key: K) -> Result<(usize, K), DecreaseKeyError<K>> {
+        let x = match self.hash_map.get(&self.nodes, value) {
+            Some(hash_node) => hash_node,
+            None => return Err(DecreaseKeyError::NotFound(key))
+        };
+        
+        if self.less(&self.nodes[x].key, &key) {
+            return Err(DecreaseKeyError::KeyGreaterThanCurrent(key));
+        }
+        
+        Ok((x, key))
+    }
+    
+    // Decreases the key of x and restores the heap property, leaving the minimum for the caller to update
+    fn decrease_node(&mut self, x: usize, key: K) -> K {
+        // Cuts only ever add roots here, so the growth of the root list counts them
+        #[cfg(feature = "tracing")]
+        let roots = self.roots.len();
+        
+        let (old_key, parent) = self.decrease_and_cut(x, key);
+        if let Some(y) = parent {
+            self.cascading_cut(y);
+        }
+        trace_event!(len = self.size, cuts = self.roots.len() - roots, "decrease_key");
+        
+        old_key
+    }
+    
+    // Decreases the key of x and cuts it from its parent if it became smaller. Returns the previous key and the parent
+    // it was cut from, leaving the cascading cut of the parent to the caller
+    fn decrease_and_cut(&mut self, x: usize, key: K) -> (K, Option<usize>) {
+        if let Some(ref monotone) = self.monotone {
+            monotone.check(&self.compare, &key, "a key was decreased below the last extracted key");
+        }
+        let old_key = mem::replace(&mut self.nodes[x].key, key);
+        
+        let parent = match self.nodes[x].parent() {
+            Some(y) if self.less(&self.nodes[x].key, &self.nodes[y].key) => {
+                self.cut(x, y);
+                Some(y)
+            },
+            _ => None
+        };
+        
+        if self.max.is(x) && self.less(&self.nodes[x].key, &old_key) {
+            self.max.forget();
+        }
+        
+        (old_key, parent)
+    }
+    
+    // Decreases the key of x and makes it the minimum if it became smaller than the minimum
+    fn decrease_to(&mut self, x: usize, key: K) -> K {
+        let old_key = self.decrease_node(x, key);
+        
+        if let Some(m) = self.min {
+            if self.less(&self.nodes[x].key, &self.nodes[m].key) {
+                self.set_min(Some(x));
+            }
+        }
+        
+        old_key
+    }
+    
+    // Changes the key of x to anything, returning the previous key
+    fn change_node(&mut self, x: usize, key: K) -> K {
+        match self.compare_keys(&key, &self.nodes[x].key) {
+            Ordering::Less => self.decrease_to(x, key),
+            Ordering::Greater => self.increase_node(x, key),
+            Ordering::Equal => mem::replace(&mut self.nodes[x].key, key)
+        }
+    }
+    
+    // Increases the key of x in place, keeping the node so handles to it stay valid. Returns the previous key
+    fn increase_node(&mut self, x: usize, key: K) -> K {
+        if let Some(y) = self.nodes[x].parent() {
+            self.cut(x, y);
+            self.cascading_cut(y);
+        }
+        
+        self.promote_children(x);
+        let old_key = mem::replace(&mut self.nodes[x].key, key);
+        
+        if self.min == Some(x) {
+            self.consolidate();
+        }
+        
+        if let Some(Some(m)) = self.max.known() {
+            if self.less(&self.nodes[m].key, &self.nodes[x].key) {
+                self.max.set(Some(x));
+            }
+        }
+        
+        old_key
+    }
+    
+    // Restores the heap after the key of the minimum, m, was changed in place to anything
+    fn restore_min(&mut self, m: usize) {
+        self.promote_children(m);
+        self.consolidate();
+        
+        match self.max.known() {
+            Some(Some(max)) if max == m => self.max.forget(),
+            Some(Some(max)) if self.less(&self.nodes[max].key, &self.nodes[m].key) => self.max.set(Some(m)),
+            _ => {}
+        }
+    }
+    
+    // Makes every child of x a root, as x is removed or they may be smaller than x after its key grew. Nothing is
+    // recorded for a node without children
+    fn promote_children(&mut self, x: usize) {
+        let mut children = mem::take(&mut self.nodes[x].children);
+        if children.is_empty() {
+            return;
+        }
+        
+        while let Some(child) = children.pop(&mut self.nodes) {
+            self.nodes[child].set_parent(None);
+            self.unmark(child);
+            self.push_root(child);
+        }
+        self.record(|heap| HeapEvent::ChildrenPromoted(heap.handle_of(x)));
+    }
+    
+    // In monotone mode, checks that x can leave the heap now and remembers its key as the last extracted one
+    fn check_extracted(&mut self, x: usize) {
+        if let Some(ref mut monotone) = self.monotone {
+            monotone.extract(&self.compare, &self.nodes[x].key);
+        }
+    }
+    
+    fn compare_keys(&self, a: &K, b: &K) -> Ordering {
+        count!(self, comparisons);
+        self.compare.compare(a, b)
+    }
+    
+    fn less(&self, a: &K, b: &K) -> bool {
+        self.compare_keys(a, b) == Ordering::Less
+    }
+    
+    fn equal(&self, a: &K, b: &K) -> bool {
+        self.compare_keys(a, b) == Ordering::Equal
+    }
+    
+    fn push_root(&mut self, x: usize) {
+        count!(self, root_insertions);
+        self.roots.push(&mut self.nodes, x);
+    }
+    
+    fn set_min(&mut self, min: Option<usize>) {
+        if self.min != min {
+            self.min = min;
+            self.record(|heap| HeapEvent::MinChanged(min.map(|m| heap.handle_of(m))));
+        }
+    }
+    
+    fn unmark(&mut self, x: usize) {
+        if self.nodes[x].marked {
+            self.nodes[x].marked = false;
+            self.record(|heap| HeapEvent::NodeUnmarked(heap.handle_of(x)));
+        }
+    }
+    
+    // Passes the event to the recorder, only building it if there is one
+    fn record<F>(&mut self, event: F)
+        where F: FnOnce(&FibonacciHeap<K, V, C>) -> HeapEvent
+    {
+        if self.recorder.is_some() {
+            let event = event(self);
+            if let Some(ref mut recorder) = self.recorder {
+                recorder(event);
+            }
+        }
+    }
+    
+    fn handle_of(&self, x: usize) -> Handle {
+        Handle {
+            heap: self.nodes.id(),
+            index: x,
+            generation: self.nodes.generation(x).expect("node is in the heap")
+        }
+    }
+    
+    // The node with the largest key, looked for and remembered if it is not known
+    fn max_index(&self) -> Option<usize> {
+        match self.max.known() {
+            Some(max) => max,
+            None => {
+                let max = self.max_node();
+                self.max.set(max);
+                max
+            }
+        }
+    }
+    
+    // Looks through every node for the largest key
+    fn max_node(&self) -> Option<usize> {
+        self.nodes.iter()
+            .max_by(|&(_, a), &(_, b)| self.compare_keys(&a.key, &b.key))
+            .map(|(index, _)| index)
+    }
+    
+    fn smallest_nodes(&self, n: usize) -> Vec<usize> {
+        let mut smallest = Vec::with_capacity(cmp::min(n, self.len()));
+        smallest.extend(self.sorted_nodes().take(n));
+        
+        smallest
+    }
+        
+    // Visits the nodes in ascending key order. Children are never smaller than their parent, so a best-first walk from
+    // the roots only expands the subtrees of nodes it has already handed out
+    fn sorted_nodes(&self) -> impl Iterator<Item = usize> + '_ {
+        let nodes = &self.nodes;
+        let compare = &self.compare;
+        let candidate = move |index: usize| Candidate { key: &nodes[index].key, index, compare };
+        let mut frontier: BinaryHeap<_> = self.roots.iter(nodes)
+            .map(candidate)
+            .collect();
+        
+        iter::from_fn(move || {
+            let Candidate { index: x, .. } = frontier.pop()?;
+            frontier.extend(nodes[x].children.iter(nodes).map(candidate));
+            
+            Some(x)
+        })
+    }
+    
+    // Removes every entry the predicate returns true for
+    fn remove_where<F>(&mut self, mut f: F) -> Vec<(K, V)>
+        where F: FnMut(&K, &V) -> bool
+    {
+        let mut removed = vec![false; self.nodes.slot_count()];
+        let mut doomed = Vec::new();
+        
+        for (index, node) in self.nodes.iter() {
+            if f(&node.key, &node.value) {
+                removed[index] = true;
+                doomed.push(index);
+            }
+        }
+        
+        if doomed.is_empty() {
+            return Vec::new();
+        }
+        
+        // Parents that stay lose a child, exactly as if the node was deleted on its own
+        for &x in &doomed {
+            if let Some(y) = self.nodes[x].parent() {
+                if !removed[y] {
+                    self.cut(x, y);
+                    self.cascading_cut(y);
+                }
+            }
+        }
+        
+        self.remove_nodes(doomed)
+    }
+    
+    // Removes the nodes and consolidates once. A node whose parent is not removed is simply detached from it, so any
+    // cascading cuts are up to the caller.
+    fn remove_nodes(&mut self, removed: Vec<usize>) -> Vec<(K, V)> {
+        let mut result = Vec::with_capacity(removed.len());
+        let removes_max = removed.iter().any(|&x| self.max.is(x));
+        
+        for x in removed {
+            match self.nodes[x].parent() {
+                Some(y) => self.nodes.remove_child(y, x),
+                None => self.roots.remove(&mut self.nodes, x)
+            }
+            
+            self.promote_children(x);
+            self.record(|heap| HeapEvent::NodeExtracted(heap.handle_of(x)));
+            
+            self.hash_map.remove(&self.nodes, x);
+            
+            let x = self.nodes.remove(x);
+            result.push((x.key, x.value));
+        }
+        
+        if self.roots.is_empty() {
+            self.set_min(None);
+        } else {
+            self.consolidate();
+        }
+        
+        self.size -= result.len();
+        
+        if removes_max {
+            self.max.forget();
+        }
+        
+        result
+    }
+    
+    fn consolidate(&mut self) {
+        #[cfg(feature = "tracing")]
+        let _span = ::tracing::trace_span!("consolidate", roots = self.roots.len()).entered();
+        
+        // Every slot is left empty by the previous call. Rather than trusting a bound computed from the size, the
+        // array grows whenever a degree does not fit, which only happens a few times over the life of the heap
+        let mut array = mem::take(&mut self.degrees);
+        
+        let mut roots = mem::take(&mut self.roots);
+            
+        while let Some(root) = roots.pop(&mut self.nodes) {
+            let mut x = root;
+            let mut d = self.nodes[x].rank();
+            loop {
+                if d >= array.len() {
+                    array.resize(d + 1, None);
+                }
+                
+                let mut y = match array[d].take() {
+                    Some(y) => y,
+                    None => break
+                };
+                if self.less(&self.nodes[y].key, &self.nodes[x].key) {
+                    mem::swap(&mut x, &mut y);
+                }
+                
+                self.heap_link(y, x);
+                d += 1;
+            }
+            array[d] = Some(x);
+        }
+        
+        let mut min = None;
+        
+        for slot in array.iter_mut() {
+            let i_root = match slot.take() {
+                Some(i_root) => i_root,
+                None => continue
+            };
+            
+            self.push_root(i_root);
+            let replace = match min {
+                Some(min) => self.less(&self.nodes[i_root].key, &self.nodes[min].key),
+                None => true
+            };
+            
+            if replace {
+                min = Some(i_root);
+            }
+        }
+        
+        self.set_min(min);
+        self.degrees = array;
+        trace_event!(roots = self.roots.len(), "consolidated");
+    }
+    
+    fn heap_link(&mut self, y: usize, x: usize) {
+        // No need to unlink y from the roots, consolidate has already popped it off the list
+        count!(self, links);
+        self.nodes.push_child(x, y);
+        self.nodes[y].set_parent(Some(x));
+        self.unmark(y);
+        self.record(|heap| HeapEvent::RootLinked { child: heap.handle_of(y), parent: heap.handle_of(x) });
+    }
+    
+    fn cut(&mut self, x: usize, y: usize) {
+        count!(self, cuts);
+        self.nodes.remove_child(y, x);
+        self.push_root(x);
+        self.nodes[x].set_parent(None);
+        self.record(|heap| HeapEvent::NodeCut(heap.handle_of(x)));
+        self.unmark(x);
+        trace_event!(roots = self.roots.len(), "cut");
+    }
+    
+    // Walks up from y with a loop rather than recursion, as a chain of marked ancestors can be arbitrarily long
+    fn cascading_cut(&mut self, mut y: usize) {
+        while let Some(z) = self.nodes[y].parent() {
+            if !self.nodes[y].marked {
+                self.nodes[y].marked = true;
+                self.record(|heap| HeapEvent::NodeMarked(heap.handle_of(y)));
+                return;
+            }
+            
+            count!(self, cascading_cuts);
+            self.cut(y, z);
+            y = z;
+        }
+    }
+    
+    // Panics with the first broken invariant. Public operations call this before they return, while a batch of
+    // BulkInsert or a PeekMut guard only call it once they are done
+    #[cfg(feature = "debug-invariants")]
+    fn assert_invariants(&self) {
+        if let Err(violation) = self.check_invariants() {
+            panic!("invariant violated: {}", violation);
+        }
+    }
+    
+    #[cfg(not(feature = "debug-invariants"))]
+    #[inline(always)]
+    fn assert_invariants(&self) { }
+}
+
+// Ways the tests break a heap on purpose, each caught by check_invariants as a different InvariantViolation. Nodes are
+// named by their value
+#[cfg(test)]
+pub enum Corruption<V> {
+    // Points the right link of a node that has siblings back at itself, a BrokenLink
+    SelfLink(V),
+    // Takes a child other than the first out of the children list of the node without counting it, a DegreeMismatch
+    DetachChild(V),
+    // Swaps the keys of the node and its parent, a HeapOrderViolated if they differ
+    SwapWithParent(V),
+    // Marks a root, a MarkedRoot
+    MarkRoot(V),
+    // Counts one entry too many, a SizeMismatch
+    GrowSize,
+    // Takes a root that is not the minimum out of the root list, leaving it stored, an UnreachableEntry
+    DetachRoot(V),
+    // Files the first node in the lookup as the second, a LookupMismatch
+    SwapLookup(V, V),
+    // Tracks the node as the minimum, a MinNotMinimal if it is not a smallest root
+    MoveMin(V),
+    // Tracks the node as the maximum, a MaxNotMaximal if it is not a largest node
+    MoveMax(V)
+}
+
+#[cfg(test)]
+impl<K, V, C> FibonacciHeap<K, V, C>
+    where K: Clone,
+          V: Eq + Hash,
+          C: Compare<K>
+{
+    pub fn corrupt(&mut self, corruption: Corruption<V>) {
+        let find = |heap: &FibonacciHeap<K, V, C>, value: &V| {
+            heap.hash_map.get(&heap.nodes, value).expect("corrupted node is in the heap")
+        };
+        
+        match corruption {
+            Corruption::SelfLink(value) => {
+                let x = find(self, &value);
+                self.nodes[x].set_right(x);
+            },
+            Corruption::DetachChild(value) => {
+                let x = find(self, &value);
+                let child = self.nodes[x].children.iter(&self.nodes).nth(1).expect("node has two children");
+                let (left, right) = (self.nodes[child].left(), self.nodes[child].right());
+                self.nodes[left].set_right(right);
+                self.nodes[right].set_left(left);
+            },
+            Corruption::SwapWithParent(value) => {
+                let x = find(self, &value);
+                let y = self.nodes[x].parent().expect("node has a parent");
+                let key = self.nodes[x].key.clone();
+                self.nodes[x].key = mem::replace(&mut self.nodes[y].key, key);
+            },
+            Corruption::MarkRoot(value) => {
+                let x = find(self, &value);
+                self.nodes[x].marked = true;
+            },
+            Corruption::GrowSize => self.size += 1,
+            Corruption::DetachRoot(value) => {
+                let x = find(self, &value);
+                self.roots.remove(&mut self.nodes, x);
+            },
+            Corruption::SwapLookup(value, other) => {
+                let (x, y) = (find(self, &value), find(self, &other));
+                let hash = self.nodes[x].hash;
+                self.hash_map.remove(&self.nodes, x);
+                self.hash_map.insert_hashed(&mut self.nodes, y, hash);
+            },
+            Corruption::MoveMin(value) => self.min = Some(find(self, &value)),
+            Corruption::MoveMax(value) => {
+                let x = find(self, &value);
+                self.max.set(Some(x));
+            }
+        }
+    }
+}
+
+// Reading the size and the entries neither compares keys nor hashes values, so it needs no bounds, and impls such as
+// Serialize that only read the entries do not need them either
+impl<K, V, C> FibonacciHeap<K, V, C> {
+    /// Returns the number of values in the heap.
+    pub fn len(&self) -> usize {
+        self.size
+    }
+    
+    /// Returns `true` if the heap contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+    
+    /// Returns an iterator over every entry of the heap in no particular order.
+    ///
+    /// The heap is left untouched and nothing is cloned.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> + '_ {
+        self.nodes.iter().map(|(_, node)| (&node.key, &node.value))
+    }
+}
+
+impl<K, V, C> Default for FibonacciHeap<K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K> + Default
+{
+    fn default() -> FibonacciHeap<K, V, C> {
+        FibonacciHeap::with_comparator(C::default())
+    }
+}
+
+impl<K, V, C> Clone for FibonacciHeap<K, V, C>
+    where K: Clone,
+          V: Clone,
+          C: Clone
+{
+    /// Copies every node. Nodes only refer to each other by index, so the copy has the same structure and shares
+    /// nothing with the original. Handles into the original do not refer to entries of the copy.
+    fn clone(&self) -> FibonacciHeap<K, V, C> {
+        FibonacciHeap {
+            nodes: self.nodes.clone(),
+            hash_map: self.hash_map.clone(),
+            roots: self.roots,
+            min: self.min,
+            max: self.max.clone(),
+            size: self.size,
+            degrees: Vec::new(),
+            pool_limit: self.pool_limit,
+            monotone: self.monotone.clone(),
+            #[cfg(feature = "instrument")]
+            counters: self.counters.clone(),
+            recorder: None,
+            compare: self.compare.clone()
+        }
+    }
+}
+
+/// Two heaps are equal if they hold the same values with equal priorities, however their trees are shaped.
+///
+/// Runs in O(n) time, looking up every value of one heap in the other. The comparators are not compared.
+impl<K, V, C> PartialEq for FibonacciHeap<K, V, C>
+    where K: PartialEq,
+          V: Eq + Hash
+{
+    fn eq(&self, other: &FibonacciHeap<K, V, C>) -> bool {
+        if self.size != other.size {
+            return false;
+        }
+        
+        self.hash_map.iter().all(|x| {
+            match other.hash_map.get(&other.nodes, &self.nodes[x].value) {
+                Some(y) => self.nodes[x].key == other.nodes[y].key,
+                None => false
+            }
+        })
+    }
+}
+
+impl<K, V, C> Eq for FibonacciHeap<K, V, C>
+    where K: Eq,
+          V: Eq + Hash
+{ }
+
+/// Shows the number of entries, the minimum and every tree with its children nested below it.
+///
+/// `{:?}` puts the children of a node in brackets after it and marks marked nodes with `*`, `{:#?}` puts every node on
+/// its own line indented by its depth. Trees are shown in the same order as `visit` walks them.
+impl<K, V, C> fmt::Debug for FibonacciHeap<K, V, C>
+    where K: fmt::Debug,
+          V: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let min = self.min.map(|m| (&self.nodes[m].key, &self.nodes[m].value));
+        let mut roots: Vec<usize> = self.roots.iter(&self.nodes).collect();
+        roots.reverse();
+        
+        // Both forms walk the trees with a stack rather than recursion, as a tree can be deep
+        if f.alternate() {
+            writeln!(f, "FibonacciHeap {{")?;
+            writeln!(f, "    len: {},", self.size)?;
+            writeln!(f, "    min: {:?},", min)?;
+            writeln!(f, "    roots: [")?;
+            
+            let mut stack: Vec<(usize, usize)> = roots.into_iter().map(|root| (root, 2)).collect();
+            while let Some((x, depth)) = stack.pop() {
+                let node = &self.nodes[x];
+                let marked = if node.marked { " (marked)" } else { "" };
+                writeln!(f, "{:indent$}{:?}: {:?}{},", "", node.key, node.value, marked, indent = depth * 4)?;
+                stack.extend(node.children.iter_rev(&self.nodes).map(|child| (child, depth + 1)));
+            }
+            
+            writeln!(f, "    ]")?;
+            write!(f, "}}")
+        } else {
+            write!(f, "FibonacciHeap {{ len: {}, min: {:?}, roots: [", self.size, min)?;
+            
+            // None closes the brackets around the children of a node
+            let mut stack: Vec<Option<usize>> = roots.into_iter().map(Some).collect();
+            let mut first = true;
+            while let Some(step) = stack.pop() {
+                let x = match step {
+                    Some(x) => x,
+                    None => {
+                        write!(f, "]")?;
+                        first = false;
+                        continue;
+                    }
+                };
+                
+                if !first {
+                    write!(f, ", ")?;
+                }
+                first = false;
+                
+                let node = &self.nodes[x];
+                write!(f, "{:?}: {:?}", node.key, node.value)?;
+                if node.marked {
+                    write!(f, "*")?;
+                }
+                
+                if !node.children.is_empty() {
+                    write!(f, " [")?;
+                    first = true;
+                    stack.push(None);
+                    stack.extend(node.children.iter_rev(&self.nodes).map(Some));
+                }
+            }
+            
+            write!(f, "] }}")
+        }
+    }
+}
+
+/// Reads the priority of a value, as `get_priority` does.
+///
+/// # Panics
+///
+/// Panics if the value is not in the heap.
+impl<K, V, C> Index<&V> for FibonacciHeap<K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    type Output = K;
+    
+    fn index(&self, value: &V) -> &K {
+        self.get_priority(value).expect("value is not in the heap")
+    }
+}
+
+impl<K, V, C> From<Vec<(K, V)>> for FibonacciHeap<K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K> + Default
+{
+    /// Builds a heap from the entries in O(n) time, as `FibonacciHeap::from_entries` does.
+    fn from(entries: Vec<(K, V)>) -> FibonacciHeap<K, V, C> {
+        FibonacciHeap::from_entries(entries)
+    }
+}
+
+impl<K, V, C> FromIterator<(K, V)> for FibonacciHeap<K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K> + Default
+{
+    /// Builds a heap from the entries as if each was inserted in order, so a repeated value keeps its last priority.
+    fn from_iter<I>(iter: I) -> FibonacciHeap<K, V, C>
+        where I: IntoIterator<Item = (K, V)>
+    {
+        let mut heap = FibonacciHeap::with_comparator(C::default());
+        heap.extend(iter);
+        
+        heap
+    }
+}
+
+impl<K, V, C> Extend<(K, V)> for FibonacciHeap<K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    /// Inserts every entry as `insert` would, in order.
+    ///
+    /// Space for the entries is reserved once from the size hint, and new entries are only compared against the minimum
+    /// and maximum of the heap once, after the smallest and largest of them have been found.
+    fn extend<I>(&mut self, iter: I)
+        where I: IntoIterator<Item = (K, V)>
+    {
+        self.bulk_insert().extend(iter);
+    }
+}
+
+impl<'a, K, V, C> Extend<(&'a K, &'a V)> for FibonacciHeap<K, V, C>
+    where K: Clone,
+          V: Clone + Eq + Hash,
+          C: Compare<K>
+{
+    /// Inserts a clone of every entry, as the `Extend` of owned entries does.
+    fn extend<I>(&mut self, iter: I)
+        where I: IntoIterator<Item = (&'a K, &'a V)>
+    {
+        self.extend(iter.into_iter().map(|(key, value)| (key.clone(), value.clone())));
+    }
+}
+
+impl<K, V, C> IntoIterator for FibonacciHeap<K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    type Item = (K, V);
+    type IntoIter = IntoIter<K, V, C>;
+    
+    /// Consumes the heap into an iterator over its entries in ascending key order.
+    fn into_iter(self) -> IntoIter<K, V, C> {
+        IntoIter {
+            heap: self
+        }
+    }
+}
+
+/// An iterator that extracts the entries of a `FibonacciHeap` in ascending key order, created by `into_iter`.
+///
+/// Each entry is extracted as the iterator reaches it. Entries that are left when the iterator is dropped are dropped
+/// with it.
+pub struct IntoIter<K, V, C = MinComparator> {
+    heap: FibonacciHeap<K, V, C>
+}
+
+impl<K, V, C> Iterator for IntoIter<K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    type Item = (K, V);
+    
+    fn next(&mut self) -> Option<(K, V)> {
+        self.heap.extract_min()
+    }
+    
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<K, V, C> ExactSizeIterator for IntoIter<K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{ }
+
+// A node waiting to be visited by smallest_nodes, ordered so that the BinaryHeap pops the smallest key first
+struct Candidate<'a, K, C> {
+    key: &'a K,
+    index: usize,
+    compare: &'a C
+}
+
+impl<'a, K, C> Ord for Candidate<'a, K, C>
+    where C: Compare<K>
+{
+    fn cmp(&self, other: &Candidate<'a, K, C>) -> Ordering {
+        self.compare.compare(other.key, self.key).then(other.index.cmp(&self.index))
+    }
+}
+
+impl<'a, K, C> PartialOrd for Candidate<'a, K, C>
+    where C: Compare<K>
+{
+    fn partial_cmp(&self, other: &Candidate<'a, K, C>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<'a, K, C> PartialEq for Candidate<'a, K, C>
+    where C: Compare<K>
+{
+    fn eq(&self, other: &Candidate<'a, K, C>) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<'a, K, C> Eq for Candidate<'a, K, C>
+    where C: Compare<K>
+{ }
+
+// Without std there is no way to tell, so the guards never check the heap rather than risk panicking while unwinding
+#[cfg(feature = "std")]
+fn panicking() -> bool {
+    thread::panicking()
+}
+
+#[cfg(not(feature = "std"))]
+fn panicking() -> bool {
+    true
+}
+
+// Escapes what is written for use inside a quoted DOT string
+struct DotEscape<'a, W: 'a>(&'a mut W);
+
+impl<'a, W> fmt::Write for DotEscape<'a, W>
+    where W: fmt::Write
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' | '\\' => write!(self.0, "\\{}", c)?,
+                '\n' => self.0.write_str("\\n")?,
+                c => self.0.write_char(c)?
+            }
+        }
+        
+        Ok(())
+    }
+}
+
+/// An iterator that removes every entry of a `FibonacciHeap` in no particular order, created by
+/// `FibonacciHeap::drain`.
+pub struct Drain<'a, K, V> {
+    slots: vec::Drain<'a, Option<FibonacciNode<K, V>>>,
+    remaining: usize
+}
+
+impl<'a, K, V> Iterator for Drain<'a, K, V> {
+    type Item = (K, V);
+    
+    fn next(&mut self) -> Option<(K, V)> {
+        let node = self.slots.by_ref().flatten().next()?;
+        self.remaining -= 1;
+        
+        Some((node.key, node.value))
+    }
+    
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Drain<'a, K, V> { }
+
+/// An iterator that removes every entry of a `FibonacciHeap` in ascending key order, created by
+/// `FibonacciHeap::drain_sorted`.
+pub struct DrainSorted<'a, K, V, C = MinComparator>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    heap: &'a mut FibonacciHeap<K, V, C>
+}
+
+impl<'a, K, V, C> Iterator for DrainSorted<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    type Item = (K, V);
+    
+    fn next(&mut self) -> Option<(K, V)> {
+        self.heap.extract_min()
+    }
+    
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.heap.len(), Some(self.heap.len()))
+    }
+}
+
+impl<'a, K, V, C> ExactSizeIterator for DrainSorted<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{ }
+
+impl<'a, K, V, C> Drop for DrainSorted<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    fn drop(&mut self) {
+        // The rest is removed in whatever order is cheapest
+        self.heap.drain();
+    }
+}
+
+/// The entry of a value in a `FibonacciHeap`, returned by `FibonacciHeap::entry`.
+pub enum Entry<'a, K, V, C = MinComparator> {
+    /// The value is in the heap.
+    Occupied(OccupiedEntry<'a, K, V, C>),
+    /// The value is not in the heap.
+    Vacant(VacantEntry<'a, K, V, C>)
+}
+
+impl<'a, K, V, C> Entry<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    /// Inserts the value with priority key if it is not in the heap. An existing priority is not overwritten.
+    ///
+    /// Returns the priority the value has afterwards.
+    pub fn or_insert(self, key: K) -> &'a K {
+        match self {
+            Entry::Occupied(entry) => entry.into_priority(),
+            Entry::Vacant(entry) => entry.insert(key)
+        }
+    }
+    
+    /// Inserts the value with the priority computed by the closure if it is not in the heap.
+    ///
+    /// The closure is only called for a vacant entry. Returns the priority the value has afterwards.
+    pub fn or_insert_with<F>(self, f: F) -> &'a K
+        where F: FnOnce() -> K
+    {
+        match self {
+            Entry::Occupied(entry) => entry.into_priority(),
+            Entry::Vacant(entry) => entry.insert(f())
+        }
+    }
+    
+    /// Changes the priority of the value with the closure if it is in the heap.
+    ///
+    /// The closure is given a copy of the current priority to modify, which is then set as with
+    /// `FibonacciHeap::change_priority`.
+    pub fn and_modify<F>(self, f: F) -> Entry<'a, K, V, C>
+        where F: FnOnce(&mut K),
+              K: Clone
+    {
+        match self {
+            Entry::Occupied(mut entry) => {
+                let mut key = entry.priority().clone();
+                f(&mut key);
+                entry.set_priority(key);
+                Entry::Occupied(entry)
+            },
+            Entry::Vacant(entry) => Entry::Vacant(entry)
+        }
+    }
+    
+    /// Returns the value of the entry.
+    pub fn value(&self) -> &V {
+        match *self {
+            Entry::Occupied(ref entry) => entry.value(),
+            Entry::Vacant(ref entry) => entry.value()
+        }
+    }
+}
+
+/// The entry of a value that is in a `FibonacciHeap`.
+pub struct OccupiedEntry<'a, K, V, C = MinComparator> {
+    heap: &'a mut FibonacciHeap<K, V, C>,
+    node: usize
+}
+
+impl<'a, K, V, C> OccupiedEntry<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    /// Returns the value of the entry.
+    pub fn value(&self) -> &V {
+        &self.heap.nodes[self.node].value
+    }
+    
+    /// Returns the current priority of the value.
+    pub fn priority(&self) -> &K {
+        &self.heap.nodes[self.node].key
+    }
+    
+    /// Returns the current priority of the value, borrowed for as long as the heap was.
+    pub fn into_priority(self) -> &'a K {
+        &self.heap.nodes[self.node].key
+    }
+    
+    /// Changes the priority of the value to the key, as with `FibonacciHeap::change_priority`.
+    ///
+    /// Returns the previous priority.
+    pub fn set_priority(&mut self, key: K) -> K {
+        // The node is kept by both decreasing and increasing a key, so the entry can still be used afterwards
+        let old_key = self.heap.change_node(self.node, key);
+        self.heap.assert_invariants();
+        
+        old_key
+    }
+    
+    /// Removes the value from the heap and returns its entry.
+    pub fn remove(self) -> (K, V) {
+        let entry = self.heap.delete_node(self.node);
+        self.heap.trim_pool();
+        self.heap.assert_invariants();
+        
+        entry
+    }
+}
+
+/// The entry of a value that is not in a `FibonacciHeap`.
+pub struct VacantEntry<'a, K, V, C = MinComparator> {
+    heap: &'a mut FibonacciHeap<K, V, C>,
+    value: V,
+    hash: u64
+}
+
+impl<'a, K, V, C> VacantEntry<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    /// Returns the value of the entry.
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+    
+    /// Inserts the value with priority key and returns the priority.
+    pub fn insert(self, key: K) -> &'a K {
+        // The value was not found when the entry was made and the heap has been borrowed since, so it is still not
+        // there and only has to be added under the hash it was looked up with
+        let node = self.heap.insert_vacant(key, self.value, self.hash);
+        self.heap.assert_invariants();
+        
+        &self.heap.nodes[node].key
+    }
+}
+
+/// A guard inserting a batch of entries into a `FibonacciHeap`, returned by `FibonacciHeap::bulk_insert`.
+pub struct BulkInsert<'a, K, V, C = MinComparator>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    heap: &'a mut FibonacciHeap<K, V, C>,
+    // Smallest and largest node added since the minimum and maximum were last updated
+    batch: Option<(usize, usize)>
+}
+
+impl<'a, K, V, C> BulkInsert<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    /// Inserts the value into the heap with priority key, as `FibonacciHeap::insert` does.
+    ///
+    /// A value already in the heap has its priority changed right away, which first brings the minimum and maximum up
+    /// to date.
+    pub fn insert(&mut self, key: K, value: V) {
+        if self.heap.contains(&value) {
+            // Replacing an entry needs the minimum and maximum to be up to date
+            self.finish();
+            self.heap.insert(key, value);
+            return;
+        }
+        
+        let heap = &mut *self.heap;
+        let node = heap.add_root(key, value);
+        self.batch = match self.batch {
+            Some((lo, hi)) => {
+                let lo = if heap.less(&heap.nodes[node].key, &heap.nodes[lo].key) { node } else { lo };
+                let hi = if heap.less(&heap.nodes[hi].key, &heap.nodes[node].key) { node } else { hi };
+                Some((lo, hi))
+            },
+            None => Some((node, node))
+        };
+    }
+    
+    /// Reserves space for at least `additional` more values, as `FibonacciHeap::reserve` does.
+    pub fn reserve(&mut self, additional: usize) {
+        self.heap.reserve(additional);
+    }
+    
+    fn finish(&mut self) {
+        if let Some((lo, hi)) = self.batch.take() {
+            self.heap.update_bounds(lo, hi);
+        }
+    }
+}
+
+impl<'a, K, V, C> Extend<(K, V)> for BulkInsert<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    /// Inserts every entry through the guard, reserving space for them once from the size hint.
+    fn extend<I>(&mut self, iter: I)
+        where I: IntoIterator<Item = (K, V)>
+    {
+        let iter = iter.into_iter();
+        
+        let (lower, _) = iter.size_hint();
+        self.heap.hash_map.reserve(lower, &self.heap.nodes);
+        
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<'a, K, V, C> Drop for BulkInsert<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    fn drop(&mut self) {
+        self.finish();
+        // A panic from a Hash or comparator may have left the heap broken, checking it then would only abort
+        if !panicking() {
+            self.heap.assert_invariants();
+        }
+    }
+}
+
+// Finishes map_priorities once every key is set, or the closure panicked, by finding the minimum and maximum roots
+struct Rebuild<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    heap: &'a mut FibonacciHeap<K, V, C>
+}
+
+impl<'a, K, V, C> Drop for Rebuild<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    fn drop(&mut self) {
+        let heap = &mut *self.heap;
+        let nodes = &heap.nodes;
+        heap.min = heap.roots.iter(nodes).min_by(|&a, &b| heap.compare_keys(&nodes[a].key, &nodes[b].key));
+        heap.max.forget();
+        heap.record(|_| HeapEvent::Reset);
+        if !panicking() {
+            heap.assert_invariants();
+        }
+    }
+}
+
+/// A guard giving mutable access to the key of the minimum of a `FibonacciHeap`, returned by
+/// `FibonacciHeap::peek_mut`.
+pub struct PeekMut<'a, K, V, C = MinComparator>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    heap: &'a mut FibonacciHeap<K, V, C>,
+    // The minimum, which stays the same node for as long as the guard borrows the heap
+    node: usize,
+    // Set once the key may have been changed, so the heap has to be restored
+    changed: bool
+}
+
+impl<'a, K, V, C> PeekMut<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    /// Returns the value of the minimum.
+    pub fn value(&self) -> &V {
+        &self.heap.nodes[self.node].value
+    }
+    
+    /// Removes the peeked entry from the heap and returns it, with its key as changed through the guard.
+    pub fn pop(mut guard: PeekMut<'a, K, V, C>) -> (K, V) {
+        guard.heap.check_extracted(guard.node);
+        let entry = guard.heap.delete_node(guard.node);
+        
+        // The tracked maximum may not hold once the key was changed
+        if guard.changed {
+            guard.heap.max.forget();
+            guard.changed = false;
+        }
+        guard.heap.trim_pool();
+        guard.heap.assert_invariants();
+        
+        entry
+    }
+}
+
+impl<'a, K, V, C> Deref for PeekMut<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    type Target = K;
+    
+    fn deref(&self) -> &K {
+        &self.heap.nodes[self.node].key
+    }
+}
+
+impl<'a, K, V, C> DerefMut for PeekMut<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    fn deref_mut(&mut self) -> &mut K {
+        self.changed = true;
+        &mut self.heap.nodes[self.node].key
+    }
+}
+
+impl<'a, K, V, C> Drop for PeekMut<'a, K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    fn drop(&mut self) {
+        if self.changed {
+            self.heap.restore_min(self.node);
+            if !panicking() {
+                self.heap.assert_invariants();
+            }
+        }
+    }
+}
+
+/// Where an entry sits in the forest of a `FibonacciHeap`, as passed to the closure of `FibonacciHeap::visit`.
+#[derive(Debug)]
+pub struct VisitInfo<'a, K> {
+    /// The number of ancestors of the entry, roots have a depth of 0.
+    pub depth: usize,
+    /// The number of children of the entry.
+    pub degree: usize,
+    /// Whether the entry has lost a child since it became a child itself.
+    pub marked: bool,
+    /// The key of the parent of the entry, or `None` for a root.
+    pub parent_key: Option<&'a K>
+}
+
+/// The memory allocated by a `FibonacciHeap`, in bytes, as returned by `FibonacciHeap::memory_usage`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MemoryUsage {
+    /// The storage of the nodes, which hold the keys and values in place, including free slots.
+    pub nodes: usize,
+    /// The table that finds the node of a value.
+    pub lookup: usize,
+    /// The memory owned by the keys and values, as reported to `FibonacciHeap::memory_usage_with`.
+    pub entries: usize,
+    /// Everything above, plus smaller buffers the heap keeps for reuse. The `FibonacciHeap` struct itself is not
+    /// included.
+    pub total: usize
+}
+
+/// Totals of the elementary steps taken by a `FibonacciHeap`, as returned by `FibonacciHeap::counters`.
+///
+/// Only comparisons made while the heap modifies or inspects its own structure are counted, the sorts and best-first
+/// walks of `into_sorted_vec`, `iter_sorted` and `smallest_n` are not. The counters are atomic, so a heap shared
+/// between threads stays `Sync`.
+#[cfg(feature = "instrument")]
+#[derive(Debug, Default)]
+pub struct Counters {
+    links: Counter,
+    cuts: Counter,
+    cascading_cuts: Counter,
+    comparisons: Counter,
+    root_insertions: Counter
+}
+
+#[cfg(all(feature = "instrument", target_has_atomic = "64"))]
+type Counter = AtomicU64;
+// Targets without 64 bit atomics count in a usize, so there a counter wraps after 2^32 steps
+#[cfg(all(feature = "instrument", not(target_has_atomic = "64")))]
+type Counter = AtomicUsize;
+
+#[cfg(all(feature = "instrument", target_has_atomic = "64"))]
+fn load(counter: &Counter) -> u64 {
+    counter.load(atomic::Ordering::Relaxed)
+}
+
+#[cfg(all(feature = "instrument", not(target_has_atomic = "64")))]
+fn load(counter: &Counter) -> u64 {
+    counter.load(atomic::Ordering::Relaxed) as u64
+}
+
+#[cfg(feature = "instrument")]
+impl Counters {
+    /// The number of times a root was linked under another root, by consolidation or `from_sorted_iter`.
+    pub fn links(&self) -> u64 {
+        load(&self.links)
+    }
+    
+    /// The number of times a node was cut from its parent and made a root, including cascading cuts.
+    pub fn cuts(&self) -> u64 {
+        load(&self.cuts)
+    }
+    
+    /// The number of cuts made by cascading up from a parent that lost a second child.
+    pub fn cascading_cuts(&self) -> u64 {
+        load(&self.cascading_cuts)
+    }
+    
+    /// The number of times the comparator was called.
+    pub fn comparisons(&self) -> u64 {
+        load(&self.comparisons)
+    }
+    
+    /// The number of nodes added to the root list one at a time. Melding splices whole lists and is not counted.
+    pub fn root_insertions(&self) -> u64 {
+        load(&self.root_insertions)
+    }
+}
+
+#[cfg(feature = "instrument")]
+impl Clone for Counters {
+    fn clone(&self) -> Counters {
+        Counters {
+            links: Counter::new(self.links.load(atomic::Ordering::Relaxed)),
+            cuts: Counter::new(self.cuts.load(atomic::Ordering::Relaxed)),
+            cascading_cuts: Counter::new(self.cascading_cuts.load(atomic::Ordering::Relaxed)),
+            comparisons: Counter::new(self.comparisons.load(atomic::Ordering::Relaxed)),
+            root_insertions: Counter::new(self.root_insertions.load(atomic::Ordering::Relaxed))
+        }
+    }
+}
+
+/// Measures of the shape of a `FibonacciHeap`, as returned by `FibonacciHeap::stats`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HeapStats {
+    /// The number of trees, that is of roots.
+    pub roots: usize,
+    /// The largest number of children of any node, 0 for an empty heap.
+    pub max_degree: usize,
+    /// The number of nodes with each degree, indexed by degree. Empty for an empty heap, otherwise the last element
+    /// counts the nodes of `max_degree`.
+    pub degrees: Vec<usize>,
+    /// The number of nodes marked as having lost a child.
+    pub marked: usize,
+    /// The number of nodes, equal to the length of the heap.
+    pub nodes: usize
+}
+
+/// A reference to an entry of a `FibonacciHeap`, returned by `FibonacciHeap::push_with_handle`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    // Id of the node storage of the heap, so the handle does not match nodes of another heap
+    heap: u64,
+    index: usize,
+    // Tells the entry apart from later entries that reuse the same node
+    generation: u64
 }
\ No newline at end of file
@@ -0,0 +1,120 @@
+use core::hash::Hash;
+
+use super::compare::MaxComparator;
+use super::error::{DecreaseKeyError, HeapError, IncreaseKeyError};
+use super::fibonacci_heap::FibonacciHeap;
+
+/// A `FibonacciHeap` that extracts the entry with the largest key first.
+///
+/// This is a `FibonacciHeap` ordered by `MaxComparator`, with the operations named for a max-heap. Moving a value
+/// towards the top of the heap is `increase_key` here, which runs in O(1) amortized time just like
+/// `FibonacciHeap::decrease_key`.
+pub struct FibonacciMaxHeap<K, V> {
+    heap: FibonacciHeap<K, V, MaxComparator>
+}
+
+impl<K, V> FibonacciMaxHeap<K, V>
+    where K: Ord,
+          V: Eq + Hash
+{
+    /// Creates a new empty `FibonacciMaxHeap`.
+    pub fn new() -> FibonacciMaxHeap<K, V> {
+        FibonacciMaxHeap {
+            heap: FibonacciHeap::with_comparator(MaxComparator)
+        }
+    }
+    
+    /// Inserts the value into the heap with priority key.
+    ///
+    /// As with `FibonacciHeap::insert`, a value already in the heap has its priority changed to key.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.heap.insert(key, value);
+    }
+    
+    /// Peeks at the maximum of the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn maximum(&self) -> Option<(&K, &V)> {
+        self.heap.minimum()
+    }
+    
+    /// Returns the number of values in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    
+    /// Returns `true` if the heap contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    
+    /// Returns `true` if the value is in the heap.
+    pub fn contains(&self, value: &V) -> bool {
+        self.heap.contains(value)
+    }
+    
+    /// Looks up the current priority of the value.
+    ///
+    /// Returns `None` if the value is not in the heap.
+    pub fn get_priority(&self, value: &V) -> Option<&K> {
+        self.heap.get_priority(value)
+    }
+    
+    /// Melds `other` into this heap, as with `FibonacciHeap::union`.
+    pub fn union(&mut self, other: FibonacciMaxHeap<K, V>) {
+        self.heap.union(other.heap);
+    }
+    
+    /// Extracts the maximum of the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn extract_max(&mut self) -> Option<(K, V)> {
+        self.heap.extract_min()
+    }
+    
+    /// Increases the priority of the value to the key.
+    ///
+    /// Returns the previous priority, or `Err` holding the rejected key if the value is not in the heap or if the key is
+    /// less than the current priority of the value.
+    pub fn increase_key(&mut self, value: V, key: K) -> Result<K, IncreaseKeyError<K>> {
+        // The min-heap underneath sees the increase as a decrease, so its errors name the other direction
+        self.heap.decrease_key(value, key).map_err(|error| match error {
+            DecreaseKeyError::NotFound(key) => IncreaseKeyError::NotFound(key),
+            DecreaseKeyError::KeyGreaterThanCurrent(key) => IncreaseKeyError::KeyLessThanCurrent(key)
+        })
+    }
+    
+    /// Decreases the priority of the value to the key.
+    ///
+    /// Returns `Err(HeapError::NotFound)` if the value is not in the heap, or `Err(HeapError::KeyGreaterThanCurrent)`
+    /// if the key is greater than the current priority of the value.
+    pub fn decrease_key(&mut self, value: &V, key: K) -> Result<(), HeapError> {
+        self.heap.increase_key(value, key).map_err(|error| match error {
+            HeapError::KeyLessThanCurrent => HeapError::KeyGreaterThanCurrent,
+            error => error
+        })
+    }
+    
+    /// Changes the priority of the value to the key, whether the key is smaller or larger than the current priority.
+    ///
+    /// Returns the previous priority or `Err(HeapError::NotFound)` if the value is not in the heap.
+    pub fn change_priority(&mut self, value: &V, key: K) -> Result<K, HeapError> {
+        self.heap.change_priority(value, key)
+    }
+    
+    /// Deletes the value from the heap.
+    ///
+    /// Returns the priority the value had or `None` if the value is not in the heap.
+    pub fn delete(&mut self, value: &V) -> Option<K> {
+        self.heap.delete(value)
+    }
+}
+
+impl<K, V> Default for FibonacciMaxHeap<K, V>
+    where K: Ord,
+          V: Eq + Hash
+{
+    fn default() -> FibonacciMaxHeap<K, V> {
+        FibonacciMaxHeap::new()
+    }
+}
@@ -0,0 +1,169 @@
+use alloc::vec::Vec;
+use core::hash::Hash;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+#[cfg(not(feature = "std"))]
+use hashbrown::HashMap;
+
+use super::error::DecreaseKeyError;
+use super::fibonacci_heap::FibonacciHeap;
+
+/// A `FibonacciHeap` that can hold the same value more than once.
+///
+/// Every insertion adds a new instance of the value, each with its own priority. Extraction returns every instance
+/// exactly once. Operations on a value rather than on the minimum, such as `decrease_key` and `delete_one`, act on the
+/// instance of the value with the smallest key.
+pub struct FibonacciMultiHeap<K, V> {
+    // Each instance is stored under its value and a sequence number, so entries of the inner heap stay unique
+    heap: FibonacciHeap<K, (V, u64)>,
+    // Sequence numbers of the instances of each value
+    instances: HashMap<V, Vec<u64>>,
+    next: u64
+}
+
+impl<K, V> FibonacciMultiHeap<K, V>
+    where K: Ord,
+          V: Clone + Eq + Hash
+{
+    /// Creates a new empty `FibonacciMultiHeap`.
+    pub fn new() -> FibonacciMultiHeap<K, V> {
+        FibonacciMultiHeap {
+            heap: FibonacciHeap::new(),
+            instances: HashMap::new(),
+            next: 0
+        }
+    }
+    
+    /// Inserts a new instance of the value into the heap with priority key.
+    ///
+    /// Instances of the value that are already in the heap are left as they are.
+    pub fn insert(&mut self, key: K, value: V) {
+        let sequence = self.next;
+        self.next += 1;
+        
+        self.instances.entry(value.clone()).or_default().push(sequence);
+        self.heap.insert(key, (value, sequence));
+    }
+    
+    /// Peeks at the minimum of the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn minimum(&self) -> Option<(&K, &V)> {
+        self.heap.minimum().map(|(key, (value, _))| (key, value))
+    }
+    
+    /// Returns the number of instances in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    
+    /// Returns `true` if the heap contains no instances.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    
+    /// Returns `true` if at least one instance of the value is in the heap.
+    pub fn contains(&self, value: &V) -> bool {
+        self.instances.contains_key(value)
+    }
+    
+    /// Returns the number of instances of the value in the heap.
+    pub fn count(&self, value: &V) -> usize {
+        self.instances.get(value).map_or(0, |instances| instances.len())
+    }
+    
+    /// Looks up the smallest priority of any instance of the value.
+    ///
+    /// Returns `None` if the value is not in the heap.
+    pub fn get_priority(&self, value: &V) -> Option<&K> {
+        let sequence = self.smallest_instance(value)?;
+        
+        self.heap.get_priority(&(value.clone(), sequence))
+    }
+    
+    /// Extracts the minimum of the heap.
+    ///
+    /// Returns `None` if the heap is empty. Other instances of the extracted value stay in the heap.
+    pub fn extract_min(&mut self) -> Option<(K, V)> {
+        let (key, (value, sequence)) = self.heap.extract_min()?;
+        self.forget(&value, sequence);
+        
+        Some((key, value))
+    }
+    
+    /// Decreases the priority of the instance of the value with the smallest key to the key.
+    ///
+    /// Returns the previous priority, or `Err` holding the rejected key if the value is not in the heap or if the key is
+    /// greater than the smallest priority of the value.
+    pub fn decrease_key(&mut self, value: V, key: K) -> Result<K, DecreaseKeyError<K>> {
+        let sequence = match self.smallest_instance(&value) {
+            Some(sequence) => sequence,
+            None => return Err(DecreaseKeyError::NotFound(key))
+        };
+        
+        self.heap.decrease_key((value, sequence), key)
+    }
+    
+    /// Deletes the instance of the value with the smallest key from the heap.
+    ///
+    /// Returns the priority the instance had or `None` if the value is not in the heap.
+    pub fn delete_one(&mut self, value: &V) -> Option<K> {
+        let sequence = self.smallest_instance(value)?;
+        let key = self.heap.delete(&(value.clone(), sequence))?;
+        self.forget(value, sequence);
+        
+        Some(key)
+    }
+    
+    /// Deletes every instance of the value from the heap.
+    ///
+    /// Returns the priorities the instances had, in no particular order. The `Vec` is empty if the value is not in the
+    /// heap.
+    pub fn delete_all(&mut self, value: &V) -> Vec<K> {
+        let instances = match self.instances.remove(value) {
+            Some(instances) => instances,
+            None => return Vec::new()
+        };
+        
+        instances.into_iter()
+            .filter_map(|sequence| self.heap.delete(&(value.clone(), sequence)))
+            .collect()
+    }
+    
+    // Finds the sequence number of the instance of the value with the smallest key
+    fn smallest_instance(&self, value: &V) -> Option<u64> {
+        let instances = self.instances.get(value)?;
+        
+        instances.iter()
+            .cloned()
+            .min_by_key(|&sequence| self.heap.get_priority(&(value.clone(), sequence)))
+    }
+    
+    // Drops the bookkeeping of an instance that has left the heap
+    fn forget(&mut self, value: &V, sequence: u64) {
+        let empty = match self.instances.get_mut(value) {
+            Some(instances) => {
+                if let Some(position) = instances.iter().position(|&s| s == sequence) {
+                    instances.swap_remove(position);
+                }
+                
+                instances.is_empty()
+            },
+            None => false
+        };
+        
+        if empty {
+            self.instances.remove(value);
+        }
+    }
+}
+
+impl<K, V> Default for FibonacciMultiHeap<K, V>
+    where K: Ord,
+          V: Clone + Eq + Hash
+{
+    fn default() -> FibonacciMultiHeap<K, V> {
+        FibonacciMultiHeap::new()
+    }
+}
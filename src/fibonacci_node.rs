@@ -1,120 +1,526 @@
-use std::cell::RefCell;
-use std::rc::Rc;
-use std::collections::LinkedList;
-
-#[derive (Clone)]
-pub struct FibonacciNodeType<K, V> (Rc<RefCell<FibonacciNode<K, V>>>);
-
-pub trait FibNode<K, V>: Sized {
-    fn new(key: K, value: V) -> Self;
-    fn get_key(&self) -> K;
-    fn set_key(&self, key: K) -> ();
-    fn get_value(&self) -> V;
-    fn rank(&self) -> usize;
-    fn is_marked(&self) -> bool;
-    fn set_marked(&self, marked: bool) -> ();
-    fn get_parent(&self) -> Option<Self>;
-    fn set_parent(&self, parent: Option<Self>) -> ();
-    fn add_child(&self, child: Self) -> ();
-    fn remove_child(&self, child: Self) -> Option<Self>;
-    fn get_children(&self) -> LinkedList<FibonacciNodeType<K, V>>;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter;
+use core::mem;
+use core::ops::{Index, IndexMut};
+#[cfg(target_has_atomic = "64")]
+use core::sync::atomic::AtomicU64;
+#[cfg(not(target_has_atomic = "64"))]
+use core::sync::atomic::AtomicUsize;
+use core::sync::atomic::Ordering;
+
+use super::error::TryReserveError;
+
+// Every storage takes an id from one counter shared by every heap, so a handle can never match a node of another heap.
+// Generations are counted by each storage on its own, so inserting never touches the shared counter
+#[cfg(target_has_atomic = "64")]
+static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+// Targets without 64 bit atomics count in a usize, so there a handle can match another heap after 2^32 heaps
+#[cfg(not(target_has_atomic = "64"))]
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(target_has_atomic = "64")]
+fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+#[cfg(not(target_has_atomic = "64"))]
+fn next_id() -> u64 {
+    NEXT_ID.fetch_add(1, Ordering::Relaxed) as u64
 }
 
+// Nodes refer to each other by u32 indices, which halves the size of every link on 64 bit targets. NONE stands for no
+// node, so a storage holds at most MAX_NODES nodes
+const NONE: u32 = u32::MAX;
+pub const MAX_NODES: usize = NONE as usize;
+
+fn link(index: usize) -> u32 {
+    debug_assert!(index < MAX_NODES, "node index fits in a link");
+    index as u32
+}
 
+fn unlink(link: u32) -> Option<usize> {
+    if link == NONE { None } else { Some(link as usize) }
+}
 
+#[derive(Clone)]
 pub struct FibonacciNode<K, V> {
-    key: K,
-    value: V,
-    marked: bool,
-    parent: Option<FibonacciNodeType<K, V>>,
-    children: LinkedList<FibonacciNodeType<K, V>>
+    pub key: K,
+    pub value: V,
+    // Hash of the value, written by the lookup when the node is added to it
+    pub hash: u64,
+    pub marked: bool,
+    parent: u32,
+    pub children: NodeList,
     // Rank is children.len()
+    // Neighbours in the root list, or in the children of the parent
+    left: u32,
+    right: u32
 }
 
-impl<K, V> FibNode<K, V> for FibonacciNodeType<K, V>
-    where K: Clone + Eq,
-          V: Clone + Eq
-{
-    fn new(key: K, value: V) -> FibonacciNodeType<K, V> {
-        FibonacciNodeType(Rc::new(RefCell::new(FibonacciNode {
-            key: key,
-            value: value,
+impl<K, V> FibonacciNode<K, V> {
+    pub fn new(key: K, value: V) -> FibonacciNode<K, V> {
+        FibonacciNode {
+            key,
+            value,
+            hash: 0,
             marked: false,
-            parent: None,
-            children: LinkedList::new()
-        })))
+            parent: NONE,
+            children: NodeList::new(),
+            left: NONE,
+            right: NONE
+        }
+    }
+    
+    pub fn rank(&self) -> usize {
+        self.children.len()
+    }
+    
+    pub fn parent(&self) -> Option<usize> {
+        unlink(self.parent)
+    }
+    
+    pub fn set_parent(&mut self, parent: Option<usize>) {
+        self.parent = parent.map_or(NONE, link);
+    }
+    
+    pub fn left(&self) -> usize {
+        self.left as usize
+    }
+    
+    pub fn set_left(&mut self, left: usize) {
+        self.left = link(left);
+    }
+    
+    pub fn right(&self) -> usize {
+        self.right as usize
+    }
+    
+    pub fn set_right(&mut self, right: usize) {
+        self.right = link(right);
+    }
+}
+
+/// Storage for the nodes of a heap. Nodes refer to each other by their index in here.
+///
+/// Every node lives in one contiguous `Vec` instead of its own allocation, and parent and child links are plain
+/// indices, so there are no reference counts or borrow flags to maintain and no cycles to leak. Dropping the storage
+/// drops the nodes one after another, so it never recurses however deep the trees are.
+///
+/// The indices are `u32`, so a storage holds at most `MAX_NODES` nodes and growing it past that panics.
+pub struct FibonacciNodes<K, V> {
+    slots: Vec<Option<FibonacciNode<K, V>>>,
+    // Generation of the node in each slot, a fresh one is taken every time a slot is filled
+    generations: Vec<u64>,
+    // Taken from NEXT_ID once, handles carry it to tell heaps apart
+    id: u64,
+    next_generation: u64,
+    // Indices of empty slots that are reused before the storage grows
+    free: Vec<usize>
+}
+
+impl<K, V> FibonacciNodes<K, V> {
+    pub fn with_capacity(capacity: usize) -> FibonacciNodes<K, V> {
+        assert!(capacity <= MAX_NODES, "capacity overflow");
+        
+        FibonacciNodes {
+            slots: Vec::with_capacity(capacity),
+            generations: Vec::with_capacity(capacity),
+            id: next_id(),
+            next_generation: 0,
+            free: Vec::new()
+        }
     }
     
-    fn get_key(&self) -> K {
-        self.0.borrow().key.clone()
+    pub fn id(&self) -> u64 {
+        self.id
     }
     
-    fn set_key(&self, key: K) -> () {
-        self.0.borrow_mut().key = key;
+    fn next_generation(&mut self) -> u64 {
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        generation
     }
     
-    fn get_value(&self) -> V {
-        self.0.borrow().value.clone()
+    pub fn len(&self) -> usize {
+        self.slots.len() - self.free.len()
     }
     
-    fn rank(&self) -> usize {
-        self.0.borrow().children.len()
+    // Number of nodes that fit before the slots are reallocated, reusing every free slot
+    pub fn capacity(&self) -> usize {
+        self.slots.capacity()
     }
     
-    fn is_marked(&self) -> bool {
-        self.0.borrow().marked
+    // Bytes allocated for the slots and the bookkeeping next to them
+    pub fn allocation_size(&self) -> usize {
+        self.slots.capacity() * mem::size_of::<Option<FibonacciNode<K, V>>>()
+            + self.generations.capacity() * mem::size_of::<u64>()
+            + self.free.capacity() * mem::size_of::<usize>()
     }
     
-    fn set_marked(&self, marked: bool) -> () {
-        self.0.borrow_mut().marked = marked;
+    pub fn reserve(&mut self, additional: usize) {
+        let additional = additional.saturating_sub(self.free.len());
+        assert!(self.slots.len().saturating_add(additional) <= MAX_NODES, "capacity overflow");
+        self.slots.reserve(additional);
+        self.generations.reserve(additional);
     }
     
-    fn get_parent(&self) -> Option<FibonacciNodeType<K, V>> {
-        self.0.borrow().parent.clone()
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), TryReserveError> {
+        let additional = additional.saturating_sub(self.free.len());
+        
+        // Vec does not say which way it failed, so an impossible size is caught first
+        let capacity = self.slots.len().checked_add(additional).ok_or(TryReserveError::CapacityOverflow)?;
+        if capacity > MAX_NODES {
+            return Err(TryReserveError::CapacityOverflow);
+        }
+        let size = capacity.checked_mul(mem::size_of::<Option<FibonacciNode<K, V>>>());
+        if size.is_none_or(|size| size > isize::MAX as usize) {
+            return Err(TryReserveError::CapacityOverflow);
+        }
+        
+        self.slots.try_reserve(additional).map_err(|_| TryReserveError::AllocError)?;
+        self.generations.try_reserve(additional).map_err(|_| TryReserveError::AllocError)
     }
     
-    fn set_parent(&self, parent: Option<FibonacciNodeType<K, V>>) -> () {
-        self.0.borrow_mut().parent = parent;
+    // Number of slots removed nodes left free for the next inserts
+    pub fn free_len(&self) -> usize {
+        self.free.len()
     }
     
-    fn add_child(&self, child: FibonacciNodeType<K, V>) -> () {
-        self.0.borrow_mut().children.push_back(child);
+    // Moves the last nodes into the free slots before them until no slot is free, then gives back the space beyond
+    // capacity. Returns where each moved node was and where it is now, in the order they were moved. Links between
+    // nodes are already rewritten, only the lists and indices the caller keeps have to follow
+    pub fn shrink_to(&mut self, capacity: usize) -> Vec<(usize, usize)> {
+        let mut moves = Vec::new();
+        self.free.sort_unstable();
+        
+        let mut holes = mem::take(&mut self.free).into_iter();
+        loop {
+            while let Some(None) = self.slots.last() {
+                self.slots.pop();
+            }
+            
+            let last = self.slots.len().wrapping_sub(1);
+            match holes.next() {
+                Some(hole) if hole < last => {
+                    self.relocate(last, hole);
+                    self.slots.pop();
+                    moves.push((last, hole));
+                },
+                _ => break
+            }
+        }
+        
+        let len = self.slots.len();
+        self.generations.truncate(len);
+        self.slots.shrink_to(capacity);
+        self.generations.shrink_to(capacity);
+        self.free.shrink_to(capacity.saturating_sub(len));
+        
+        moves
+    }
+    
+    // Moves the node at from into the free slot to, pointing its neighbours, parent and children at the new slot
+    fn relocate(&mut self, from: usize, to: usize) {
+        let node = self.slots[from].take().expect("node index is not in use");
+        let (left, right, parent, children) = (node.left(), node.right(), node.parent(), node.children);
+        self.slots[to] = Some(node);
+        // The moved node keeps its generation, which no handle to the old occupant of to can have
+        self.generations[to] = self.generations[from];
+        
+        if left == from {
+            self[to].set_left(to);
+            self[to].set_right(to);
+        } else {
+            self[left].set_right(to);
+            self[right].set_left(to);
+        }
+        
+        if let Some(parent) = parent {
+            let mut siblings = self[parent].children;
+            siblings.relocate(from, to);
+            self[parent].children = siblings;
+        }
+        
+        let mut child = children.first();
+        for _ in 0..children.len() {
+            let x = child.expect("child list is as long as it says");
+            self[x].set_parent(Some(to));
+            child = Some(self[x].right());
+        }
     }
     
-    fn remove_child(&self, child: FibonacciNodeType<K, V>) -> Option<FibonacciNodeType<K, V>> {
-        let children = &mut self.0.borrow_mut().children;
+    pub fn insert(&mut self, node: FibonacciNode<K, V>) -> usize {
+        let generation = self.next_generation();
         
-        remove_element(children, child)
+        match self.free.pop() {
+            Some(index) => {
+                self.slots[index] = Some(node);
+                self.generations[index] = generation;
+                index
+            },
+            None => {
+                assert!(self.slots.len() < MAX_NODES, "a heap holds at most {} entries", MAX_NODES);
+                self.slots.push(Some(node));
+                self.generations.push(generation);
+                self.slots.len() - 1
+            }
+        }
+    }
+    
+    pub fn remove(&mut self, index: usize) -> FibonacciNode<K, V> {
+        let node = self.slots[index].take().expect("node index is not in use");
+        self.free.push(index);
+        node
+    }
+    
+    // Generation of the node at index, or None if the index is not in use
+    pub fn generation(&self, index: usize) -> Option<u64> {
+        match self.slots.get(index) {
+            Some(Some(_)) => Some(self.generations[index]),
+            _ => None
+        }
     }
     
-    fn get_children(&self) -> LinkedList<FibonacciNodeType<K, V>> {
-        self.0.borrow().children.clone()
+    // Gives the node at index a fresh generation, for when it is reused for a different entry
+    pub fn renew(&mut self, index: usize) {
+        self.generations[index] = self.next_generation();
+    }
+    
+    // Empties the storage, handing out every slot while keeping the allocation
+    pub fn drain(&mut self) -> vec::Drain<'_, Option<FibonacciNode<K, V>>> {
+        self.free.clear();
+        self.generations.clear();
+        self.slots.drain(..)
+    }
+    
+    // One past the largest index that can be in use
+    pub fn slot_count(&self) -> usize {
+        self.slots.len()
+    }
+    
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &FibonacciNode<K, V>)> {
+        self.slots.iter()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.as_ref().map(|node| (index, node)))
+    }
+    
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (usize, &mut FibonacciNode<K, V>)> {
+        self.slots.iter_mut()
+            .enumerate()
+            .filter_map(|(index, slot)| slot.as_mut().map(|node| (index, node)))
+    }
+    
+    /// Moves every node of `other` into this storage, leaving `other` empty with its allocations kept.
+    ///
+    /// Returns the new index of each of `other`'s indices, links between the moved nodes are already rewritten.
+    pub fn append(&mut self, other: &mut FibonacciNodes<K, V>) -> Vec<usize> {
+        let mut mapping = vec![usize::MAX; other.slots.len()];
+        let mut moved = Vec::with_capacity(other.len());
+        
+        other.free.clear();
+        other.generations.clear();
+        for (old_index, slot) in other.slots.drain(..).enumerate() {
+            if let Some(node) = slot {
+                let index = self.insert(node);
+                mapping[old_index] = index;
+                moved.push(index);
+            }
+        }
+        
+        for index in moved {
+            let node = &mut self[index];
+            node.set_parent(node.parent().map(|parent| mapping[parent]));
+            node.children = node.children.remap(&mapping);
+            node.set_left(mapping[node.left()]);
+            node.set_right(mapping[node.right()]);
+        }
+        
+        mapping
+    }
+    
+    /// Takes the nodes of `other` over without moving them, leaving `other` with the storage of this one.
+    ///
+    /// Each storage keeps its id, and both go on from the larger generation count, so a handle made for a node of one
+    /// storage never finds another node.
+    pub fn swap_nodes(&mut self, other: &mut FibonacciNodes<K, V>) {
+        let ids = (self.id, other.id);
+        mem::swap(self, other);
+        self.id = ids.0;
+        other.id = ids.1;
+        
+        let generation = self.next_generation.max(other.next_generation);
+        self.next_generation = generation;
+        other.next_generation = generation;
+    }
+    
+    // Adds child at the end of the children of parent, leaving the parent link to the caller
+    pub fn push_child(&mut self, parent: usize, child: usize) {
+        let mut children = self[parent].children;
+        children.push(self, child);
+        self[parent].children = children;
+    }
+    
+    // Unlinks child from the children of parent, leaving the parent link to the caller
+    pub fn remove_child(&mut self, parent: usize, child: usize) {
+        let mut children = self[parent].children;
+        children.remove(self, child);
+        self[parent].children = children;
     }
 }
 
-impl<K, V> PartialEq for FibonacciNodeType<K, V>
-    where K: Eq,
-          V: Eq
-{
-    fn eq(&self, other: &FibonacciNodeType<K, V>) -> bool {
-        self.0.borrow().value == other.0.borrow().value && self.0.borrow().key == other.0.borrow().key
+/// The roots of a heap, or the children of a node, kept as a circular doubly linked list through the `left` and
+/// `right` links of the nodes.
+///
+/// Every node is in exactly one list at a time. Adding, removing and splicing nodes only rewrites links, without
+/// hashing or allocating.
+#[derive(Clone, Copy)]
+pub struct NodeList {
+    head: u32,
+    len: u32
+}
+
+impl NodeList {
+    pub fn new() -> NodeList {
+        NodeList {
+            head: NONE,
+            len: 0
+        }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+    
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+    
+    pub fn first(&self) -> Option<usize> {
+        unlink(self.head)
+    }
+    
+    // Adds x at the end of the list, just before the head
+    pub fn push<K, V>(&mut self, nodes: &mut FibonacciNodes<K, V>, x: usize) {
+        match self.first() {
+            Some(head) => {
+                let last = nodes[head].left();
+                nodes[x].set_left(last);
+                nodes[x].set_right(head);
+                nodes[last].set_right(x);
+                nodes[head].set_left(x);
+            },
+            None => {
+                nodes[x].set_left(x);
+                nodes[x].set_right(x);
+                self.head = link(x);
+            }
+        }
+        
+        self.len += 1;
+    }
+    
+    // Unlinks x, which has to be in the list
+    pub fn remove<K, V>(&mut self, nodes: &mut FibonacciNodes<K, V>, x: usize) {
+        let (left, right) = (nodes[x].left(), nodes[x].right());
+        nodes[left].set_right(right);
+        nodes[right].set_left(left);
+        
+        self.len -= 1;
+        if self.len == 0 {
+            self.head = NONE;
+        } else if self.first() == Some(x) {
+            self.head = link(right);
+        }
+    }
+    
+    // Unlinks and returns the head
+    pub fn pop<K, V>(&mut self, nodes: &mut FibonacciNodes<K, V>) -> Option<usize> {
+        let head = self.first()?;
+        self.remove(nodes, head);
+        
+        Some(head)
+    }
+    
+    // Moves every node of other to the end of this list in O(1) time
+    pub fn append<K, V>(&mut self, nodes: &mut FibonacciNodes<K, V>, other: NodeList) {
+        let (head, other_head) = match (self.first(), other.first()) {
+            (Some(head), Some(other_head)) => (head, other_head),
+            (None, _) => {
+                *self = other;
+                return;
+            },
+            (_, None) => return
+        };
+        
+        let last = nodes[head].left();
+        let other_last = nodes[other_head].left();
+        nodes[last].set_right(other_head);
+        nodes[other_head].set_left(last);
+        nodes[other_last].set_right(head);
+        nodes[head].set_left(other_last);
+        
+        self.len += other.len;
+    }
+    
+    // Follows the head of the list from one slot to another, after `FibonacciNodes::shrink_to` moved it
+    pub fn relocate(&mut self, from: usize, to: usize) {
+        if self.first() == Some(from) {
+            self.head = link(to);
+        }
+    }
+    
+    // Points the list at the new position of its head after the nodes were moved by `FibonacciNodes::append`
+    pub fn remap(self, mapping: &[usize]) -> NodeList {
+        NodeList {
+            head: self.first().map_or(NONE, |head| link(mapping[head])),
+            len: self.len
+        }
+    }
+    
+    pub fn iter<'a, K, V>(&self, nodes: &'a FibonacciNodes<K, V>) -> impl Iterator<Item = usize> + 'a {
+        iter::successors(self.first(), move |&x| Some(nodes[x].right())).take(self.len())
+    }
+    
+    // Iterates from the last node back to the head
+    pub fn iter_rev<'a, K, V>(&self, nodes: &'a FibonacciNodes<K, V>) -> impl Iterator<Item = usize> + 'a {
+        iter::successors(self.first().map(|head| nodes[head].left()), move |&x| Some(nodes[x].left())).take(self.len())
     }
 }
 
-impl<K, V> Eq for FibonacciNodeType<K, V> where K: Eq, V: Eq { }
+impl Default for NodeList {
+    fn default() -> NodeList {
+        NodeList::new()
+    }
+}
 
-pub fn remove_element<T>(list: &mut LinkedList<T>, element: T) -> Option<T>
-    where T: Eq
+impl<K, V> Clone for FibonacciNodes<K, V>
+    where K: Clone,
+          V: Clone
 {
-    for _ in 0..list.len() {
-        if *list.front().unwrap() == element {
-            return list.pop_front();
+    // Nodes keep their indices and generations, but the copy has an id of its own so handles into the original do not
+    // match it
+    fn clone(&self) -> FibonacciNodes<K, V> {
+        FibonacciNodes {
+            slots: self.slots.clone(),
+            generations: self.generations.clone(),
+            id: next_id(),
+            next_generation: self.next_generation,
+            free: self.free.clone()
         }
-        
-        list.pop_back().map(|tail| {
-            list.push_front(tail)
-        });
     }
+}
+
+impl<K, V> Index<usize> for FibonacciNodes<K, V> {
+    type Output = FibonacciNode<K, V>;
     
-    None
+    fn index(&self, index: usize) -> &FibonacciNode<K, V> {
+        self.slots[index].as_ref().expect("node index is not in use")
+    }
+}
+
+impl<K, V> IndexMut<usize> for FibonacciNodes<K, V> {
+    fn index_mut(&mut self, index: usize) -> &mut FibonacciNode<K, V> {
+        self.slots[index].as_mut().expect("node index is not in use")
+    }
 }
\ No newline at end of file
@@ -0,0 +1,47 @@
+use core::hash::Hash;
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use super::compare::Compare;
+use super::fibonacci_heap::FibonacciHeap;
+
+/// Builds a heap by running a sequence of operations drawn from the input, ordered by the default comparator of C.
+///
+/// Inserts are mixed with extractions, which link the roots into trees, and with priority changes and deletions of
+/// entries already in the heap, which cut nodes and mark their parents. A generated heap therefore has the deep trees
+/// and marked nodes a heap gets from real use, not only a forest of freshly inserted roots. The number of operations
+/// is bounded by the length of the input.
+impl<'a, K, V, C> Arbitrary<'a> for FibonacciHeap<K, V, C>
+    where K: Arbitrary<'a>,
+          V: Clone + Eq + Hash + Arbitrary<'a>,
+          C: Compare<K> + Default
+{
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<FibonacciHeap<K, V, C>> {
+        let mut heap = FibonacciHeap::with_comparator(C::default());
+        let operations = u.arbitrary_len::<(u8, K, V)>()?;
+        
+        for _ in 0..operations {
+            match u.int_in_range(0..=9)? {
+                0..=5 => heap.insert(K::arbitrary(u)?, V::arbitrary(u)?),
+                6 => {
+                    heap.extract_min();
+                },
+                operation => {
+                    if heap.is_empty() {
+                        continue;
+                    }
+                    
+                    let index = u.choose_index(heap.len())?;
+                    let value = heap.values().nth(index).cloned().expect("index is less than the length");
+                    if operation == 9 {
+                        heap.delete(&value);
+                    } else {
+                        let _ = heap.change_priority(&value, K::arbitrary(u)?);
+                    }
+                }
+            }
+        }
+        
+        Ok(heap)
+    }
+}
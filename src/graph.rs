@@ -0,0 +1,69 @@
+use core::cmp::Ordering;
+use core::hash::Hash;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use petgraph::algo::Measure;
+use petgraph::visit::{EdgeRef, IntoEdges, VisitMap, Visitable};
+
+use super::compare::FnComparator;
+use super::fibonacci_heap::FibonacciHeap;
+
+/// Finds the cost of the shortest path from `start` to every node reachable from it, as `petgraph::algo::dijkstra`
+/// does.
+///
+/// Each node is in the heap at most once, and finding a cheaper path to it decreases its key with `decrease_key`
+/// instead of pushing it again. If `goal` is given the search stops once the goal is reached, so only some costs are
+/// final. `edge_cost` must not return negative costs. A cost that is not equal to itself, such as NaN, comes after
+/// every other cost.
+pub fn dijkstra<G, F, K>(graph: G, start: G::NodeId, goal: Option<G::NodeId>, mut edge_cost: F) -> HashMap<G::NodeId, K>
+    where G: IntoEdges + Visitable,
+          G::NodeId: Eq + Hash,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy
+{
+    let mut visited = graph.visit_map();
+    let mut scores = HashMap::new();
+    let mut heap = FibonacciHeap::with_comparator(FnComparator(compare_costs::<K>));
+    
+    scores.insert(start, K::default());
+    heap.insert(K::default(), start);
+    
+    while let Some((cost, node)) = heap.extract_min() {
+        visited.visit(node);
+        if goal == Some(node) {
+            break;
+        }
+        
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            if visited.is_visited(&next) {
+                continue;
+            }
+            
+            let next_cost = cost + edge_cost(edge);
+            match scores.entry(next) {
+                Entry::Occupied(mut entry) => {
+                    if compare_costs(&next_cost, entry.get()) == Ordering::Less {
+                        entry.insert(next_cost);
+                        // Every node with a score that is not visited yet is still in the heap
+                        heap.decrease_key(next, next_cost).expect("node is in the heap");
+                    }
+                },
+                Entry::Vacant(entry) => {
+                    entry.insert(next_cost);
+                    heap.insert(next_cost, next);
+                }
+            }
+        }
+    }
+    
+    scores
+}
+
+// Costs only have a partial order, those that do not compare to themselves are put last
+fn compare_costs<K>(a: &K, b: &K) -> Ordering
+    where K: PartialOrd
+{
+    a.partial_cmp(b).unwrap_or_else(|| a.partial_cmp(a).is_none().cmp(&b.partial_cmp(b).is_none()))
+}
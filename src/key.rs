@@ -0,0 +1,64 @@
+use core::cmp::Ordering;
+
+/// Keys that a delta can be subtracted from, as used by `FibonacciHeap::decrease_key_by`.
+pub trait CheckedSub: Sized {
+    /// Subtracts `delta` from `self`, returning `None` if the result overflows.
+    fn checked_sub(&self, delta: &Self) -> Option<Self>;
+}
+
+macro_rules! impl_checked_sub {
+    ($($t:ty)*) => ($(
+        impl CheckedSub for $t {
+            fn checked_sub(&self, delta: &$t) -> Option<$t> {
+                <$t>::checked_sub(*self, *delta)
+            }
+        }
+    )*)
+}
+
+impl_checked_sub! { u8 u16 u32 u64 u128 usize i8 i16 i32 i64 i128 isize }
+
+/// A floating point key with a total order, so `f32` and `f64` priorities can be used in a `FibonacciHeap`.
+///
+/// Keys are ordered as the floats are, except that `NaN` is greater than every other key and equal to any other `NaN`,
+/// so it is extracted last. `-0.0` and `0.0` are equal.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FloatKey<T>(pub T);
+
+macro_rules! impl_float_key {
+    ($($t:ty)*) => ($(
+        impl Ord for FloatKey<$t> {
+            fn cmp(&self, other: &FloatKey<$t>) -> Ordering {
+                match (self.0.is_nan(), other.0.is_nan()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    // Neither is NaN, so the floats are always comparable
+                    (false, false) => self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal)
+                }
+            }
+        }
+        
+        impl PartialOrd for FloatKey<$t> {
+            fn partial_cmp(&self, other: &FloatKey<$t>) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        
+        impl PartialEq for FloatKey<$t> {
+            fn eq(&self, other: &FloatKey<$t>) -> bool {
+                self.cmp(other) == Ordering::Equal
+            }
+        }
+        
+        impl Eq for FloatKey<$t> { }
+        
+        impl From<$t> for FloatKey<$t> {
+            fn from(value: $t) -> FloatKey<$t> {
+                FloatKey(value)
+            }
+        }
+    )*)
+}
+
+impl_float_key! { f32 f64 }
@@ -0,0 +1,156 @@
+use core::borrow::Borrow;
+use core::hash::{Hash, Hasher};
+use core::mem;
+
+use super::error::{DecreaseKeyError, HeapError};
+use super::fibonacci_heap::FibonacciHeap;
+
+/// A `FibonacciHeap` whose entries carry a payload next to the value identifying them.
+///
+/// Only the id, I, is hashed to find entries again, the payload, P, can be any type and is never cloned. Neither has
+/// to be `Clone`. The order of the heap only depends on the keys, so the payloads can be changed freely through
+/// `payload_mut`.
+pub struct KeyedFibonacciHeap<K, I, P> {
+    heap: FibonacciHeap<K, Keyed<I, P>>
+}
+
+// The value of an entry, hashed and compared by its id alone so the heap can find it by the id
+struct Keyed<I, P> {
+    id: I,
+    payload: P
+}
+
+impl<I, P> PartialEq for Keyed<I, P>
+    where I: PartialEq
+{
+    fn eq(&self, other: &Keyed<I, P>) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<I, P> Eq for Keyed<I, P>
+    where I: Eq
+{ }
+
+impl<I, P> Hash for Keyed<I, P>
+    where I: Hash
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<I, P> Borrow<I> for Keyed<I, P> {
+    fn borrow(&self) -> &I {
+        &self.id
+    }
+}
+
+impl<K, I, P> KeyedFibonacciHeap<K, I, P>
+    where K: Ord,
+          I: Eq + Hash
+{
+    /// Creates a new empty `KeyedFibonacciHeap`.
+    pub fn new() -> KeyedFibonacciHeap<K, I, P> {
+        KeyedFibonacciHeap {
+            heap: FibonacciHeap::new()
+        }
+    }
+    
+    /// Inserts the id with its payload into the heap with priority key.
+    ///
+    /// If the id is already in the heap its entry is replaced, and the old payload is returned.
+    pub fn insert_with_payload(&mut self, key: K, id: I, payload: P) -> Option<P> {
+        if let Some(entry) = self.heap.get_value_mut(&id) {
+            let old = mem::replace(&mut entry.payload, payload);
+            let _ = self.heap.change_priority(&id, key);
+            return Some(old);
+        }
+        
+        self.heap.insert(key, Keyed { id, payload });
+        
+        None
+    }
+    
+    /// Peeks at the minimum of the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn minimum(&self) -> Option<(&K, &I, &P)> {
+        self.heap.minimum().map(|(key, entry)| (key, &entry.id, &entry.payload))
+    }
+    
+    /// Returns the number of entries in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    
+    /// Returns `true` if the heap contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    
+    /// Returns `true` if the id is in the heap.
+    pub fn contains(&self, id: &I) -> bool {
+        self.heap.contains(id)
+    }
+    
+    /// Looks up the current priority of the id.
+    ///
+    /// Returns `None` if the id is not in the heap.
+    pub fn get_priority(&self, id: &I) -> Option<&K> {
+        self.heap.get_priority(id)
+    }
+    
+    /// Returns the payload of the id, or `None` if the id is not in the heap.
+    pub fn payload(&self, id: &I) -> Option<&P> {
+        let handle = self.heap.handle(id)?;
+        self.heap.get_handle(handle).map(|(_, entry)| &entry.payload)
+    }
+    
+    /// Returns the payload of the id for modification, or `None` if the id is not in the heap.
+    pub fn payload_mut(&mut self, id: &I) -> Option<&mut P> {
+        self.heap.get_value_mut(id).map(|entry| &mut entry.payload)
+    }
+    
+    /// Extracts the minimum of the heap along with its payload.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn extract_min(&mut self) -> Option<(K, I, P)> {
+        self.heap.extract_min().map(|(key, entry)| (key, entry.id, entry.payload))
+    }
+    
+    /// Decreases the priority of the id to the key.
+    ///
+    /// Returns the previous priority, or `Err` holding the rejected key if the id is not in the heap or if the key is
+    /// greater than the current priority of the id.
+    pub fn decrease_key(&mut self, id: I, key: K) -> Result<K, DecreaseKeyError<K>> {
+        match self.heap.handle(&id) {
+            Some(handle) => self.heap.decrease_key_handle(handle, key),
+            None => Err(DecreaseKeyError::NotFound(key))
+        }
+    }
+    
+    /// Changes the priority of the id to the key, whether the key is smaller or larger than the current priority.
+    ///
+    /// Returns the previous priority or `Err(HeapError::NotFound)` if the id is not in the heap.
+    pub fn change_priority(&mut self, id: &I, key: K) -> Result<K, HeapError> {
+        self.heap.change_priority(id, key)
+    }
+    
+    /// Deletes the id from the heap.
+    ///
+    /// Returns the priority and the payload the id had or `None` if the id is not in the heap.
+    pub fn delete(&mut self, id: &I) -> Option<(K, P)> {
+        let handle = self.heap.handle(id)?;
+        self.heap.delete_handle(handle).map(|(key, entry)| (key, entry.payload))
+    }
+}
+
+impl<K, I, P> Default for KeyedFibonacciHeap<K, I, P>
+    where K: Ord,
+          I: Eq + Hash
+{
+    fn default() -> KeyedFibonacciHeap<K, I, P> {
+        KeyedFibonacciHeap::new()
+    }
+}
@@ -1,4 +1,76 @@
-pub use self::fibonacci_heap::FibonacciHeap;
+#![no_std]
+#![forbid(unsafe_code)]
 
+extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+#[cfg(feature = "arbitrary")]
+extern crate arbitrary;
+#[cfg(feature = "bincode")]
+extern crate bincode;
+extern crate hashbrown;
+#[cfg(test)]
+extern crate itertools;
+#[cfg(feature = "petgraph")]
+extern crate petgraph;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "serde")]
+extern crate serde;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+
+pub use self::bounded_fibonacci_heap::BoundedFibonacciHeap;
+pub use self::compare::{Compare, FnComparator, KeyComparator, MaxComparator, MinComparator};
+pub use self::error::{
+    DecreaseKeyByError, DecreaseKeyError, HeapError, IncreaseKeyError, InvariantViolation, OccupiedError, SnapshotError,
+    TryPushError, TryReserveError
+};
+#[cfg(feature = "std")]
+pub use self::event::EventLog;
+pub use self::event::HeapEvent;
+pub use self::fibonacci_heap::{
+    BulkInsert, Drain, DrainSorted, Entry, FibonacciHeap, Handle, HeapStats, IntoIter, MemoryUsage, OccupiedEntry,
+    PeekMut, VacantEntry, VisitInfo
+};
+#[cfg(feature = "instrument")]
+pub use self::fibonacci_heap::Counters;
+pub use self::fibonacci_max_heap::FibonacciMaxHeap;
+pub use self::fibonacci_multi_heap::FibonacciMultiHeap;
+#[cfg(feature = "petgraph")]
+pub use self::graph::dijkstra;
+pub use self::key::{CheckedSub, FloatKey};
+pub use self::keyed_fibonacci_heap::KeyedFibonacciHeap;
+pub use self::merge::kway_merge;
+pub use self::offset_fibonacci_heap::OffsetFibonacciHeap;
+pub use self::snapshot::{HeapSnapshot, SnapshotNode};
+pub use self::sort::{heapsort, HeapSortExt};
+pub use self::stable_fibonacci_heap::StableFibonacciHeap;
+
+mod bounded_fibonacci_heap;
+mod compare;
+mod error;
+mod event;
 mod fibonacci_heap;
-mod fibonacci_node;
\ No newline at end of file
+mod fibonacci_max_heap;
+mod fibonacci_multi_heap;
+mod fibonacci_node;
+#[cfg(feature = "arbitrary")]
+mod fuzz;
+#[cfg(feature = "petgraph")]
+mod graph;
+mod key;
+mod keyed_fibonacci_heap;
+mod lookup;
+mod merge;
+mod offset_fibonacci_heap;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "serde")]
+mod serialize;
+mod snapshot;
+mod sort;
+mod stable_fibonacci_heap;
+#[cfg(test)]
+mod tests;
\ No newline at end of file
@@ -0,0 +1,117 @@
+use core::borrow::Borrow;
+use core::hash::{BuildHasher, Hash};
+#[cfg(feature = "std")]
+use std::collections::hash_map::RandomState;
+
+#[cfg(not(feature = "std"))]
+use hashbrown::DefaultHashBuilder as RandomState;
+use hashbrown::HashTable;
+
+use super::error::TryReserveError;
+use super::fibonacci_node::FibonacciNodes;
+
+/// Finds the node that holds a value.
+///
+/// Only node indices are stored, the values are hashed and compared where they sit in their nodes, so a value is never
+/// copied into the table. Every index in the table must refer to a node that is still in the storage.
+///
+/// The hash of a value is kept in its node when it is added, so growing the table and removing nodes never hash a
+/// value again. Indices are kept as `u32`, like the links between nodes.
+#[derive(Clone)]
+pub struct Lookup {
+    table: HashTable<u32>,
+    hasher: RandomState
+}
+
+impl Lookup {
+    pub fn with_capacity(capacity: usize) -> Lookup {
+        Lookup {
+            table: HashTable::with_capacity(capacity),
+            hasher: RandomState::default()
+        }
+    }
+    
+    pub fn len(&self) -> usize {
+        self.table.len()
+    }
+    
+    pub fn allocation_size(&self) -> usize {
+        self.table.allocation_size()
+    }
+    
+    pub fn clear(&mut self) {
+        self.table.clear();
+    }
+    
+    pub fn reserve<K, V>(&mut self, additional: usize, nodes: &FibonacciNodes<K, V>) {
+        self.table.reserve(additional, |&x| nodes[x as usize].hash);
+    }
+    
+    pub fn try_reserve<K, V>(&mut self, n: usize, nodes: &FibonacciNodes<K, V>) -> Result<(), TryReserveError> {
+        self.table.try_reserve(n, |&x| nodes[x as usize].hash).map_err(|error| match error {
+            hashbrown::TryReserveError::CapacityOverflow => TryReserveError::CapacityOverflow,
+            hashbrown::TryReserveError::AllocError { .. } => TryReserveError::AllocError
+        })
+    }
+    
+    pub fn shrink_to_fit<K, V>(&mut self, nodes: &FibonacciNodes<K, V>) {
+        self.table.shrink_to_fit(|&x| nodes[x as usize].hash);
+    }
+    
+    // The value may be any borrowed form of the values in the table, as for the lookups of a HashMap
+    pub fn get<K, V, Q>(&self, nodes: &FibonacciNodes<K, V>, value: &Q) -> Option<usize>
+        where V: Borrow<Q>,
+              Q: Eq + Hash + ?Sized
+    {
+        self.get_hashed(nodes, self.hash(value), value)
+    }
+    
+    // Adds the node at index, whose value must not be in the table yet, and stores the hash of the value in the node
+    pub fn insert<K, V>(&mut self, nodes: &mut FibonacciNodes<K, V>, index: usize)
+        where V: Hash
+    {
+        let hash = self.hash(&nodes[index].value);
+        self.insert_hashed(nodes, index, hash);
+    }
+    
+    // The hash the table files the value under, so a caller that both looks up and inserts a value hashes it once
+    pub fn hash<Q>(&self, value: &Q) -> u64
+        where Q: Hash + ?Sized
+    {
+        self.hasher.hash_one(value)
+    }
+    
+    pub fn get_hashed<K, V, Q>(&self, nodes: &FibonacciNodes<K, V>, hash: u64, value: &Q) -> Option<usize>
+        where V: Borrow<Q>,
+              Q: Eq + ?Sized
+    {
+        self.table.find(hash, |&x| nodes[x as usize].value.borrow() == value).map(|&x| x as usize)
+    }
+    
+    // Adds the node at index as insert does, with the hash of its value already known
+    pub fn insert_hashed<K, V>(&mut self, nodes: &mut FibonacciNodes<K, V>, index: usize, hash: u64) {
+        nodes[index].hash = hash;
+        
+        let nodes = &*nodes;
+        self.table.insert_unique(hash, index as u32, |&x| nodes[x as usize].hash);
+    }
+    
+    // Points the entry of the node that moved from one index to another at its new index, the node is already at to
+    pub fn relocate<K, V>(&mut self, nodes: &FibonacciNodes<K, V>, from: usize, to: usize) {
+        if let Some(x) = self.table.find_mut(nodes[to].hash, |&x| x as usize == from) {
+            *x = to as u32;
+        }
+    }
+    
+    // Removes the node at index, which has to be done while the node is still in the storage with its hash
+    pub fn remove<K, V>(&mut self, nodes: &FibonacciNodes<K, V>, index: usize) {
+        let hash = nodes[index].hash;
+        if let Ok(entry) = self.table.find_entry(hash, |&x| x as usize == index) {
+            entry.remove();
+        }
+    }
+    
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.table.iter().map(|&x| x as usize)
+    }
+}
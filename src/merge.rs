@@ -0,0 +1,54 @@
+use alloc::vec::Vec;
+
+use super::fibonacci_heap::FibonacciHeap;
+
+/// Merges sorted iterators into one sorted iterator.
+///
+/// Each iterator has to yield its items in ascending order. The heap holds the next item of every iterator that is not
+/// exhausted, so each item costs O(log k) amortized time for k iterators. Equal items come out in the order of the
+/// iterators they came from.
+pub fn kway_merge<I, T>(mut iters: Vec<I>) -> impl Iterator<Item = T>
+    where I: Iterator<Item = T>,
+          T: Ord
+{
+    let mut heap = FibonacciHeap::with_capacity(iters.len());
+    for (index, iter) in iters.iter_mut().enumerate() {
+        if let Some(item) = iter.next() {
+            heap.insert((item, index), index);
+        }
+    }
+    
+    KWayMerge {
+        heap,
+        iters
+    }
+}
+
+// The heads are keyed by their iterator as well as by themselves, so ties are broken by the order of the iterators
+struct KWayMerge<I, T> {
+    heap: FibonacciHeap<(T, usize), usize>,
+    iters: Vec<I>
+}
+
+impl<I, T> Iterator for KWayMerge<I, T>
+    where I: Iterator<Item = T>,
+          T: Ord
+{
+    type Item = T;
+    
+    fn next(&mut self) -> Option<T> {
+        let ((item, index), _) = self.heap.extract_min()?;
+        if let Some(next) = self.iters[index].next() {
+            self.heap.insert((next, index), index);
+        }
+        
+        Some(item)
+    }
+    
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iters.iter().fold((self.heap.len(), Some(self.heap.len())), |(lower, upper), iter| {
+            let (iter_lower, iter_upper) = iter.size_hint();
+            (lower.saturating_add(iter_lower), upper.and_then(|upper| upper.checked_add(iter_upper?)))
+        })
+    }
+}
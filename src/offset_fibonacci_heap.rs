@@ -0,0 +1,132 @@
+use core::hash::Hash;
+use core::ops::{Add, Sub};
+
+use super::error::{DecreaseKeyError, HeapError};
+use super::fibonacci_heap::FibonacciHeap;
+
+/// A `FibonacciHeap` that adds a delta to every priority in O(1) time.
+///
+/// The heap stores each key less an offset kept for the whole heap, and `add_to_all_keys` only changes the offset.
+/// Priorities are computed as they are read, so they are returned by value. Adding the same delta to every key has to
+/// keep their order, as it does for integers that do not overflow, and every priority less the offset has to be
+/// representable as well.
+pub struct OffsetFibonacciHeap<K, V> {
+    heap: FibonacciHeap<K, V>,
+    // Added to every stored key to give the priority of its value
+    offset: K
+}
+
+impl<K, V> OffsetFibonacciHeap<K, V>
+    where K: Ord + Clone + Default + Add<Output = K> + Sub<Output = K>,
+          V: Eq + Hash
+{
+    /// Creates a new empty `OffsetFibonacciHeap`, with an offset of `K::default()`.
+    pub fn new() -> OffsetFibonacciHeap<K, V> {
+        OffsetFibonacciHeap {
+            heap: FibonacciHeap::new(),
+            offset: K::default()
+        }
+    }
+    
+    /// Inserts the value into the heap with priority key, or changes its priority to key if it is already in the heap.
+    pub fn insert(&mut self, key: K, value: V) {
+        let key = key - self.offset.clone();
+        self.heap.insert(key, value);
+    }
+    
+    /// Adds delta to the priority of every value in the heap, in O(1) time.
+    pub fn add_to_all_keys(&mut self, delta: K) {
+        self.offset = self.offset.clone() + delta;
+    }
+    
+    /// Peeks at the minimum of the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn minimum(&self) -> Option<(K, &V)> {
+        self.heap.minimum().map(|(key, value)| (key.clone() + self.offset.clone(), value))
+    }
+    
+    /// Returns the number of values in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    
+    /// Returns `true` if the heap contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    
+    /// Returns `true` if the value is in the heap.
+    pub fn contains(&self, value: &V) -> bool {
+        self.heap.contains(value)
+    }
+    
+    /// Looks up the current priority of the value.
+    ///
+    /// Returns `None` if the value is not in the heap.
+    pub fn get_priority(&self, value: &V) -> Option<K> {
+        self.heap.get_priority(value).map(|key| key.clone() + self.offset.clone())
+    }
+    
+    /// Extracts the minimum of the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn extract_min(&mut self) -> Option<(K, V)> {
+        let offset = self.offset.clone();
+        self.heap.extract_min().map(|(key, value)| (key + offset, value))
+    }
+    
+    /// Decreases the priority of the value to the key.
+    ///
+    /// Returns the previous priority, or `Err` holding the rejected key if the value is not in the heap or if the key is
+    /// greater than the current priority of the value.
+    pub fn decrease_key(&mut self, value: V, key: K) -> Result<K, DecreaseKeyError<K>> {
+        let offset = self.offset.clone();
+        match self.heap.decrease_key(value, key - offset.clone()) {
+            Ok(old) => Ok(old + offset),
+            Err(DecreaseKeyError::NotFound(key)) => Err(DecreaseKeyError::NotFound(key + offset)),
+            Err(DecreaseKeyError::KeyGreaterThanCurrent(key)) => {
+                Err(DecreaseKeyError::KeyGreaterThanCurrent(key + offset))
+            }
+        }
+    }
+    
+    /// Changes the priority of the value to the key, whether the key is smaller or larger than the current priority.
+    ///
+    /// Returns the previous priority or `Err(HeapError::NotFound)` if the value is not in the heap.
+    pub fn change_priority(&mut self, value: &V, key: K) -> Result<K, HeapError> {
+        let offset = self.offset.clone();
+        self.heap.change_priority(value, key - offset.clone()).map(|old| old + offset)
+    }
+    
+    /// Deletes the value from the heap.
+    ///
+    /// Returns the priority the value had or `None` if the value is not in the heap.
+    pub fn delete(&mut self, value: &V) -> Option<K> {
+        let offset = self.offset.clone();
+        self.heap.delete(value).map(|key| key + offset)
+    }
+    
+    /// Melds `other` into this heap, keeping the priorities of both.
+    ///
+    /// If the offsets differ the keys of `other` are shifted to this heap's offset first, in O(m) time for the m
+    /// entries of `other`. A value in both heaps keeps the priority it has in `other`, as with `FibonacciHeap::union`.
+    pub fn union(&mut self, mut other: OffsetFibonacciHeap<K, V>) {
+        if other.offset != self.offset {
+            let offset = self.offset.clone();
+            let other_offset = other.offset.clone();
+            other.heap.map_priorities(|key, _| key.clone() + other_offset.clone() - offset.clone());
+        }
+        
+        self.heap.union(other.heap);
+    }
+}
+
+impl<K, V> Default for OffsetFibonacciHeap<K, V>
+    where K: Ord + Clone + Default + Add<Output = K> + Sub<Output = K>,
+          V: Eq + Hash
+{
+    fn default() -> OffsetFibonacciHeap<K, V> {
+        OffsetFibonacciHeap::new()
+    }
+}
@@ -0,0 +1,58 @@
+use alloc::vec::Vec;
+use core::hash::Hash;
+
+use rayon::iter::{FromParallelIterator, IntoParallelIterator, ParallelExtend, ParallelIterator};
+
+use super::compare::Compare;
+use super::fibonacci_heap::FibonacciHeap;
+
+impl<K, V, C> FromParallelIterator<(K, V)> for FibonacciHeap<K, V, C>
+    where K: Send,
+          V: Eq + Hash + Send,
+          C: Compare<K> + Clone + Default + Send + Sync
+{
+    /// Builds a heap from the entries in parallel, as `ParallelExtend::par_extend` does on an empty heap.
+    fn from_par_iter<I>(par_iter: I) -> FibonacciHeap<K, V, C>
+        where I: IntoParallelIterator<Item = (K, V)>
+    {
+        let mut heap = FibonacciHeap::with_comparator(C::default());
+        heap.par_extend(par_iter);
+        
+        heap
+    }
+}
+
+impl<K, V, C> ParallelExtend<(K, V)> for FibonacciHeap<K, V, C>
+    where K: Send,
+          V: Eq + Hash + Send,
+          C: Compare<K> + Clone + Send + Sync
+{
+    /// Inserts every entry, building a heap on each thread from the entries it takes and melding them into this one.
+    ///
+    /// Melding moves the nodes of the later heap each time, so the last melds run on one thread and bound the
+    /// speedup. Heaps are melded in the order of the entries, so a repeated value keeps its last priority. How the
+    /// entries are split between threads can change from run to run, and with it the shape of the trees and the order
+    /// in which entries with equal keys are extracted.
+    fn par_extend<I>(&mut self, par_iter: I)
+        where I: IntoParallelIterator<Item = (K, V)>
+    {
+        let compare = self.comparator().clone();
+        
+        let mut heap = par_iter.into_par_iter()
+            .fold(Vec::new, |mut entries, entry| {
+                entries.push(entry);
+                entries
+            })
+            .map(|entries| {
+                let mut heap = FibonacciHeap::with_comparator(compare.clone());
+                heap.extend(entries);
+                heap
+            })
+            .reduce(|| FibonacciHeap::with_comparator(compare.clone()), |mut heap, mut other| {
+                heap.append(&mut other);
+                heap
+            });
+        
+        self.append(&mut heap);
+    }
+}
@@ -0,0 +1,74 @@
+use core::fmt;
+use core::hash::Hash;
+use core::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+
+use super::compare::Compare;
+use super::fibonacci_heap::FibonacciHeap;
+
+/// Serializes the heap as a sequence of `(key, value)` pairs in no particular order.
+///
+/// Only the entries are written, not the shape of the trees, so a deserialized heap starts out with every entry as a
+/// root of its own and is consolidated by the first `extract_min`. The comparator is not written either.
+impl<K, V, C> Serialize for FibonacciHeap<K, V, C>
+    where K: Serialize,
+          V: Eq + Hash + Serialize
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for entry in self.iter() {
+            seq.serialize_element(&entry)?;
+        }
+        
+        seq.end()
+    }
+}
+
+/// Deserializes a heap from a sequence of `(key, value)` pairs, ordered by the default comparator of C.
+///
+/// Fails if a value appears more than once, rather than letting one entry replace another.
+impl<'de, K, V, C> Deserialize<'de> for FibonacciHeap<K, V, C>
+    where K: Deserialize<'de>,
+          V: Eq + Hash + Deserialize<'de>,
+          C: Compare<K> + Default
+{
+    fn deserialize<D>(deserializer: D) -> Result<FibonacciHeap<K, V, C>, D::Error>
+        where D: Deserializer<'de>
+    {
+        deserializer.deserialize_seq(HeapVisitor(PhantomData))
+    }
+}
+
+struct HeapVisitor<K, V, C>(PhantomData<FibonacciHeap<K, V, C>>);
+
+impl<'de, K, V, C> Visitor<'de> for HeapVisitor<K, V, C>
+    where K: Deserialize<'de>,
+          V: Eq + Hash + Deserialize<'de>,
+          C: Compare<K> + Default
+{
+    type Value = FibonacciHeap<K, V, C>;
+    
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a sequence of (key, value) pairs with distinct values")
+    }
+    
+    fn visit_seq<A>(self, mut seq: A) -> Result<FibonacciHeap<K, V, C>, A::Error>
+        where A: SeqAccess<'de>
+    {
+        let mut heap = FibonacciHeap::with_comparator(C::default());
+        
+        while let Some((key, value)) = seq.next_element::<(K, V)>()? {
+            if heap.contains(&value) {
+                return Err(de::Error::custom(format_args!("value appears more than once at entry {}", heap.len())));
+            }
+            
+            heap.insert(key, value);
+        }
+        
+        Ok(heap)
+    }
+}
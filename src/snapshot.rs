@@ -0,0 +1,75 @@
+use alloc::vec::Vec;
+#[cfg(feature = "bincode")]
+use core::hash::Hash;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "bincode")]
+use serde::de::DeserializeOwned;
+
+#[cfg(feature = "bincode")]
+use super::compare::Compare;
+#[cfg(feature = "bincode")]
+use super::error::SnapshotError;
+#[cfg(feature = "bincode")]
+use super::fibonacci_heap::FibonacciHeap;
+
+/// The exact forest of a `FibonacciHeap`, as recorded by `FibonacciHeap::to_snapshot`.
+///
+/// Nodes refer to their children by position in `nodes`. A snapshot can be restored with
+/// `FibonacciHeap::from_snapshot`, which checks that it describes a valid heap. With the `serde` feature it can be
+/// serialized in any format serde supports, keeping the shape of the trees which serializing the heap itself does not.
+/// With the `bincode` feature, `FibonacciHeap::to_bytes` and `from_bytes` do both steps at once.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HeapSnapshot<K, V> {
+    /// Every node of the heap, in no particular order.
+    pub nodes: Vec<SnapshotNode<K, V>>,
+    /// The positions of the roots of the trees in `nodes`.
+    pub roots: Vec<usize>
+}
+
+/// A node of a `HeapSnapshot`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SnapshotNode<K, V> {
+    pub key: K,
+    pub value: V,
+    /// The number of children, which must match the length of `children`.
+    pub degree: usize,
+    /// Whether the node has lost a child since it became a child itself.
+    pub marked: bool,
+    /// The positions of the children in `HeapSnapshot::nodes`, in order.
+    pub children: Vec<usize>
+}
+
+#[cfg(feature = "bincode")]
+impl<K, V, C> FibonacciHeap<K, V, C>
+    where V: Eq + Hash,
+          C: Compare<K>
+{
+    /// Encodes the exact shape of the heap with bincode, as `to_snapshot` records it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a key or value fails to serialize.
+    pub fn to_bytes(&self) -> Vec<u8>
+        where K: Clone + Serialize,
+              V: Clone + Serialize
+    {
+        bincode::serialize(&self.to_snapshot()).expect("keys and values serialize")
+    }
+    
+    /// Restores a heap from the bytes written by `to_bytes`, with the same trees as the heap they were written from.
+    ///
+    /// The decoded snapshot is checked as `from_snapshot` checks it. Returns `Err(SnapshotError::Undecodable)` if the
+    /// bytes do not decode to a snapshot at all.
+    pub fn from_bytes(bytes: &[u8]) -> Result<FibonacciHeap<K, V, C>, SnapshotError>
+        where K: DeserializeOwned,
+              V: DeserializeOwned,
+              C: Default
+    {
+        let snapshot = bincode::deserialize(bytes).map_err(|_| SnapshotError::Undecodable)?;
+        FibonacciHeap::from_snapshot(snapshot)
+    }
+}
@@ -0,0 +1,49 @@
+use alloc::vec;
+use alloc::vec::Vec;
+
+use super::fibonacci_heap::FibonacciHeap;
+
+/// Sorts the entries by key by building a `FibonacciHeap` from them and extracting its minimum until it is empty.
+///
+/// The sort is stable, entries with equal keys keep their order in `items`, and values do not need to be distinct or
+/// hashable. It runs in O(n log n) time and allocates the heap for n entries, a slot per entry to park its value in
+/// while the key is in the heap, and the returned `Vec`. This is no faster than `slice::sort`, it is meant for putting
+/// data through the heap.
+pub fn heapsort<K, V>(items: Vec<(K, V)>) -> Vec<(K, V)>
+    where K: Ord
+{
+    let mut values = Vec::with_capacity(items.len());
+    let mut entries = Vec::with_capacity(items.len());
+    for (index, (key, value)) in items.into_iter().enumerate() {
+        values.push(Some(value));
+        entries.push(((key, index), index));
+    }
+    
+    // Keying by the position as well breaks ties in input order and keeps every heap value distinct
+    let mut heap: FibonacciHeap<_, _> = FibonacciHeap::from_entries(entries);
+    let mut sorted = Vec::with_capacity(heap.len());
+    for ((key, index), _) in heap.drain_sorted() {
+        sorted.push((key, values[index].take().expect("every position is extracted once")));
+    }
+    
+    sorted
+}
+
+/// Sorts the items of any iterator through a `FibonacciHeap`, the same way as `heapsort`.
+pub trait HeapSortExt: Iterator {
+    /// Collects the items and returns them in ascending order, equal items in the order they were yielded.
+    fn sorted_via_fib_heap(self) -> vec::IntoIter<Self::Item>
+        where Self: Sized,
+              Self::Item: Ord
+    {
+        let entries = self.enumerate().map(|(index, item)| ((item, index), index)).collect();
+        let mut heap: FibonacciHeap<_, _> = FibonacciHeap::from_entries(entries);
+        let sorted: Vec<_> = heap.drain_sorted().map(|((item, _), _)| item).collect();
+        
+        sorted.into_iter()
+    }
+}
+
+impl<I> HeapSortExt for I
+    where I: Iterator
+{ }
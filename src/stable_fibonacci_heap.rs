@@ -0,0 +1,125 @@
+use core::hash::Hash;
+
+use super::error::{DecreaseKeyError, HeapError};
+use super::fibonacci_heap::FibonacciHeap;
+
+/// A `FibonacciHeap` that extracts entries with equal keys in the order they were inserted.
+///
+/// Each value is given a sequence number when it is first inserted, and keys are compared together with it. Changing
+/// the priority of a value keeps its sequence number, so among the entries with its new key it comes out as if it had
+/// been inserted with that key from the start.
+pub struct StableFibonacciHeap<K, V> {
+    heap: FibonacciHeap<(K, u64), V>,
+    next: u64
+}
+
+impl<K, V> StableFibonacciHeap<K, V>
+    where K: Ord,
+          V: Eq + Hash
+{
+    /// Creates a new empty `StableFibonacciHeap`.
+    pub fn new() -> StableFibonacciHeap<K, V> {
+        StableFibonacciHeap {
+            heap: FibonacciHeap::new(),
+            next: 0
+        }
+    }
+    
+    /// Inserts the value into the heap with priority key.
+    ///
+    /// As with `FibonacciHeap::insert`, a value already in the heap has its priority changed to key, and it keeps its
+    /// place among the entries with equal keys.
+    pub fn insert(&mut self, key: K, value: V) {
+        let sequence = match self.heap.get_priority(&value) {
+            Some(&(_, sequence)) => sequence,
+            None => {
+                let sequence = self.next;
+                self.next += 1;
+                sequence
+            }
+        };
+        
+        self.heap.insert((key, sequence), value);
+    }
+    
+    /// Peeks at the minimum of the heap, the earliest inserted of the entries with the smallest key.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn minimum(&self) -> Option<(&K, &V)> {
+        self.heap.minimum().map(|((key, _), value)| (key, value))
+    }
+    
+    /// Returns the number of values in the heap.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+    
+    /// Returns `true` if the heap contains no values.
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+    
+    /// Returns `true` if the value is in the heap.
+    pub fn contains(&self, value: &V) -> bool {
+        self.heap.contains(value)
+    }
+    
+    /// Looks up the current priority of the value.
+    ///
+    /// Returns `None` if the value is not in the heap.
+    pub fn get_priority(&self, value: &V) -> Option<&K> {
+        self.heap.get_priority(value).map(|(key, _)| key)
+    }
+    
+    /// Extracts the minimum of the heap.
+    ///
+    /// Returns `None` if the heap is empty.
+    pub fn extract_min(&mut self) -> Option<(K, V)> {
+        self.heap.extract_min().map(|((key, _), value)| (key, value))
+    }
+    
+    /// Decreases the priority of the value to the key.
+    ///
+    /// Returns the previous priority, or `Err` holding the rejected key if the value is not in the heap or if the key is
+    /// greater than the current priority of the value.
+    pub fn decrease_key(&mut self, value: V, key: K) -> Result<K, DecreaseKeyError<K>> {
+        let sequence = match self.heap.get_priority(&value) {
+            Some(&(_, sequence)) => sequence,
+            None => return Err(DecreaseKeyError::NotFound(key))
+        };
+        
+        match self.heap.decrease_key(value, (key, sequence)) {
+            Ok((old, _)) => Ok(old),
+            Err(DecreaseKeyError::NotFound((key, _))) => Err(DecreaseKeyError::NotFound(key)),
+            Err(DecreaseKeyError::KeyGreaterThanCurrent((key, _))) => Err(DecreaseKeyError::KeyGreaterThanCurrent(key))
+        }
+    }
+    
+    /// Changes the priority of the value to the key, whether the key is smaller or larger than the current priority.
+    ///
+    /// Returns the previous priority or `Err(HeapError::NotFound)` if the value is not in the heap.
+    pub fn change_priority(&mut self, value: &V, key: K) -> Result<K, HeapError> {
+        let sequence = match self.heap.get_priority(value) {
+            Some(&(_, sequence)) => sequence,
+            None => return Err(HeapError::NotFound)
+        };
+        
+        self.heap.change_priority(value, (key, sequence)).map(|(old, _)| old)
+    }
+    
+    /// Deletes the value from the heap.
+    ///
+    /// Returns the priority the value had or `None` if the value is not in the heap.
+    pub fn delete(&mut self, value: &V) -> Option<K> {
+        self.heap.delete(value).map(|(key, _)| key)
+    }
+}
+
+impl<K, V> Default for StableFibonacciHeap<K, V>
+    where K: Ord,
+          V: Eq + Hash
+{
+    fn default() -> StableFibonacciHeap<K, V> {
+        StableFibonacciHeap::new()
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashMap;
+use std::num::Wrapping;
+#[cfg(debug_assertions)]
+use std::panic::{self, AssertUnwindSafe};
+
+use super::Rng;
+use {DecreaseKeyError, FibonacciHeap, OffsetFibonacciHeap};
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn offsets_interleaved_with_decreases_keep_extraction_order() {
+    for seed in 0..10 {
+        let mut rng = Rng::new(seed);
+        let mut heap = FibonacciHeap::new();
+        let mut model: HashMap<u64, i64> = HashMap::new();
+        
+        for _ in 0..1000 {
+            let value = rng.below(100);
+            match rng.below(5) {
+                0 | 1 => {
+                    let key = rng.below(1000) as i64;
+                    heap.insert(key, value);
+                    model.insert(value, key);
+                },
+                2 => {
+                    let key = rng.below(1000) as i64 - 500;
+                    if heap.decrease_key(value, key).is_ok() {
+                        model.insert(value, key);
+                    }
+                },
+                3 => {
+                    let delta = rng.below(200) as i64 - 100;
+                    heap.add_to_all_keys(delta);
+                    for key in model.values_mut() {
+                        *key += delta;
+                    }
+                },
+                _ => {
+                    if let Some((key, value)) = heap.extract_min() {
+                        assert_eq!(model.remove(&value), Some(key));
+                        assert!(model.values().all(|&other| other >= key));
+                    }
+                }
+            }
+            
+            assert_eq!(heap.minimum().map(|(&key, _)| key), model.values().min().cloned());
+            assert_eq!(heap.get_priority(&value), model.get(&value));
+        }
+        
+        assert_eq!(heap.check_invariants(), Ok(()));
+    }
+}
+
+#[test]
+fn offset_under_a_comparator_that_does_not_keep_the_order() {
+    let mut heap = FibonacciHeap::new_by_key(|key: &i64| key.abs());
+    heap.insert(-5, "a");
+    heap.insert(3, "b");
+    assert_eq!(heap.minimum(), Some((&3, &"b")));
+    
+    heap.add_to_all_keys(10);
+    
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(heap.extract_min(), Some((5, "a")));
+    assert_eq!(heap.extract_min(), Some((13, "b")));
+}
+
+#[test]
+fn offset_that_wraps_around() {
+    let mut heap = FibonacciHeap::new();
+    heap.insert(Wrapping(250u8), "a");
+    heap.insert(Wrapping(3), "b");
+    
+    heap.add_to_all_keys(Wrapping(10));
+    
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(heap.extract_min(), Some((Wrapping(4), "a")));
+    assert_eq!(heap.extract_min(), Some((Wrapping(13), "b")));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn offset_that_overflows_leaves_a_valid_heap() {
+    let mut heap: FibonacciHeap<_, _> = (0..20).map(|i| (240 + i as u8 % 16, i)).collect();
+    heap.extract_min();
+    
+    let result = panic::catch_unwind(AssertUnwindSafe(|| heap.add_to_all_keys(10)));
+    
+    assert!(result.is_err());
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(heap.len(), 19);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn stored_offsets_interleaved_with_decreases_and_unions_keep_extraction_order() {
+    for seed in 0..10 {
+        let mut rng = Rng::new(seed);
+        let mut heap = OffsetFibonacciHeap::new();
+        let mut model: HashMap<u64, i64> = HashMap::new();
+        
+        for _ in 0..1000 {
+            let value = rng.below(100);
+            match rng.below(6) {
+                0 | 1 => {
+                    let key = rng.below(1000) as i64;
+                    heap.insert(key, value);
+                    model.insert(value, key);
+                },
+                2 => {
+                    let key = rng.below(1000) as i64 - 500;
+                    match heap.decrease_key(value, key) {
+                        Ok(old) => assert_eq!(model.insert(value, key), Some(old)),
+                        Err(DecreaseKeyError::KeyGreaterThanCurrent(rejected)) => {
+                            assert_eq!(rejected, key);
+                            assert!(model[&value] < key);
+                        },
+                        Err(DecreaseKeyError::NotFound(rejected)) => {
+                            assert_eq!(rejected, key);
+                            assert!(!model.contains_key(&value));
+                        }
+                    }
+                },
+                3 => {
+                    let delta = rng.below(200) as i64 - 100;
+                    heap.add_to_all_keys(delta);
+                    for key in model.values_mut() {
+                        *key += delta;
+                    }
+                },
+                4 => {
+                    // The other heap has an offset of its own, and may share values with this one
+                    let mut other = OffsetFibonacciHeap::new();
+                    other.add_to_all_keys(rng.below(200) as i64 - 100);
+                    for _ in 0..rng.below(4) {
+                        let (key, value) = (rng.below(1000) as i64, rng.below(110));
+                        other.insert(key, value);
+                        model.insert(value, key);
+                    }
+                    heap.union(other);
+                },
+                _ => {
+                    if let Some((key, value)) = heap.extract_min() {
+                        assert_eq!(model.remove(&value), Some(key));
+                        assert!(model.values().all(|&other| other >= key));
+                    }
+                }
+            }
+            
+            assert_eq!(heap.len(), model.len());
+            assert_eq!(heap.minimum().map(|(key, _)| key), model.values().min().cloned());
+            assert_eq!(heap.get_priority(&value), model.get(&value).cloned());
+        }
+    }
+}
+
+#[test]
+fn stored_offset_applies_to_every_priority_read() {
+    let mut heap = OffsetFibonacciHeap::new();
+    heap.insert(5, "a");
+    heap.insert(8, "b");
+    
+    heap.add_to_all_keys(-10);
+    assert_eq!(heap.minimum(), Some((-5, &"a")));
+    assert_eq!(heap.get_priority(&"b"), Some(-2));
+    
+    // Keys passed in after the offset changed are taken as they are
+    heap.insert(-3, "c");
+    assert_eq!(heap.decrease_key("b", -4), Ok(-2));
+    assert_eq!(heap.decrease_key("a", 0), Err(DecreaseKeyError::KeyGreaterThanCurrent(0)));
+    assert_eq!(heap.decrease_key("d", 0), Err(DecreaseKeyError::NotFound(0)));
+    assert_eq!(heap.change_priority(&"a", 1), Ok(-5));
+    
+    heap.add_to_all_keys(100);
+    assert_eq!(heap.delete(&"c"), Some(97));
+    assert_eq!(heap.extract_min(), Some((96, "b")));
+    assert_eq!(heap.extract_min(), Some((101, "a")));
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn union_of_heaps_with_different_offsets_keeps_both_priorities() {
+    let mut a = OffsetFibonacciHeap::new();
+    a.insert(10, 'a');
+    a.insert(20, 'x');
+    a.add_to_all_keys(5);
+    
+    let mut b = OffsetFibonacciHeap::new();
+    b.add_to_all_keys(-50);
+    b.insert(12, 'b');
+    b.insert(30, 'x');
+    b.add_to_all_keys(1);
+    
+    a.union(b);
+    
+    // The value in both heaps keeps the priority it had in the heap melded in
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.get_priority(&'x'), Some(31));
+    assert_eq!(a.extract_min(), Some((13, 'b')));
+    assert_eq!(a.extract_min(), Some((15, 'a')));
+    assert_eq!(a.extract_min(), Some((31, 'x')));
+}
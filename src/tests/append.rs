@@ -0,0 +1,166 @@
+use core::cmp;
+use core::hash::{Hash, Hasher};
+use std::collections::HashMap;
+use std::vec;
+use std::vec::Vec;
+
+use super::Rng;
+use FibonacciHeap;
+
+#[test]
+fn append_keeps_the_handles_of_self_valid() {
+    let mut a = FibonacciHeap::new();
+    let handle = a.push_with_handle(5, 1);
+    let mut b: FibonacciHeap<_, _> = (10..1000).map(|i| (i, i)).collect();
+    b.extract_min();
+    
+    a.append(&mut b);
+    
+    assert_eq!(a.get_handle(handle), Some((&5, &1)));
+    assert_eq!(a.decrease_key_handle(handle, 0), Ok(5));
+    assert_eq!(a.minimum(), Some((&0, &1)));
+}
+
+#[test]
+fn append_leaves_other_empty_with_its_capacity() {
+    let mut a: FibonacciHeap<_, _> = (0..10).map(|i| (i, i)).collect();
+    let mut b: FibonacciHeap<_, _> = (100..1100).map(|i| (i, i)).collect();
+    let capacity = b.capacity();
+    
+    a.append(&mut b);
+    
+    assert!(b.is_empty());
+    assert!(b.minimum().is_none());
+    assert!(b.capacity() >= capacity);
+    assert_eq!(a.len(), 1010);
+    assert_eq!(b.check_invariants(), Ok(()));
+    assert_eq!(a.check_invariants(), Ok(()));
+    
+    b.insert(1, 1);
+    assert_eq!(b.extract_min(), Some((1, 1)));
+}
+
+#[test]
+fn decrease_key_works_on_appended_values() {
+    let mut a: FibonacciHeap<_, _> = (0..50).map(|i| (i, i)).collect();
+    let mut b: FibonacciHeap<_, _> = (50..100).map(|i| (i, i)).collect();
+    b.extract_min();
+    
+    a.append(&mut b);
+    for i in 60..100 {
+        assert_eq!(a.decrease_key(i, -i), Ok(i));
+    }
+    
+    let keys: Vec<_> = a.into_iter().map(|(key, _)| key).collect();
+    let mut expected: Vec<_> = (0..50).chain(51..60).chain((60..100).map(|i| -i)).collect();
+    expected.sort();
+    assert_eq!(keys, expected);
+}
+
+#[test]
+fn append_replaces_entries_of_self_with_those_of_other() {
+    let mut a: FibonacciHeap<_, _> = (0..10).map(|i| (i, i)).collect();
+    let mut b: FibonacciHeap<_, _> = (5..15).map(|i| (i + 100, i)).collect();
+    
+    a.union(b.clone());
+    b.append(&mut a.clone());
+    
+    assert_eq!(a.len(), 15);
+    assert_eq!(a.get_priority(&7), Some(&107));
+    assert_eq!(a.get_priority(&3), Some(&3));
+    assert_eq!(b.get_priority(&7), Some(&107));
+}
+
+#[test]
+fn union_all_keeps_the_smallest_key_of_a_value_in_several_heaps() {
+    // The first heap is smaller than the second and larger than the third, so values are looked up from either side
+    let a: FibonacciHeap<_, _> = vec![(5, 'x'), (1, 'y'), (7, 'a')].into_iter().collect();
+    let b: FibonacciHeap<_, _> = vec![(3, 'x'), (4, 'y'), (9, 'z'), (8, 'b'), (6, 'c')].into_iter().collect();
+    let c: FibonacciHeap<_, _> = vec![(2, 'z')].into_iter().collect();
+    
+    let mut merged = FibonacciHeap::union_all(vec![a, b, c]);
+    
+    assert_eq!(merged.len(), 6);
+    assert_eq!(merged.check_invariants(), Ok(()));
+    assert_eq!(merged.get_priority(&'x'), Some(&3));
+    assert_eq!(merged.get_priority(&'y'), Some(&1));
+    assert_eq!(merged.get_priority(&'z'), Some(&2));
+    assert_eq!(merged.decrease_key('c', 0), Ok(6));
+    
+    let entries: Vec<_> = merged.into_iter().collect();
+    assert_eq!(entries, [(0, 'c'), (1, 'y'), (2, 'z'), (3, 'x'), (7, 'a'), (8, 'b')]);
+}
+
+#[test]
+fn union_all_keeps_the_first_of_equal_keys() {
+    let mut a = FibonacciHeap::new();
+    a.insert(1, Tagged(0, 'a'));
+    let mut b = FibonacciHeap::new();
+    b.insert(1, Tagged(0, 'b'));
+    b.insert(2, Tagged(1, 'b'));
+    
+    let merged = FibonacciHeap::union_all(vec![a, b]);
+    
+    let entries: Vec<_> = merged.into_iter().map(|(key, Tagged(id, heap))| (key, id, heap)).collect();
+    assert_eq!(entries, [(1, 0, 'a'), (2, 1, 'b')]);
+}
+
+// A value that is equal to another with the same id, whichever heap it came from
+#[derive(Debug)]
+struct Tagged(u64, char);
+
+impl PartialEq for Tagged {
+    fn eq(&self, other: &Tagged) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl Eq for Tagged { }
+
+impl Hash for Tagged {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.hash(state);
+    }
+}
+
+#[test]
+fn union_all_matches_a_merge_of_every_entry() {
+    let seeds = if cfg!(miri) { 4 } else { 100 };
+    for seed in 0..seeds {
+        let mut rng = Rng::new(seed);
+        let mut expected: HashMap<u64, u64> = HashMap::new();
+        let heaps: Vec<FibonacciHeap<u64, u64>> = (0..rng.below(8)).map(|_| {
+            let mut heap = FibonacciHeap::new();
+            for _ in 0..rng.below(64) {
+                let (key, value) = (rng.below(1000), rng.below(200));
+                heap.insert(key, value);
+            }
+            if rng.below(2) == 0 {
+                heap.extract_min();
+            }
+            for (&key, &value) in heap.iter() {
+                let min = expected.entry(value).or_insert(key);
+                *min = cmp::min(*min, key);
+            }
+            heap
+        }).collect();
+        
+        let merged = FibonacciHeap::union_all(heaps);
+        assert_eq!(merged.check_invariants(), Ok(()), "seed {}", seed);
+        
+        let mut expected: Vec<_> = expected.into_iter().map(|(value, key)| (key, value)).collect();
+        expected.sort();
+        let mut entries: Vec<_> = merged.into_iter().collect();
+        // Equal keys come out in either order
+        entries.sort();
+        assert_eq!(entries, expected, "seed {}", seed);
+    }
+}
+
+#[test]
+fn union_all_of_no_heaps_is_empty() {
+    let merged: FibonacciHeap<u64, u64> = FibonacciHeap::union_all(Vec::new());
+    
+    assert!(merged.is_empty());
+    assert!(merged.minimum().is_none());
+}
@@ -0,0 +1,39 @@
+#![cfg(feature = "arbitrary")]
+
+use std::vec::Vec;
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use super::Rng;
+use FibonacciHeap;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn generated_heaps_are_valid_and_have_marked_nodes() {
+    let mut rng = Rng::new(60);
+    let mut marked = 0;
+    let mut deepest = 0;
+    
+    for _ in 0..200 {
+        let bytes: Vec<u8> = (0..rng.below(4096)).map(|_| rng.next() as u8).collect();
+        let mut u = Unstructured::new(&bytes);
+        let heap = FibonacciHeap::<u8, u16>::arbitrary(&mut u).unwrap();
+        
+        assert_eq!(heap.check_invariants(), Ok(()));
+        // Each entry takes at least a byte of the input
+        assert!(heap.len() <= bytes.len());
+        marked += heap.stats().marked;
+        heap.visit(|_, _, info| deepest = deepest.max(info.depth));
+    }
+    
+    assert!(marked > 0);
+    assert!(deepest > 1);
+}
+
+#[test]
+fn an_empty_input_gives_an_empty_heap() {
+    let mut u = Unstructured::new(&[]);
+    let heap = FibonacciHeap::<u8, u16>::arbitrary(&mut u).unwrap();
+    
+    assert!(heap.is_empty());
+}
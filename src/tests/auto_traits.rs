@@ -0,0 +1,73 @@
+use core::cmp::Ordering;
+use std::string::String;
+use std::sync::Arc;
+use std::thread;
+use std::vec::Vec;
+
+use {BoundedFibonacciHeap, Drain, FibonacciHeap, FibonacciMaxHeap, FibonacciMultiHeap, FnComparator, Handle, IntoIter,
+     KeyedFibonacciHeap, MaxComparator, PeekMut, StableFibonacciHeap};
+
+fn assert_send<T: Send>() { }
+
+fn assert_sync<T: Sync>() { }
+
+// Checked by the compiler, the test only has to build
+#[test]
+fn heaps_are_send_and_sync() {
+    assert_send::<FibonacciHeap<u64, String>>();
+    assert_sync::<FibonacciHeap<u64, String>>();
+    assert_send::<FibonacciHeap<u64, String, MaxComparator>>();
+    assert_sync::<FibonacciHeap<u64, String, FnComparator<fn(&u64, &u64) -> Ordering>>>();
+    assert_send::<FibonacciMaxHeap<u64, String>>();
+    assert_sync::<FibonacciMaxHeap<u64, String>>();
+    assert_send::<BoundedFibonacciHeap<u64, String>>();
+    assert_sync::<BoundedFibonacciHeap<u64, String>>();
+    assert_send::<StableFibonacciHeap<u64, String>>();
+    assert_sync::<StableFibonacciHeap<u64, String>>();
+    assert_send::<FibonacciMultiHeap<u64, String>>();
+    assert_sync::<FibonacciMultiHeap<u64, String>>();
+    assert_send::<KeyedFibonacciHeap<u64, u32, String>>();
+    assert_sync::<KeyedFibonacciHeap<u64, u32, String>>();
+}
+
+#[test]
+fn iterators_guards_and_handles_are_send_and_sync() {
+    assert_send::<IntoIter<u64, String>>();
+    assert_sync::<IntoIter<u64, String>>();
+    assert_send::<Drain<'static, u64, String>>();
+    assert_sync::<Drain<'static, u64, String>>();
+    assert_send::<PeekMut<'static, u64, String>>();
+    assert_sync::<PeekMut<'static, u64, String>>();
+    assert_send::<Handle>();
+    assert_sync::<Handle>();
+}
+
+#[test]
+fn heap_moves_into_another_thread() {
+    let mut heap = FibonacciHeap::new();
+    for i in 0..1000u64 {
+        heap.insert(i % 17, i);
+    }
+    heap.extract_min();
+    
+    let sorted = thread::spawn(move || heap.into_sorted_vec()).join().unwrap();
+    assert_eq!(sorted.len(), 999);
+    assert!(sorted.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+}
+
+#[test]
+fn heap_is_read_from_several_threads() {
+    let heap: FibonacciHeap<_, _> = (0..1000u64).map(|i| (i, i)).collect();
+    let heap = Arc::new(heap);
+    
+    let threads: Vec<_> = (0..4u64)
+        .map(|t| {
+            let heap = heap.clone();
+            thread::spawn(move || (0..1000).filter(|i| i % 4 == t).all(|i| heap.contains(&i)))
+        })
+        .collect();
+    for thread in threads {
+        assert!(thread.join().unwrap());
+    }
+    assert_eq!(heap.minimum(), Some((&0, &0)));
+}
@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+use std::vec::Vec;
+
+use super::Rng;
+use BoundedFibonacciHeap;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn bounded_heap_keeps_the_smallest_entries_of_a_stream() {
+    let n = if cfg!(feature = "debug-invariants") { 100_000 } else { 1_000_000 };
+    let mut rng = Rng::new(7);
+    let stream: Vec<(u64, u64)> = (0..n).map(|i| (rng.next(), i)).collect();
+    
+    let mut heap = BoundedFibonacciHeap::new(100);
+    let mut rejected = Vec::new();
+    for &(key, value) in &stream {
+        rejected.extend(heap.insert(key, value));
+        assert!(heap.len() <= 100);
+    }
+    
+    let mut sorted = stream.clone();
+    sorted.sort();
+    assert_eq!(heap.find_max(), sorted.get(99).map(|(key, value)| (key, value)));
+    
+    // Every entry either survives or is handed back exactly once
+    let survivors = heap.into_sorted_vec();
+    assert_eq!(survivors, &sorted[..100]);
+    rejected.sort();
+    assert_eq!(rejected, &sorted[100..]);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn evicting_on_every_insert_stays_fast() {
+    // Each key is smaller than every key in the heap, so each insert evicts the largest. Looking through the bound for
+    // the next largest key on every insert would take about 10^9 steps
+    let (bound, n) = if cfg!(feature = "debug-invariants") { (100, 10_000) } else { (10_000, 100_000) };
+    let mut heap = BoundedFibonacciHeap::new(bound);
+    for i in (0..n).rev() {
+        let evicted = heap.insert(i, i);
+        assert_eq!(evicted, if i + bound < n { Some((i + bound, i + bound)) } else { None });
+    }
+    
+    assert_eq!(heap.len(), bound);
+    assert_eq!(heap.minimum(), Some((&0, &0)));
+    assert_eq!(heap.find_max(), Some((&(bound - 1), &(bound - 1))));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn bounded_heap_matches_a_model_under_random_operations() {
+    for seed in 0..4 {
+        let mut rng = Rng::new(seed);
+        let mut heap = BoundedFibonacciHeap::new(16);
+        let mut model: Vec<(u64, u64)> = Vec::new();
+        
+        for _ in 0..5000 {
+            let value = rng.below(64);
+            let key = rng.below(1000);
+            match rng.below(5) {
+                0 | 1 => {
+                    let evicted = heap.insert(key, value);
+                    match model.iter().position(|&(_, v)| v == value) {
+                        Some(i) => {
+                            model[i].0 = key;
+                            assert_eq!(evicted, None);
+                        },
+                        None => {
+                            model.push((key, value));
+                            model.sort();
+                            let expected = if model.len() > 16 { model.pop() } else { None };
+                            assert_eq!(evicted.map(|(key, _)| key), expected.map(|(key, _)| key));
+                            // Of equal largest keys either may go, so the model drops the one the heap dropped
+                            if let (Some(evicted), Some(expected)) = (evicted, expected) {
+                                if evicted != expected {
+                                    let i = model.iter().position(|&entry| entry == evicted).unwrap();
+                                    model[i] = expected;
+                                }
+                            }
+                        }
+                    }
+                },
+                2 => {
+                    let expected = model.iter().position(|&(_, v)| v == value);
+                    match heap.decrease_key(value, key) {
+                        Ok(old) => {
+                            let i = expected.unwrap();
+                            assert_eq!(model[i].0, old);
+                            model[i].0 = key;
+                        },
+                        Err(_) => assert!(expected.is_none_or(|i| model[i].0 < key))
+                    }
+                },
+                3 => {
+                    let expected = model.iter().position(|&(_, v)| v == value).map(|i| model.remove(i).0);
+                    assert_eq!(heap.delete(&value), expected);
+                },
+                _ => {
+                    model.sort();
+                    let extracted = heap.extract_min();
+                    assert_eq!(extracted.map(|(key, _)| key), model.first().map(|&(key, _)| key));
+                    if let Some(extracted) = extracted {
+                        let i = model.iter().position(|&entry| entry == extracted).unwrap();
+                        model.remove(i);
+                    }
+                }
+            }
+            
+            model.sort();
+            assert_eq!(heap.len(), model.len());
+            assert_eq!(heap.minimum().map(|(&key, _)| key), model.first().map(|&(key, _)| key));
+            assert_eq!(heap.find_max().map(|(&key, _)| key), model.last().map(|&(key, _)| key));
+            let values: HashSet<_> = model.iter().map(|&(_, value)| value).collect();
+            for value in 0..64 {
+                assert_eq!(heap.contains(&value), values.contains(&value));
+            }
+        }
+    }
+}
+
+#[test]
+fn zero_bound_rejects_everything() {
+    let mut heap = BoundedFibonacciHeap::new(0);
+    
+    assert_eq!(heap.insert(1, "a"), Some((1, "a")));
+    assert!(heap.is_empty());
+    assert!(heap.find_max().is_none());
+}
@@ -0,0 +1,48 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::vec::Vec;
+
+use FibonacciHeap;
+
+#[test]
+fn dropping_the_guard_brings_the_minimum_and_maximum_up_to_date() {
+    let mut heap: FibonacciHeap<_, _> = (10..20).map(|i| (i, i)).collect();
+    
+    {
+        let mut bulk = heap.bulk_insert();
+        bulk.extend((20..30).map(|i| (35 - i, i)));
+        // Replacing an entry finishes the batch so far before changing the priority
+        bulk.insert(40, 10);
+        bulk.insert(1, 30);
+    }
+    
+    assert_eq!(heap.len(), 21);
+    assert_eq!(heap.peek(), Some((&1, &30)));
+    assert_eq!(heap.peek_max(), Some((&40, &10)));
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn a_panic_halfway_through_a_batch_leaves_the_inserted_entries_in_a_valid_heap() {
+    let mut heap: FibonacciHeap<_, _> = (1000..1100).map(|i| (i, i)).collect();
+    heap.extract_min();
+    
+    // The batch falls below the minimum and rises above the maximum of the heap before the iterator panics
+    let batch = (0..1000).map(|i| {
+        if i == 500 {
+            panic!("batch");
+        }
+        (if i % 2 == 0 { 500 - i } else { 1500 + i }, 2000 + i)
+    });
+    let result = panic::catch_unwind(AssertUnwindSafe(|| heap.bulk_insert().extend(batch)));
+    
+    assert!(result.is_err());
+    assert_eq!(heap.len(), 99 + 500);
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(heap.peek(), Some((&2, &2498)));
+    assert_eq!(heap.peek_max(), Some((&1999, &2499)));
+    
+    let keys: Vec<_> = heap.into_sorted_vec().into_iter().map(|(key, _)| key).collect();
+    let mut expected: Vec<_> = (1001..1100).chain((0..500).map(|i| if i % 2 == 0 { 500 - i } else { 1500 + i })).collect();
+    expected.sort();
+    assert_eq!(keys, expected);
+}
@@ -0,0 +1,184 @@
+use std::vec::Vec;
+
+use super::Rng;
+use FibonacciHeap;
+
+// Drains 99% of a heap built from random keys, so the survivors are spread over the whole node storage
+fn drained(n: u64) -> (FibonacciHeap<u64, u64>, Vec<(u64, u64)>) {
+    let mut rng = Rng::new(7);
+    let mut heap = FibonacciHeap::new();
+    for value in 0..n {
+        heap.insert(rng.below(1 << 20), value);
+    }
+    // Links the roots into trees, so moving nodes has to follow parents and children
+    heap.extract_min();
+    
+    let mut survivors = Vec::new();
+    for value in 0..n {
+        if value % 100 == 0 {
+            if let Some(&key) = heap.get_priority(&value) {
+                survivors.push((key, value));
+            }
+        } else {
+            heap.delete(&value);
+        }
+    }
+    survivors.sort();
+    
+    (heap, survivors)
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn shrink_to_fit_gives_back_the_space_of_drained_entries() {
+    let n = if cfg!(feature = "debug-invariants") { 2_000 } else { 100_000 };
+    let (mut heap, survivors) = drained(n);
+    let peak = heap.capacity();
+    let peak_memory = heap.memory_usage().total;
+    assert!(peak >= n as usize);
+    
+    heap.shrink_to_fit();
+    
+    assert!(heap.capacity() < peak / 10, "capacity {} of {} entries", heap.capacity(), heap.len());
+    assert!(heap.memory_usage().total < peak_memory / 10);
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(heap.len(), survivors.len());
+    for &(key, value) in &survivors {
+        assert_eq!(heap.get_priority(&value), Some(&key));
+    }
+    assert_eq!(heap.into_sorted_vec(), survivors);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn shrunk_heap_keeps_working() {
+    let n = if cfg!(feature = "debug-invariants") { 2_000 } else { 20_000 };
+    let (mut heap, mut survivors) = drained(n);
+    heap.shrink_to_fit();
+    
+    // Decreasing keys cuts moved nodes from their moved parents
+    for entry in survivors.iter_mut().skip(1).step_by(2) {
+        heap.decrease_key(entry.1, entry.0 / 2).unwrap();
+        entry.0 /= 2;
+    }
+    for value in n..n + 100 {
+        heap.insert(value, value);
+        survivors.push((value, value));
+    }
+    survivors.sort();
+    
+    assert_eq!(heap.check_invariants(), Ok(()));
+    for &entry in &survivors {
+        assert_eq!(heap.extract_min(), Some(entry));
+    }
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn shrink_to_fit_invalidates_only_the_handles_of_moved_entries() {
+    let mut heap = FibonacciHeap::new();
+    let handles: Vec<_> = (0..10).map(|i| heap.push_with_handle(i, i)).collect();
+    for i in 0..5 {
+        heap.delete(&i);
+    }
+    
+    heap.shrink_to_fit();
+    
+    // Every entry after the first five moved down into their space
+    for &handle in &handles {
+        assert_eq!(heap.get_handle(handle), None);
+    }
+    assert_eq!(heap.len(), 5);
+    
+    // Entries before every removed one stay where they are
+    let mut heap = FibonacciHeap::new();
+    let handles: Vec<_> = (0..10).map(|i| heap.push_with_handle(i, i)).collect();
+    heap.delete(&9);
+    heap.delete(&8);
+    heap.shrink_to_fit();
+    for (i, &handle) in handles.iter().enumerate().take(8) {
+        assert_eq!(heap.get_handle(handle), Some((&(i as u64), &(i as u64))));
+    }
+}
+
+#[test]
+fn shrink_to_fit_follows_the_minimum() {
+    let mut heap = FibonacciHeap::new();
+    for i in 0..10 {
+        heap.insert(100 - i, i);
+    }
+    for i in 0..9 {
+        heap.delete(&i);
+    }
+    
+    heap.shrink_to_fit();
+    
+    assert_eq!(heap.minimum(), Some((&91, &9)));
+    assert_eq!(heap.find_max(), Some((&91, &9)));
+    assert_eq!(heap.capacity(), 1);
+}
+
+#[test]
+fn clear_keeps_the_capacity() {
+    let mut heap: FibonacciHeap<_, _> = (0..1000).map(|i| (i, i)).collect();
+    let capacity = heap.capacity();
+    
+    heap.clear();
+    
+    assert!(heap.is_empty());
+    assert_eq!(heap.capacity(), capacity);
+    heap.extend((0..1000).map(|i| (i, i)));
+    assert_eq!(heap.capacity(), capacity);
+    
+    heap.clear();
+    heap.shrink_to_fit();
+    assert_eq!(heap.capacity(), 0);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn pool_limit_bounds_the_free_slots() {
+    let mut rng = Rng::new(3);
+    let mut heap = FibonacciHeap::new();
+    assert_eq!(heap.pool_limit(), usize::MAX);
+    heap.set_pool_limit(16);
+    
+    let mut model = Vec::new();
+    for value in 0..2000 {
+        let key = rng.below(1 << 20);
+        heap.insert(key, value);
+        model.push((key, value));
+        if rng.below(3) == 0 {
+            let (key, value) = model.swap_remove(rng.below(model.len() as u64) as usize);
+            assert_eq!(heap.delete(&value), Some(key));
+        }
+        assert!(heap.capacity() <= heap.len() * 2 + 16);
+    }
+    
+    // Draining all but a few entries compacts on the way down, leaving at most the limit free since the last time and
+    // the limit of spare room it kept
+    while heap.len() > 10 {
+        let (key, value) = heap.extract_min().unwrap();
+        assert!(model.contains(&(key, value)));
+    }
+    assert!(heap.capacity() <= 10 + 2 * 16, "capacity {}", heap.capacity());
+    assert_eq!(heap.check_invariants(), Ok(()));
+    
+    model.sort();
+    let rest = model.split_off(model.len() - 10);
+    assert_eq!(heap.into_sorted_vec(), rest);
+}
+
+#[test]
+fn lowering_the_pool_limit_compacts_at_once() {
+    let mut heap: FibonacciHeap<_, _> = (0..100).map(|i| (i, i)).collect();
+    for i in 0..90 {
+        heap.delete(&i);
+    }
+    assert!(heap.capacity() >= 100);
+    
+    heap.set_pool_limit(0);
+    
+    assert_eq!(heap.capacity(), 10);
+    assert_eq!(heap.into_sorted_vec(), (90..100).map(|i| (i, i)).collect::<Vec<_>>());
+}
@@ -0,0 +1,82 @@
+use core::cmp::{Ordering, Reverse};
+use std::vec;
+use std::vec::Vec;
+
+use super::Rng;
+use {DecreaseKeyError, FibonacciHeap};
+
+#[test]
+fn reversed_comparator_extracts_in_descending_order() {
+    let mut heap = FibonacciHeap::new_by(|a: &i32, b: &i32| b.cmp(a));
+    heap.extend((0..50).map(|i| ((i * 17) % 50, i)));
+    heap.extract_min();
+    
+    // Under the reversed order a larger number is a smaller key
+    assert_eq!(heap.decrease_key(3, 100), Ok(1));
+    assert_eq!(heap.decrease_key(4, 0), Err(DecreaseKeyError::KeyGreaterThanCurrent(0)));
+    assert_eq!(heap.check_invariants(), Ok(()));
+    
+    let keys: Vec<_> = heap.into_iter().map(|(key, _)| key).collect();
+    let mut expected: Vec<_> = (0..49).filter(|&key| key != 1).chain(Some(100)).collect();
+    expected.sort_by(|a, b| b.cmp(a));
+    assert_eq!(keys, expected);
+}
+
+#[test]
+fn new_by_orders_by_one_field_of_the_key() {
+    // Keys are (name, priority), ordered by priority alone
+    let mut heap = FibonacciHeap::new_by(|a: &(&str, u32), b: &(&str, u32)| a.1.cmp(&b.1));
+    heap.insert(("c", 3), 'c');
+    heap.insert(("a", 9), 'a');
+    heap.insert(("b", 1), 'b');
+    
+    assert_eq!(heap.minimum(), Some((&("b", 1), &'b')));
+    assert_eq!(heap.decrease_key('a', ("z", 0)), Ok(("a", 9)));
+    
+    let names: Vec<_> = heap.into_iter().map(|((name, _), _)| name).collect();
+    assert_eq!(names, ["z", "b", "c"]);
+}
+
+#[test]
+fn new_by_key_orders_by_the_extracted_sort_key() {
+    let mut heap = FibonacciHeap::new_by_key(|key: &&str| key.len());
+    heap.extend(vec![("three", 3), ("a", 1), ("xx", 2), ("sixsix", 6)]);
+    assert_eq!(heap.minimum(), Some((&"a", &1)));
+    
+    let values: Vec<_> = heap.into_iter().map(|(_, value)| value).collect();
+    assert_eq!(values, [1, 2, 3, 6]);
+    
+    // A reversed sort key gives a max-heap
+    let mut heap = FibonacciHeap::new_by_key(|&key: &u32| Reverse(key));
+    heap.extend((0..10).map(|i| (i, i)));
+    assert_eq!(heap.extract_min(), Some((9, 9)));
+    assert_eq!(heap.extract_min(), Some((8, 8)));
+}
+
+// Orders by the last digit, and numbers with the same last digit largest first
+fn by_digit_then_descending(a: &u64, b: &u64) -> Ordering {
+    (a % 10).cmp(&(b % 10)).then(b.cmp(a))
+}
+
+#[test]
+fn comparators_match_sort_by_on_random_keys() {
+    let seeds = if cfg!(miri) { 2 } else { 50 };
+    for seed in 0..seeds {
+        let mut rng = Rng::new(seed);
+        let keys: Vec<u64> = (0..rng.below(300)).map(|_| rng.below(1000)).collect();
+        
+        let mut heap = FibonacciHeap::new_by(by_digit_then_descending);
+        heap.extend(keys.iter().enumerate().map(|(i, &key)| (key, i)));
+        let mut expected = keys.clone();
+        expected.sort_by(by_digit_then_descending);
+        let extracted: Vec<_> = heap.into_iter().map(|(key, _)| key).collect();
+        assert_eq!(extracted, expected, "seed {}", seed);
+        
+        let mut heap = FibonacciHeap::new_by_key(|&key: &u64| (key / 100, Reverse(key % 100)));
+        heap.extend(keys.iter().enumerate().map(|(i, &key)| (key, i)));
+        let mut expected = keys.clone();
+        expected.sort_by_key(|&key| (key / 100, Reverse(key % 100)));
+        let extracted: Vec<_> = heap.into_iter().map(|(key, _)| key).collect();
+        assert_eq!(extracted, expected, "seed {}", seed);
+    }
+}
@@ -0,0 +1,54 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::Rng;
+use FibonacciHeap;
+
+// Only push, pop, peek, len and is_empty, the surface shared with BinaryHeap
+#[test]
+#[cfg_attr(miri, ignore)]
+fn push_pop_peek_match_a_reversed_binary_heap() {
+    let mut rng = Rng::new(9);
+    let mut heap = FibonacciHeap::new();
+    let mut std_heap = BinaryHeap::new();
+    let mut next = 0;
+    
+    for _ in 0..5000 {
+        if rng.below(3) == 0 {
+            let popped = heap.pop().map(|(key, _)| key);
+            assert_eq!(popped, std_heap.pop().map(|Reverse(key)| key));
+        } else {
+            let key = rng.below(100);
+            heap.push(key, next);
+            std_heap.push(Reverse(key));
+            next += 1;
+        }
+        
+        assert_eq!(heap.peek().map(|(&key, _)| key), std_heap.peek().map(|&Reverse(key)| key));
+        assert_eq!(heap.len(), std_heap.len());
+        assert_eq!(heap.is_empty(), std_heap.is_empty());
+    }
+}
+
+#[test]
+fn pop_and_peek_on_an_empty_heap_return_none() {
+    let mut heap: FibonacciHeap<u32, u32> = FibonacciHeap::new();
+    
+    assert_eq!(heap.peek(), None);
+    assert_eq!(heap.pop(), None);
+    
+    heap.push(1, 1);
+    assert_eq!(heap.pop(), Some((1, 1)));
+    assert_eq!(heap.pop(), None);
+}
+
+#[test]
+fn peek_does_not_remove() {
+    let mut heap = FibonacciHeap::new();
+    heap.push(2, "b");
+    heap.push(1, "a");
+    
+    assert_eq!(heap.peek(), Some((&1, &"a")));
+    assert_eq!(heap.peek(), Some((&1, &"a")));
+    assert_eq!(heap.len(), 2);
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "instrument")]
+
+use FibonacciHeap;
+
+// The number of bits of n, which is log2(n) rounded up for n that are not powers of two
+fn log2(n: u64) -> u64 {
+    64 - u64::from(n.leading_zeros())
+}
+
+#[test]
+fn inserting_and_extracting_n_entries_links_o_n_log_n_times() {
+    let sizes: &[u64] = if cfg!(feature = "debug-invariants") { &[100, 1000] } else { &[100, 1000, 10_000, 100_000] };
+    for &n in sizes {
+        let mut heap = FibonacciHeap::new();
+        for i in 0..n {
+            heap.insert(i.wrapping_mul(0x9e37_79b9) % n, i);
+        }
+        assert_eq!(heap.counters().links(), 0);
+        assert_eq!(heap.counters().root_insertions(), n);
+        
+        while heap.extract_min().is_some() { }
+        
+        let counters = heap.counters();
+        // The first extraction alone links all but the O(log n) remaining roots
+        assert!(counters.links() >= n - 1 - log2(n), "{} links for {} entries", counters.links(), n);
+        assert!(counters.links() <= n * log2(n), "{} links for {} entries", counters.links(), n);
+        // Checking the heap after every operation compares keys as well
+        if !cfg!(feature = "debug-invariants") {
+            let comparisons = counters.comparisons();
+            assert!(comparisons <= 3 * n * log2(n), "{} comparisons for {} entries", comparisons, n);
+        }
+        assert_eq!(counters.cuts(), 0);
+    }
+}
+
+#[test]
+fn decreasing_every_key_of_a_binomial_tree_cuts_each_node_once() {
+    let depth = if cfg!(feature = "debug-invariants") { 8 } else { 14 };
+    let n = 1u64 << depth;
+    // Extracting the smallest of n + 1 entries links the other n into one binomial tree
+    let mut heap: FibonacciHeap<_, _> = (0..=n).map(|i| (i + 1, i)).collect();
+    heap.extract_min();
+    assert_eq!(heap.stats().roots, 1);
+    heap.reset_counters();
+    
+    // Below the root, in an order that takes children from the same parent one after the other
+    for value in (2..=n).rev() {
+        heap.decrease_key(value, 0).unwrap();
+    }
+    
+    let counters = heap.counters();
+    // Once cut a node is a root, so every node but the root is cut exactly once, directly or by a cascade
+    assert_eq!(counters.cuts(), n - 1);
+    assert!(counters.cascading_cuts() > 0);
+    assert!(counters.cascading_cuts() < counters.cuts());
+    assert_eq!(counters.links(), 0);
+}
+
+#[test]
+fn decreases_that_keep_the_heap_order_cut_nothing() {
+    let mut heap: FibonacciHeap<_, _> = (0..1025u64).map(|i| (i * 2, i)).collect();
+    heap.extract_min();
+    heap.reset_counters();
+    
+    // Every child is at least one larger than its parent, which the odd keys stay above
+    for value in 1..1025 {
+        heap.decrease_key(value, value * 2 - 1).unwrap();
+    }
+    
+    assert_eq!(heap.counters().cuts(), 0);
+    assert_eq!(heap.counters().cascading_cuts(), 0);
+}
+
+#[test]
+fn cascading_cuts_stay_within_twice_the_decreases() {
+    let n: u64 = if cfg!(feature = "debug-invariants") { 1000 } else { 50_000 };
+    let mut heap: FibonacciHeap<_, _> = (0..n).map(|i| (n + i, i)).collect();
+    let mut decreases = 0;
+    
+    // Interleaving extractions keeps building trees that the decreases then take apart
+    for i in 0..n {
+        if i % 10 == 0 {
+            heap.extract_min();
+        } else if heap.decrease_key(i.wrapping_mul(0x9e37_79b9) % n, n - i).is_ok() {
+            decreases += 1;
+        }
+    }
+    
+    let counters = heap.counters();
+    // A cascade only cuts nodes whose mark an earlier decrease set, and each decrease sets at most one
+    assert!(counters.cascading_cuts() <= decreases);
+    assert!(counters.cuts() <= 2 * decreases);
+    assert!(counters.cuts() > 0);
+}
@@ -0,0 +1,52 @@
+use std::format;
+use std::vec;
+use std::vec::Vec;
+
+use {FibonacciHeap, HeapSnapshot, SnapshotNode};
+
+fn node(key: u64, value: char, marked: bool, children: Vec<usize>) -> SnapshotNode<u64, char> {
+    SnapshotNode { key, value, degree: children.len(), marked, children }
+}
+
+// 0 ── 1 ── 4       3
+//   └─ 2 (marked)
+fn forest() -> FibonacciHeap<u64, char> {
+    let snapshot = HeapSnapshot {
+        nodes: vec![node(0, 'a', false, vec![1, 2]), node(1, 'b', false, vec![4]), node(2, 'c', true, vec![]),
+                    node(3, 'd', false, vec![]), node(4, 'e', false, vec![])],
+        roots: vec![0, 3]
+    };
+    
+    FibonacciHeap::from_snapshot(snapshot).unwrap()
+}
+
+#[test]
+fn debug_prints_the_forest_on_one_line() {
+    assert_eq!(format!("{:?}", forest()),
+               "FibonacciHeap { len: 5, min: Some((0, 'a')), roots: [0: 'a' [1: 'b' [4: 'e'], 2: 'c'*], 3: 'd'] }");
+}
+
+#[test]
+fn alternate_debug_indents_each_child_below_its_parent() {
+    let expected = "\
+FibonacciHeap {
+    len: 5,
+    min: Some((0, 'a')),
+    roots: [
+        0: 'a',
+            1: 'b',
+                4: 'e',
+            2: 'c' (marked),
+        3: 'd',
+    ]
+}";
+    assert_eq!(format!("{:#?}", forest()), expected);
+}
+
+#[test]
+fn debug_of_an_empty_heap() {
+    let heap: FibonacciHeap<u64, char> = FibonacciHeap::new();
+    
+    assert_eq!(format!("{:?}", heap), "FibonacciHeap { len: 0, min: None, roots: [] }");
+    assert_eq!(format!("{:#?}", heap), "FibonacciHeap {\n    len: 0,\n    min: None,\n    roots: [\n    ]\n}");
+}
@@ -0,0 +1,82 @@
+use std::vec;
+use std::vec::Vec;
+
+use super::Rng;
+use DecreaseKeyError;
+use FibonacciHeap;
+
+fn consolidated(n: u64) -> FibonacciHeap<u64, u64> {
+    let mut heap: FibonacciHeap<_, _> = (0..n).map(|i| (i * 10, i)).collect();
+    heap.extract_min();
+    
+    heap
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn batch_matches_updates_applied_one_by_one() {
+    for seed in 0..20 {
+        let mut rng = Rng::new(seed);
+        // Keys are distinct, so both heaps extract the same values even though their trees differ
+        let mut batched: FibonacciHeap<_, _> = (0..500).map(|i| (i << 32, i)).collect();
+        batched.extract_min();
+        let mut looped = batched.clone();
+        
+        for _ in 0..20 {
+            let updates: Vec<(u64, u64)> = (0..30).map(|_| (rng.below(600), rng.below(500 << 32))).collect();
+            let results = batched.decrease_keys(updates.clone());
+            let expected: Vec<_> = updates.into_iter().map(|(value, key)| looped.decrease_key(value, key)).collect();
+            
+            assert_eq!(results, expected);
+            assert_eq!(batched.check_invariants(), Ok(()));
+            assert_eq!(batched.minimum(), looped.minimum());
+            
+            batched.extract_min();
+            looped.extract_min();
+        }
+        
+        let batched: Vec<_> = batched.into_iter().map(|(key, _)| key).collect();
+        let looped: Vec<_> = looped.into_iter().map(|(key, _)| key).collect();
+        assert_eq!(batched, looped);
+    }
+}
+
+#[test]
+fn batch_reports_errors_in_order() {
+    let mut heap = consolidated(10);
+    let results = heap.decrease_keys(vec![(3, 5), (42, 0), (4, 1000), (3, 1), (2, 1)]);
+    
+    assert_eq!(results, vec![
+        Ok(30),
+        Err(DecreaseKeyError::NotFound(0)),
+        Err(DecreaseKeyError::KeyGreaterThanCurrent(1000)),
+        Ok(5),
+        Ok(20)
+    ]);
+    assert_eq!(heap.get_priority(&3), Some(&1));
+    assert_eq!(heap.minimum(), Some((&1, &3)));
+}
+
+#[test]
+fn parent_losing_two_children_in_a_batch_is_cut() {
+    let mut heap = consolidated(16);
+    
+    // A node below a root with at least two children
+    let snapshot = heap.to_snapshot();
+    let is_child = |x: usize| snapshot.nodes.iter().any(|node| node.children.contains(&x));
+    let parent = (0..snapshot.nodes.len())
+        .find(|&x| snapshot.nodes[x].children.len() >= 2 && is_child(x))
+        .expect("a consolidated heap of 15 nodes has one");
+    let children = &snapshot.nodes[parent].children;
+    let parent = snapshot.nodes[parent].value;
+    
+    let updates = vec![
+        (snapshot.nodes[children[0]].value, 0),
+        (snapshot.nodes[children[1]].value, 0)
+    ];
+    heap.decrease_keys(updates);
+    
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert!(heap.roots().any(|(_, &value)| value == parent));
+    heap.visit(|_, _, info| assert!(!info.marked || info.depth > 0));
+}
@@ -0,0 +1,158 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::thread;
+use std::vec;
+use std::vec::Vec;
+
+use super::{Counted, Rng};
+use {FibonacciHeap, HeapSnapshot, SnapshotNode};
+
+const SMALL_STACK: usize = 128 * 1024;
+const MEBIBYTE_STACK: usize = 1024 * 1024;
+
+// A single path from the root down, with every node but the root marked so a cut at the bottom cascades to the top
+fn marked_chain(depth: usize) -> FibonacciHeap<usize, usize> {
+    chain(depth, |i| i)
+}
+
+fn chain<V, F>(depth: usize, mut value: F) -> FibonacciHeap<usize, V>
+    where V: Eq + ::core::hash::Hash,
+          F: FnMut(usize) -> V
+{
+    let nodes = (0..depth)
+        .map(|i| SnapshotNode {
+            key: i,
+            value: value(i),
+            degree: if i + 1 < depth { 1 } else { 0 },
+            marked: i > 0,
+            children: if i + 1 < depth { vec![i + 1] } else { Vec::new() }
+        })
+        .collect();
+    
+    FibonacciHeap::from_snapshot(HeapSnapshot { nodes, roots: vec![0] }).unwrap()
+}
+
+fn on_stack<F>(size: usize, f: F)
+    where F: FnOnce() + Send + 'static
+{
+    thread::Builder::new().stack_size(size).spawn(f).unwrap().join().unwrap();
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn cascading_cut_up_a_deep_marked_chain() {
+    on_stack(SMALL_STACK, || {
+        let depth = 100_000;
+        let mut heap = marked_chain(depth);
+        assert_eq!(heap.stats().max_degree, 1);
+        
+        // Cutting the leaf cuts every marked ancestor in turn, leaving each of them a root
+        assert_eq!(heap.decrease_key(depth - 1, 0), Ok(depth - 1));
+        assert_eq!(heap.stats().roots, depth);
+        assert_eq!(heap.stats().marked, 0);
+        assert!(heap.check_invariants().is_ok());
+        
+        assert_eq!(heap.extract_min(), Some((0, 0)));
+        assert_eq!(heap.extract_min(), Some((0, depth - 1)));
+        assert!(heap.check_invariants().is_ok());
+    });
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn decrease_leaves_of_tall_trees_on_a_small_stack() {
+    on_stack(SMALL_STACK, || {
+        let mut rng = Rng::new(9);
+        let mut heap = FibonacciHeap::new();
+        let mut next = 0;
+        // With debug-invariants every operation checks the whole heap, so the heap is kept small there
+        let rounds = if cfg!(feature = "debug-invariants") { 2 } else { 20 };
+        
+        // Ascending inserts linked by an extraction, then decreases deep in the trees to build marked chains
+        for _ in 0..rounds {
+            for _ in 0..2000 {
+                heap.insert(next + 1_000_000, next);
+                next += 1;
+            }
+            heap.extract_min();
+            
+            for _ in 0..1000 {
+                let value = rng.below(next as u64) as usize;
+                if let Some(&key) = heap.get_priority(&value) {
+                    let _ = heap.decrease_key(value, key - 1 - rng.below(1000) as usize);
+                }
+            }
+        }
+        
+        assert!(heap.check_invariants().is_ok());
+        let mut last = 0;
+        while let Some((key, _)) = heap.extract_min() {
+            assert!(key >= last);
+            last = key;
+        }
+    });
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn deep_chain_is_dropped_on_a_small_stack() {
+    on_stack(MEBIBYTE_STACK, || {
+        let drops = Rc::new(Cell::new(0));
+        let heap = chain(1_000_000, |i| Counted::new(i as u64, &drops));
+        
+        drop(heap);
+        assert_eq!(drops.get(), 1_000_000);
+    });
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn deep_chain_is_cleared_on_a_small_stack() {
+    on_stack(MEBIBYTE_STACK, || {
+        let drops = Rc::new(Cell::new(0));
+        let mut heap = chain(1_000_000, |i| Counted::new(i as u64, &drops));
+        
+        heap.clear();
+        assert_eq!(drops.get(), 1_000_000);
+        assert!(heap.is_empty());
+    });
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn partly_consumed_into_iter_of_a_deep_chain_is_dropped_on_a_small_stack() {
+    on_stack(MEBIBYTE_STACK, || {
+        let drops = Rc::new(Cell::new(0));
+        let mut iter = chain(1_000_000, |i| Counted::new(i as u64, &drops)).into_iter();
+        
+        // Each extraction promotes the single child of the old root, so the chain stays as deep as it was
+        for _ in 0..3 {
+            iter.next();
+        }
+        assert_eq!(drops.get(), 3);
+        drop(iter);
+        assert_eq!(drops.get(), 1_000_000);
+    });
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn tall_consolidated_trees_are_dropped_on_a_small_stack() {
+    on_stack(MEBIBYTE_STACK, || {
+        let drops = Rc::new(Cell::new(0));
+        let mut heap = FibonacciHeap::new();
+        // With debug-invariants every operation checks the whole heap, so the heap is kept small there
+        let (rounds, size) = if cfg!(feature = "debug-invariants") { (3, 1000) } else { (20, 10_000) };
+        
+        // Ascending inserts and one extraction at a time, so each round links the new entries under the old trees
+        for round in 0..rounds {
+            for i in 0..size {
+                heap.insert(round * size + i, Counted::new(round * size + i, &drops));
+            }
+            heap.extract_min();
+        }
+        
+        drop(heap);
+        assert_eq!(drops.get() as u64, rounds * size);
+    });
+}
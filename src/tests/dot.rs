@@ -0,0 +1,47 @@
+use std::vec;
+use std::vec::Vec;
+
+use {FibonacciHeap, HeapSnapshot, SnapshotNode};
+
+fn node(key: u64, value: char, marked: bool, children: Vec<usize>) -> SnapshotNode<u64, char> {
+    SnapshotNode { key, value, degree: children.len(), marked, children }
+}
+
+// 3       0 ── 1 ── 4
+//           └─ 2 (marked)
+fn forest() -> FibonacciHeap<u64, char> {
+    let snapshot = HeapSnapshot {
+        nodes: vec![node(3, 'd', false, vec![]), node(0, 'a', false, vec![2, 3]), node(1, 'b', false, vec![4]),
+                    node(2, 'c', true, vec![]), node(4, 'e', false, vec![])],
+        roots: vec![0, 1]
+    };
+    
+    FibonacciHeap::from_snapshot(snapshot).unwrap()
+}
+
+#[test]
+fn to_dot_draws_edges_marks_and_the_minimum() {
+    let expected = "\
+digraph FibonacciHeap {
+    n0 [label=\"3: 'd'\"];
+    n1 [label=\"0: 'a'\"];
+    n2 [label=\"1: 'b'\"];
+    n1 -> n2;
+    n3 [label=\"4: 'e'\"];
+    n2 -> n3;
+    n4 [label=\"2: 'c'\", style=filled, fillcolor=gray];
+    n1 -> n4;
+    { rank=same; n0; n1; }
+    min [shape=plaintext];
+    min -> n1 [style=dashed];
+}
+";
+    assert_eq!(forest().to_dot(), expected);
+}
+
+#[test]
+fn to_dot_of_an_empty_heap_is_an_empty_digraph() {
+    let heap: FibonacciHeap<u64, char> = FibonacciHeap::new();
+    
+    assert_eq!(heap.to_dot(), "digraph FibonacciHeap {\n}\n");
+}
@@ -0,0 +1,73 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use super::{Counted, Rng};
+use FibonacciHeap;
+
+#[test]
+fn drain_sorted_yields_every_entry_in_ascending_key_order() {
+    let mut rng = Rng::new(44);
+    let mut entries: Vec<_> = (0..500).map(|i| (rng.below(50), i)).collect();
+    let mut heap: FibonacciHeap<_, _> = entries.iter().cloned().collect();
+    heap.extract_min();
+    entries.sort();
+    entries.remove(0);
+    
+    let mut drain = heap.drain_sorted();
+    let mut drained = Vec::new();
+    for remaining in (0..entries.len()).rev() {
+        drained.extend(drain.next());
+        assert_eq!(drain.size_hint(), (remaining, Some(remaining)));
+        assert_eq!(drain.len(), remaining);
+    }
+    assert_eq!(drain.next(), None);
+    drop(drain);
+    
+    assert!(drained.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    drained.sort();
+    assert_eq!(drained, entries);
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn dropping_drain_sorted_early_empties_the_heap() {
+    let drops = Rc::new(Cell::new(0));
+    let mut heap = FibonacciHeap::new();
+    for i in 0..100 {
+        heap.insert(i, Counted::new(i, &drops));
+    }
+    
+    let first: Vec<_> = heap.drain_sorted().take(3).map(|(key, _)| key).collect();
+    assert_eq!(first, [0, 1, 2]);
+    assert!(heap.is_empty());
+    assert!(heap.minimum().is_none());
+    assert_eq!(heap.num_roots(), 0);
+    assert_eq!(drops.get(), 100);
+    
+    // A drain that is never advanced removes everything as well
+    heap.insert(0, Counted::new(0, &drops));
+    drop(heap.drain_sorted());
+    assert!(heap.is_empty());
+    assert_eq!(drops.get(), 101);
+}
+
+#[test]
+fn the_heap_can_be_used_again_after_drain_sorted() {
+    let mut heap = FibonacciHeap::new();
+    for i in 0..10 {
+        heap.insert(10 - i, i);
+    }
+    
+    assert_eq!(heap.drain_sorted().next(), Some((1, 9)));
+    for i in 0..5 {
+        heap.insert(i, i);
+    }
+    assert_eq!(heap.len(), 5);
+    assert!(heap.contains(&4));
+    assert!(!heap.contains(&9));
+    assert_eq!(heap.drain_sorted().collect::<Vec<_>>(), [(0, 0), (1, 1), (2, 2), (3, 3), (4, 4)]);
+    
+    heap.insert(7, 7);
+    assert_eq!(heap.extract_min(), Some((7, 7)));
+}
@@ -0,0 +1,52 @@
+use std::vec;
+use std::vec::Vec;
+
+use {FibonacciHeap, HeapSnapshot, SnapshotNode};
+
+fn node(key: u64, value: char, marked: bool, children: Vec<usize>) -> SnapshotNode<u64, char> {
+    SnapshotNode { key, value, degree: children.len(), marked, children }
+}
+
+// 3       0 ── 1 ── 4
+//           └─ 2 (marked)
+fn forest() -> FibonacciHeap<u64, char> {
+    let snapshot = HeapSnapshot {
+        nodes: vec![node(3, 'd', false, vec![]), node(0, 'a', false, vec![2, 3]), node(1, 'b', false, vec![4]),
+                    node(2, 'c', true, vec![]), node(4, 'e', false, vec![])],
+        roots: vec![0, 1]
+    };
+    
+    FibonacciHeap::from_snapshot(snapshot).unwrap()
+}
+
+#[test]
+fn dump_draws_each_tree_with_marks_and_the_minimum() {
+    let expected = "\
+3: 'd'
+min→ 0: 'a'
+├── 1: 'b'
+│   └── 4: 'e'
+└── 2: 'c'*
+len: 5, roots: 2";
+    assert_eq!(forest().dump(), expected);
+}
+
+#[test]
+fn dump_truncated_counts_the_entries_left_out() {
+    let expected = "\
+3: 'd'
+min→ 0: 'a'
+├── 1: 'b'
+… 2 more
+len: 5, roots: 2";
+    assert_eq!(forest().dump_truncated(3), expected);
+    assert_eq!(forest().dump_truncated(5), forest().dump());
+}
+
+#[test]
+fn dump_of_an_empty_heap_is_only_the_summary() {
+    let heap: FibonacciHeap<u64, char> = FibonacciHeap::new();
+    
+    assert_eq!(heap.dump(), "len: 0, roots: 0");
+    assert_eq!(heap.dump_truncated(0), "len: 0, roots: 0");
+}
@@ -0,0 +1,87 @@
+use std::collections::HashMap;
+use std::vec;
+use std::vec::Vec;
+
+use super::Rng;
+use FibonacciHeap;
+
+// Drains the heap, checking that every value comes out once and in order
+fn drain_checked(heap: &mut FibonacciHeap<u64, u64>) -> Vec<(u64, u64)> {
+    let mut drained = Vec::new();
+    while let Some(entry) = heap.extract_min() {
+        assert_eq!(heap.check_invariants(), Ok(()));
+        drained.push(entry);
+    }
+    
+    for pair in drained.windows(2) {
+        assert!(pair[0].0 <= pair[1].0);
+    }
+    let mut values: Vec<u64> = drained.iter().map(|&(_, value)| value).collect();
+    values.sort();
+    values.dedup();
+    assert_eq!(values.len(), drained.len());
+    
+    drained
+}
+
+#[test]
+fn duplicate_insert_of_a_root_replaces_it() {
+    let mut heap = FibonacciHeap::new();
+    heap.insert(5, 1);
+    heap.insert(7, 2);
+    heap.insert(3, 1);
+    
+    assert_eq!(heap.len(), 2);
+    assert_eq!(heap.get_priority(&1), Some(&3));
+    assert_eq!(drain_checked(&mut heap), vec![(3, 1), (7, 2)]);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn duplicate_insert_deep_in_a_tree_replaces_it() {
+    let mut heap = FibonacciHeap::new();
+    for i in 0..64 {
+        heap.insert(i, i);
+    }
+    heap.extract_min();
+    
+    // Smaller and larger keys for values that are children and grandchildren by now
+    heap.insert(100, 33);
+    heap.insert(0, 63);
+    heap.insert(40, 2);
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(heap.len(), 63);
+    
+    let drained = drain_checked(&mut heap);
+    assert_eq!(drained[0], (0, 63));
+    assert_eq!(drained[62], (100, 33));
+    assert!(drained.contains(&(40, 2)));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn random_duplicate_inserts_keep_one_node_per_value() {
+    for seed in 0..10 {
+        let mut rng = Rng::new(seed);
+        let mut heap = FibonacciHeap::new();
+        let mut model = HashMap::new();
+        
+        for _ in 0..1000 {
+            let (key, value) = (rng.below(500), rng.below(100));
+            heap.insert(key, value);
+            model.insert(value, key);
+            
+            if rng.below(5) == 0 {
+                if let Some((key, value)) = heap.extract_min() {
+                    assert_eq!(model.remove(&value), Some(key));
+                }
+            }
+        }
+        
+        let drained = drain_checked(&mut heap);
+        assert_eq!(drained.len(), model.len());
+        for (key, value) in drained {
+            assert_eq!(model[&value], key);
+        }
+    }
+}
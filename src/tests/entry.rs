@@ -0,0 +1,151 @@
+use std::vec::Vec;
+
+use Entry;
+use FibonacciHeap;
+
+#[test]
+fn or_insert_inserts_only_into_a_vacant_entry() {
+    let mut heap = FibonacciHeap::new();
+    heap.insert(5, "a");
+    
+    assert_eq!(*heap.entry("a").or_insert(1), 5);
+    assert_eq!(*heap.entry("b").or_insert(3), 3);
+    assert_eq!(*heap.entry("c").or_insert_with(|| 0), 0);
+    assert_eq!(*heap.entry("c").or_insert_with(|| panic!("entry is occupied")), 0);
+    
+    assert_eq!(heap.len(), 3);
+    assert_eq!(heap.minimum(), Some((&0, &"c")));
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn and_modify_changes_only_an_occupied_entry() {
+    let mut heap: FibonacciHeap<_, _> = (0..10).map(|i| (i, i)).collect();
+    heap.extract_min();
+    
+    // Lowering a key makes it the minimum, raising the minimum's key makes another entry the minimum
+    assert_eq!(*heap.entry(5).and_modify(|key| *key -= 10).or_insert(100), -5);
+    assert_eq!(heap.minimum(), Some((&-5, &5)));
+    assert_eq!(*heap.entry(5).and_modify(|key| *key += 20).or_insert(100), 15);
+    assert_eq!(heap.minimum(), Some((&1, &1)));
+    
+    // A vacant entry is left for or_insert
+    assert_eq!(*heap.entry(20).and_modify(|_| panic!("entry is vacant")).or_insert(7), 7);
+    
+    assert_eq!(heap.len(), 10);
+    assert_eq!(heap.check_invariants(), Ok(()));
+    let keys: Vec<_> = heap.into_iter().map(|(key, _)| key).collect();
+    assert_eq!(keys, [1, 2, 3, 4, 6, 7, 7, 8, 9, 15]);
+}
+
+#[test]
+fn occupied_entry_changes_and_removes_its_value() {
+    let mut heap: FibonacciHeap<_, _> = (0..10).map(|i| (i, i)).collect();
+    heap.extract_min();
+    
+    match heap.entry(4) {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.value(), &4);
+            assert_eq!(entry.set_priority(0), 4);
+            assert_eq!(entry.priority(), &0);
+            assert_eq!(entry.set_priority(40), 0);
+            assert_eq!(entry.remove(), (40, 4));
+        },
+        Entry::Vacant(_) => panic!("entry is vacant")
+    }
+    
+    assert!(!heap.contains(&4));
+    assert_eq!(heap.len(), 8);
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn insert_or_update_inserts_a_new_value_and_hands_back_the_old_priority() {
+    let mut heap = FibonacciHeap::new();
+    
+    assert_eq!(heap.insert_or_update(5, "a"), None);
+    assert_eq!(heap.insert_or_update(7, "b"), None);
+    assert_eq!(heap.len(), 2);
+    assert_eq!(heap.minimum(), Some((&5, &"a")));
+    
+    // Raising the minimum's key above another makes that one the minimum, an equal key changes nothing
+    assert_eq!(heap.insert_or_update(9, "a"), Some(5));
+    assert_eq!(heap.minimum(), Some((&7, &"b")));
+    assert_eq!(heap.insert_or_update(7, "b"), Some(7));
+    assert_eq!(heap.insert_or_update(1, "a"), Some(9));
+    assert_eq!(heap.minimum(), Some((&1, &"a")));
+    
+    assert_eq!(heap.len(), 2);
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn insert_or_update_keeps_a_consolidated_heap_valid() {
+    let mut heap: FibonacciHeap<_, _> = (0..64).map(|i| (i, i)).collect();
+    heap.extract_min();
+    
+    // Even values move below every other key, odd ones bring a new value along far above
+    for i in 1..64 {
+        if i % 2 == 0 {
+            assert_eq!(heap.insert_or_update(-i, i), Some(i));
+        } else {
+            assert_eq!(heap.insert_or_update(100 + i, 100 + i), None);
+        }
+    }
+    
+    assert_eq!(heap.len(), 63 + 32);
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(heap.minimum(), Some((&-62, &62)));
+    assert_eq!(heap.get_priority(&163), Some(&163));
+}
+
+// Checking the invariants hashes every value again
+#[cfg(not(feature = "debug-invariants"))]
+#[test]
+fn entry_hashes_the_value_once() {
+    use std::cell::Cell;
+    use std::hash::{Hash, Hasher};
+    
+    // A value that counts how often it is hashed
+    struct Hashed<'a> {
+        id: u64,
+        hashes: &'a Cell<usize>
+    }
+    
+    impl<'a> PartialEq for Hashed<'a> {
+        fn eq(&self, other: &Hashed<'a>) -> bool {
+            self.id == other.id
+        }
+    }
+    
+    impl<'a> Eq for Hashed<'a> { }
+    
+    impl<'a> Hash for Hashed<'a> {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            self.hashes.set(self.hashes.get() + 1);
+            self.id.hash(state);
+        }
+    }
+    
+    let hashes = Cell::new(0);
+    let mut heap = FibonacciHeap::with_capacity(16);
+    for id in 0..8 {
+        heap.insert(id, Hashed { id, hashes: &hashes });
+    }
+    
+    hashes.set(0);
+    heap.entry(Hashed { id: 20, hashes: &hashes }).or_insert(20);
+    assert_eq!(hashes.get(), 1);
+    
+    hashes.set(0);
+    heap.entry(Hashed { id: 3, hashes: &hashes }).and_modify(|key| *key = 30).or_insert(0);
+    assert_eq!(hashes.get(), 1);
+    
+    hashes.set(0);
+    assert_eq!(heap.insert_or_update(5, Hashed { id: 3, hashes: &hashes }), Some(30));
+    assert_eq!(heap.insert_or_update(21, Hashed { id: 21, hashes: &hashes }), None);
+    assert_eq!(hashes.get(), 2);
+    
+    assert_eq!(heap.len(), 10);
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
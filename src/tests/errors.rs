@@ -0,0 +1,100 @@
+use core::error::Error;
+use std::string::ToString;
+
+use {DecreaseKeyByError, DecreaseKeyError, FibonacciHeap, HeapError, OccupiedError, TryPushError, TryReserveError};
+
+#[test]
+fn decrease_key_reports_each_failure() {
+    let mut heap = FibonacciHeap::new();
+    heap.insert(5u32, "a");
+    
+    assert_eq!(heap.decrease_key("b", 1), Err(DecreaseKeyError::NotFound(1)));
+    assert_eq!(heap.decrease_key("a", 6), Err(DecreaseKeyError::KeyGreaterThanCurrent(6)));
+    // An equal key is not greater, so it is accepted
+    assert_eq!(heap.decrease_key("a", 5), Ok(5));
+    assert_eq!(heap.decrease_key("a", 2), Ok(5));
+    assert_eq!(heap.minimum(), Some((&2, &"a")));
+}
+
+#[test]
+fn decrease_key_by_reports_each_failure() {
+    let mut heap = FibonacciHeap::new();
+    heap.insert(5u32, "a");
+    
+    assert_eq!(heap.decrease_key_by(&"b", 1), Err(DecreaseKeyByError::NotFound(1)));
+    assert_eq!(heap.decrease_key_by(&"a", 6), Err(DecreaseKeyByError::Overflow(6)));
+    assert_eq!(heap.decrease_key_by(&"a", 0), Err(DecreaseKeyByError::NotDecreased(0)));
+    assert_eq!(heap.decrease_key_by(&"a", 5), Ok(0));
+}
+
+#[test]
+fn rejected_key_is_handed_back() {
+    assert_eq!(DecreaseKeyError::NotFound(1).into_key(), 1);
+    assert_eq!(DecreaseKeyError::KeyGreaterThanCurrent(2).into_key(), 2);
+    assert_eq!(DecreaseKeyByError::NotFound(3).into_delta(), 3);
+    assert_eq!(DecreaseKeyByError::NotDecreased(4).into_delta(), 4);
+    assert_eq!(DecreaseKeyByError::Overflow(5).into_delta(), 5);
+}
+
+#[test]
+fn increase_key_and_change_priority_report_each_failure() {
+    let mut heap = FibonacciHeap::new();
+    heap.insert(5u32, "a");
+    
+    assert_eq!(heap.increase_key(&"b", 9), Err(HeapError::NotFound));
+    assert_eq!(heap.increase_key(&"a", 4), Err(HeapError::KeyLessThanCurrent));
+    assert_eq!(heap.increase_key(&"a", 9), Ok(()));
+    assert_eq!(heap.change_priority(&"b", 1), Err(HeapError::NotFound));
+    assert_eq!(heap.change_priority(&"a", 1), Ok(9));
+}
+
+#[test]
+fn try_insert_hands_back_the_rejected_entry() {
+    let mut heap = FibonacciHeap::new();
+    assert_eq!(heap.try_insert(5u32, "a"), Ok(()));
+    
+    assert_eq!(heap.try_insert(1, "a"), Err(OccupiedError { key: 1, value: "a", existing_key: 5 }));
+    assert_eq!(heap.minimum(), Some((&5, &"a")));
+}
+
+#[test]
+fn try_reserve_reports_overflow() {
+    let mut heap: FibonacciHeap<u64, u64> = FibonacciHeap::new();
+    
+    assert_eq!(heap.try_reserve(usize::MAX), Err(TryReserveError::CapacityOverflow));
+    // Nodes link to each other by u32 indices, so no heap holds more than u32::MAX values
+    assert_eq!(heap.try_reserve(u32::MAX as usize + 1), Err(TryReserveError::CapacityOverflow));
+    assert_eq!(heap.try_reserve(10), Ok(()));
+}
+
+#[test]
+fn messages_name_what_was_rejected() {
+    assert_eq!(DecreaseKeyError::NotFound(7).to_string(), "the value is not in the heap, rejected key 7");
+    assert_eq!(DecreaseKeyError::KeyGreaterThanCurrent(7).to_string(),
+               "key 7 would not decrease the current priority of the value");
+    assert_eq!(DecreaseKeyByError::Overflow(7).to_string(),
+               "subtracting 7 from the current priority of the value overflows");
+    assert_eq!(HeapError::KeyLessThanCurrent.to_string(),
+               "the key would not increase the current priority of the value");
+    
+    let error = OccupiedError { key: 1, value: "a", existing_key: 5 };
+    assert_eq!(error.to_string(), "\"a\" is already in the heap with priority 5, rejected key 1");
+    
+    let error = TryPushError { key: 1, value: "a", error: TryReserveError::AllocError };
+    assert_eq!(error.to_string(),
+               "\"a\" with priority 1 could not be inserted, the memory allocator could not reserve the space");
+}
+
+#[test]
+fn errors_are_std_errors() {
+    fn source(error: &dyn Error) -> bool {
+        error.source().is_some()
+    }
+    
+    assert!(!source(&DecreaseKeyError::NotFound(1)));
+    assert!(!source(&DecreaseKeyByError::Overflow(1)));
+    assert!(!source(&HeapError::NotFound));
+    assert!(!source(&OccupiedError { key: 1, value: 2, existing_key: 3 }));
+    assert!(!source(&TryReserveError::AllocError));
+    assert!(!source(&TryPushError { key: 1, value: 2, error: TryReserveError::AllocError }));
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "std")]
+
+use std::vec::Vec;
+
+use HeapEvent::*;
+use {EventLog, FibonacciHeap};
+
+#[test]
+fn scripted_scenario_records_the_canonical_sequence() {
+    let log = EventLog::new();
+    let mut heap = FibonacciHeap::new();
+    heap.set_recorder(log.recorder());
+    
+    let h: Vec<_> = (0..5).map(|i| heap.push_with_handle(i, i)).collect();
+    assert_eq!(log.take(), [
+        NodeInserted(h[0]),
+        MinChanged(Some(h[0])),
+        NodeInserted(h[1]),
+        NodeInserted(h[2]),
+        NodeInserted(h[3]),
+        NodeInserted(h[4])
+    ]);
+    
+    // 0 has no children to promote, the other roots are linked into 1 -> {2, 3 -> {4}}
+    heap.extract_min();
+    assert_eq!(log.take(), [
+        NodeExtracted(h[0]),
+        RootLinked { child: h[2], parent: h[1] },
+        RootLinked { child: h[4], parent: h[3] },
+        RootLinked { child: h[3], parent: h[1] },
+        MinChanged(Some(h[1]))
+    ]);
+    
+    heap.decrease_key(4, 0).unwrap();
+    assert_eq!(log.take(), [NodeCut(h[4]), NodeMarked(h[3]), MinChanged(Some(h[4]))]);
+    
+    // Deleting a node that is not the minimum leaves the minimum as it is
+    heap.delete(&2);
+    assert_eq!(log.take(), [NodeCut(h[2]), NodeExtracted(h[2])]);
+    
+    heap.delete(&3);
+    assert_eq!(log.take(), [
+        NodeCut(h[3]),
+        NodeUnmarked(h[3]),
+        NodeExtracted(h[3]),
+        RootLinked { child: h[1], parent: h[4] }
+    ]);
+    
+    heap.extract_min();
+    heap.extract_min();
+    assert_eq!(log.take(), [
+        ChildrenPromoted(h[4]),
+        NodeExtracted(h[4]),
+        MinChanged(Some(h[1])),
+        NodeExtracted(h[1]),
+        MinChanged(None)
+    ]);
+}
+
+#[test]
+fn events_name_the_entries_by_their_handles() {
+    let log = EventLog::new();
+    let mut heap = FibonacciHeap::new();
+    heap.set_recorder(log.recorder());
+    heap.insert(2, "b");
+    heap.insert(1, "a");
+    
+    for event in log.take() {
+        match event {
+            NodeInserted(handle) | MinChanged(Some(handle)) => assert!(heap.get_handle(handle).is_some()),
+            event => panic!("unexpected {:?}", event)
+        }
+    }
+    
+    heap.clear();
+    assert_eq!(log.take(), [Reset]);
+}
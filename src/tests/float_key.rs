@@ -0,0 +1,87 @@
+use core::cmp::Ordering;
+use std::vec::Vec;
+
+use {FibonacciHeap, FloatKey};
+
+#[test]
+fn infinities_order_below_and_above_every_finite_key() {
+    let mut heap = FibonacciHeap::new();
+    for (i, &key) in [1.5, f64::INFINITY, -2.0, f64::NEG_INFINITY, f64::MAX, f64::MIN].iter().enumerate() {
+        heap.insert(FloatKey(key), i);
+    }
+    
+    assert_eq!(heap.minimum().map(|(key, _)| key.0), Some(f64::NEG_INFINITY));
+    assert_eq!(heap.find_max().map(|(key, _)| key.0), Some(f64::INFINITY));
+    
+    let keys: Vec<_> = heap.into_iter().map(|(key, _)| key.0).collect();
+    assert_eq!(keys, [f64::NEG_INFINITY, f64::MIN, -2.0, 1.5, f64::MAX, f64::INFINITY]);
+}
+
+#[test]
+fn negative_and_positive_zero_are_equal() {
+    assert_eq!(FloatKey(-0.0f64).cmp(&FloatKey(0.0)), Ordering::Equal);
+    assert_eq!(FloatKey(-0.0f32), FloatKey(0.0f32));
+    
+    let mut heap = FibonacciHeap::new();
+    heap.insert(FloatKey(0.0), "positive");
+    heap.insert(FloatKey(-0.0), "negative");
+    heap.insert(FloatKey(-1e-300), "below");
+    
+    // Neither zero is a decrease of the other
+    assert!(heap.decrease_key("positive", FloatKey(-0.0)).is_ok());
+    assert_eq!(heap.extract_min().map(|(_, value)| value), Some("below"));
+    let (first, _) = heap.extract_min().unwrap();
+    let (second, _) = heap.extract_min().unwrap();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn nan_is_ordered_after_every_other_key() {
+    let nan = FloatKey(f64::NAN);
+    assert_eq!(nan.cmp(&FloatKey(f64::INFINITY)), Ordering::Greater);
+    assert_eq!(FloatKey(f64::INFINITY).cmp(&nan), Ordering::Less);
+    assert_eq!(nan.cmp(&FloatKey(-f64::NAN)), Ordering::Equal);
+    assert_eq!(nan, nan);
+    
+    let mut heap = FibonacciHeap::new();
+    heap.insert(FloatKey(f64::NAN), 0);
+    heap.insert(FloatKey(f64::INFINITY), 1);
+    heap.insert(FloatKey(3.0), 2);
+    heap.insert(FloatKey(f64::NAN), 3);
+    heap.insert(FloatKey(-1.0), 4);
+    heap.extract_min();
+    
+    // A NaN key is not a decrease of any other key, and any other key is a decrease of NaN
+    assert!(heap.decrease_key(2, FloatKey(f64::NAN)).is_err());
+    assert!(heap.decrease_key(3, FloatKey(f64::NAN)).is_ok());
+    assert!(heap.decrease_key(0, FloatKey(f64::INFINITY)).is_ok());
+    assert_eq!(heap.check_invariants(), Ok(()));
+    
+    let keys: Vec<_> = heap.into_iter().map(|(key, _)| key.0).collect();
+    assert_eq!(keys[..3], [3.0, f64::INFINITY, f64::INFINITY]);
+    assert!(keys[3].is_nan());
+}
+
+#[test]
+fn float_keys_survive_consolidation_with_nan() {
+    let mut heap = FibonacciHeap::new();
+    for i in 0..200u32 {
+        let key = match i % 7 {
+            0 => f32::NAN,
+            1 => f32::INFINITY,
+            2 => -0.0,
+            _ => (i as f32 - 100.0) / 3.0
+        };
+        heap.insert(FloatKey(key), i);
+    }
+    
+    let mut last = FloatKey(f32::NEG_INFINITY);
+    let mut nans = 0;
+    while let Some((key, _)) = heap.extract_min() {
+        assert!(last <= key);
+        nans += key.0.is_nan() as usize;
+        last = key;
+    }
+    assert_eq!(nans, 29);
+    assert!(last.0.is_nan());
+}
@@ -0,0 +1,61 @@
+use std::vec;
+use std::vec::Vec;
+
+use super::Rng;
+use FibonacciHeap;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn from_entries_extracts_in_the_order_of_the_sorted_input() {
+    for seed in 0..10 {
+        let mut rng = Rng::new(seed);
+        let mut entries: Vec<_> = (0..1000).map(|i| (rng.below(100), i)).collect();
+        let mut heap: FibonacciHeap<_, _> = FibonacciHeap::from_entries(entries.clone());
+        assert_eq!(heap.len(), 1000);
+        
+        let mut extracted = Vec::new();
+        while let Some(entry) = heap.extract_min() {
+            extracted.push(entry);
+        }
+        
+        // Equal keys may come out in any order, so only the keys are compared in order
+        entries.sort();
+        assert_eq!(extracted.iter().map(|&(key, _)| key).collect::<Vec<_>>(),
+                   entries.iter().map(|&(key, _)| key).collect::<Vec<_>>());
+        extracted.sort();
+        assert_eq!(extracted, entries);
+    }
+}
+
+#[test]
+fn from_entries_is_consolidated_already() {
+    let heap: FibonacciHeap<_, _> = FibonacciHeap::from((0..1024).map(|i| (i, i)).collect::<Vec<_>>());
+    
+    // A single binomial tree holds 1024 entries
+    assert_eq!(heap.num_roots(), 1);
+    assert_eq!(heap.minimum(), Some((&0, &0)));
+    assert_eq!(heap.stats().max_degree, 10);
+}
+
+#[test]
+fn a_value_given_more_than_once_keeps_its_last_priority() {
+    let entries = vec![(5, 'a'), (1, 'b'), (3, 'a'), (0, 'c'), (9, 'c'), (7, 'a')];
+    let mut heap: FibonacciHeap<_, _> = FibonacciHeap::from(entries);
+    
+    assert_eq!(heap.len(), 3);
+    assert_eq!(heap.find_max(), Some((&9, &'c')));
+    assert_eq!(heap.extract_min(), Some((1, 'b')));
+    assert_eq!(heap.extract_min(), Some((7, 'a')));
+    assert_eq!(heap.extract_min(), Some((9, 'c')));
+}
+
+#[test]
+fn from_entries_of_nothing_is_empty() {
+    let mut heap: FibonacciHeap<u64, u64> = FibonacciHeap::from_entries(Vec::new());
+    
+    assert!(heap.is_empty());
+    assert_eq!(heap.num_roots(), 0);
+    assert!(heap.extract_min().is_none());
+    heap.insert(1, 1);
+    assert_eq!(heap.minimum(), Some((&1, &1)));
+}
@@ -0,0 +1,57 @@
+use std::vec;
+use std::vec::Vec;
+
+use super::Rng;
+use FibonacciHeap;
+use MaxComparator;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn from_sorted_iter_builds_a_valid_heap() {
+    for &n in &[0, 1, 2, 3, 64, 1000, 1023] {
+        let heap: FibonacciHeap<_, _> = FibonacciHeap::from_sorted_iter((0..n).map(|i| (i / 3, i)));
+        
+        assert!(heap.check_invariants().is_ok());
+        assert_eq!(heap.len(), n as usize);
+        assert_eq!(heap.minimum().map(|(&key, _)| key), if n == 0 { None } else { Some(0) });
+        assert_eq!(heap.find_max().map(|(&key, _)| key), if n == 0 { None } else { Some((n - 1) / 3) });
+        assert_eq!(heap.into_sorted_vec().into_iter().map(|(_, value)| value).collect::<Vec<_>>(),
+                   (0..n).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn from_sorted_iter_follows_the_comparator() {
+    let heap: FibonacciHeap<_, _, MaxComparator> = FibonacciHeap::from_sorted_iter((0..100).rev().map(|i| (i, i)));
+    
+    assert!(heap.check_invariants().is_ok());
+    assert_eq!(heap.minimum(), Some((&99, &99)));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn from_sorted_iter_matches_collect() {
+    let mut rng = Rng::new(5);
+    let mut entries: Vec<_> = (0..2000).map(|i| (rng.below(300), i)).collect();
+    entries.sort();
+    
+    let mut sorted: FibonacciHeap<_, _> = FibonacciHeap::from_sorted_iter(entries.iter().cloned());
+    let mut collected: FibonacciHeap<_, _> = entries.iter().cloned().collect();
+    while let Some((key, _)) = sorted.extract_min() {
+        assert_eq!(collected.extract_min().map(|(key, _)| key), Some(key));
+        assert!(sorted.check_invariants().is_ok());
+    }
+    assert!(collected.is_empty());
+}
+
+#[test]
+#[should_panic(expected = "input of from_sorted_iter is not in ascending order, entry 3")]
+fn from_sorted_iter_rejects_unsorted_input() {
+    let _: FibonacciHeap<_, _> = FibonacciHeap::from_sorted_iter(vec![(1, 'a'), (2, 'b'), (2, 'c'), (1, 'd')]);
+}
+
+#[test]
+#[should_panic(expected = "value is in the input of from_sorted_iter more than once")]
+fn from_sorted_iter_rejects_repeated_values() {
+    let _: FibonacciHeap<_, _> = FibonacciHeap::from_sorted_iter(vec![(1, 'a'), (2, 'a')]);
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "petgraph")]
+
+use std::vec::Vec;
+
+use petgraph::algo;
+use petgraph::graph::{NodeIndex, UnGraph};
+use petgraph::Graph;
+
+use super::Rng;
+use dijkstra;
+
+// A graph of up to `nodes` nodes with about `degree` edges per node between random nodes, loops and parallel edges
+// included, with weights below `weight`
+fn random_graph(rng: &mut Rng, nodes: u64, degree: u64, weight: u64) -> Graph<(), u64> {
+    let mut graph = Graph::new();
+    let nodes = rng.below(nodes) + 1;
+    for _ in 0..nodes {
+        graph.add_node(());
+    }
+    for _ in 0..rng.below(nodes * degree + 1) {
+        let a = NodeIndex::new(rng.below(nodes) as usize);
+        let b = NodeIndex::new(rng.below(nodes) as usize);
+        graph.add_edge(a, b, rng.below(weight));
+    }
+    
+    graph
+}
+
+#[test]
+fn dijkstra_matches_petgraph_on_random_graphs() {
+    let seeds = if cfg!(miri) { 4 } else { 300 };
+    for seed in 0..seeds {
+        let mut rng = Rng::new(seed);
+        // Few distinct weights, so many paths tie, and zero weights as well
+        let weight = if seed % 2 == 0 { 4 } else { 1000 };
+        let degree = rng.below(6);
+        let graph = random_graph(&mut rng, 200, degree, weight);
+        let start = NodeIndex::new(rng.below(graph.node_count() as u64) as usize);
+        
+        let costs = dijkstra(&graph, start, None, |edge| *edge.weight());
+        let expected = algo::dijkstra(&graph, start, None, |edge| *edge.weight());
+        
+        assert_eq!(costs.len(), expected.len(), "seed {}", seed);
+        for (node, cost) in &expected {
+            assert_eq!(costs.get(node), Some(cost), "seed {}", seed);
+        }
+    }
+}
+
+#[test]
+fn dijkstra_finds_the_cost_of_the_goal_as_petgraph_does() {
+    let seeds = if cfg!(miri) { 4 } else { 300 };
+    for seed in 0..seeds {
+        let mut rng = Rng::new(seed);
+        let graph = random_graph(&mut rng, 200, 3, 100);
+        let start = NodeIndex::new(rng.below(graph.node_count() as u64) as usize);
+        let goal = NodeIndex::new(rng.below(graph.node_count() as u64) as usize);
+        
+        // Which other costs are final when the search stops depends on how ties are broken, the goal's does not
+        let costs = dijkstra(&graph, start, Some(goal), |edge| *edge.weight());
+        let expected = algo::dijkstra(&graph, start, Some(goal), |edge| *edge.weight());
+        
+        assert_eq!(costs.get(&goal), expected.get(&goal), "seed {}", seed);
+    }
+}
+
+#[test]
+fn dijkstra_matches_petgraph_on_undirected_graphs_with_float_costs() {
+    let seeds = if cfg!(miri) { 4 } else { 100 };
+    for seed in 0..seeds {
+        let mut rng = Rng::new(seed);
+        let mut graph = UnGraph::new_undirected();
+        let nodes: Vec<_> = (0..rng.below(100) + 1).map(|_| graph.add_node(())).collect();
+        for _ in 0..rng.below(400) {
+            let a = nodes[rng.below(nodes.len() as u64) as usize];
+            let b = nodes[rng.below(nodes.len() as u64) as usize];
+            graph.add_edge(a, b, rng.below(1 << 20) as f64 / 64.0);
+        }
+        
+        let costs = dijkstra(&graph, nodes[0], None, |edge| *edge.weight());
+        let expected = algo::dijkstra(&graph, nodes[0], None, |edge| *edge.weight());
+        
+        assert_eq!(costs.len(), expected.len(), "seed {}", seed);
+        for (node, cost) in &expected {
+            assert_eq!(costs.get(node), Some(cost), "seed {}", seed);
+        }
+    }
+}
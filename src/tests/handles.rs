@@ -0,0 +1,167 @@
+use std::vec;
+use std::vec::Vec;
+
+use super::Rng;
+use DecreaseKeyError;
+use FibonacciHeap;
+
+#[test]
+fn handle_is_stale_once_its_entry_is_removed() {
+    let mut heap = FibonacciHeap::new();
+    let a = heap.push_with_handle(1, "a");
+    let b = heap.push_with_handle(2, "b");
+    
+    assert_eq!(heap.extract_min(), Some((1, "a")));
+    assert_eq!(heap.get_handle(a), None);
+    assert_eq!(heap.delete_handle(a), None);
+    
+    // The new entry reuses the node of a, which must not make a valid again
+    let c = heap.push_with_handle(0, "c");
+    assert_eq!(heap.get_handle(a), None);
+    match heap.decrease_key_handle(a, -1) {
+        Err(DecreaseKeyError::NotFound(-1)) => {},
+        result => panic!("unexpected {:?}", result)
+    }
+    assert_eq!(heap.get_handle(b), Some((&2, &"b")));
+    assert_eq!(heap.get_handle(c), Some((&0, &"c")));
+}
+
+#[test]
+fn handle_is_stale_once_its_entry_is_replaced() {
+    let mut heap = FibonacciHeap::new();
+    let a = heap.push_with_handle(5, "a");
+    heap.push_with_handle(7, "b");
+    
+    // Replacing the minimum in place gives the node a new generation
+    assert_eq!(heap.pop_push(9, "c"), Some((5, "a")));
+    assert_eq!(heap.get_handle(a), None);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn handles_survive_consolidation_and_cuts() {
+    let mut rng = Rng::new(3);
+    let mut heap = FibonacciHeap::new();
+    let handles: Vec<_> = (0..512).map(|i| heap.push_with_handle(rng.below(1 << 20) as i64, i)).collect();
+    
+    // Links every node into trees, then cuts nodes out of them in random order
+    let mut removed = vec![false; handles.len()];
+    while let Some((_, value)) = heap.extract_min() {
+        removed[value] = true;
+        if heap.len() < 256 {
+            break;
+        }
+    }
+    assert!(heap.stats().max_degree > 1);
+    
+    for _ in 0..1000 {
+        let i = rng.below(handles.len() as u64) as usize;
+        if removed[i] {
+            assert_eq!(heap.get_handle(handles[i]), None);
+            continue;
+        }
+        
+        let key = *heap.get_handle(handles[i]).unwrap().0;
+        assert_eq!(heap.decrease_key_handle(handles[i], key - 1), Ok(key));
+        assert_eq!(heap.get_handle(handles[i]), Some((&(key - 1), &i)));
+        assert!(heap.check_invariants().is_ok());
+    }
+    
+    for (i, &handle) in handles.iter().enumerate() {
+        assert_eq!(heap.get_handle(handle).is_some(), !removed[i]);
+    }
+}
+
+#[test]
+fn handle_does_not_match_another_heap() {
+    let mut a = FibonacciHeap::new();
+    let mut b = FibonacciHeap::new();
+    let handle = a.push_with_handle(1, 'x');
+    b.push_with_handle(1, 'x');
+    
+    // Both entries are in the first node with the first generation of their heap
+    assert_eq!(b.get_handle(handle), None);
+    assert_eq!(b.delete_handle(handle), None);
+    
+    let copy = a.clone();
+    assert_eq!(copy.get_handle(handle), None);
+    assert_eq!(a.get_handle(handle), Some((&1, &'x')));
+}
+
+#[test]
+fn handles_of_the_appended_heap_are_stale() {
+    let mut a = FibonacciHeap::new();
+    let mut b = FibonacciHeap::new();
+    let kept = a.push_with_handle(1, 'a');
+    let moved = b.push_with_handle(2, 'b');
+    
+    a.append(&mut b);
+    assert_eq!(a.get_handle(kept), Some((&1, &'a')));
+    assert_eq!(a.get_handle(moved), None);
+    assert_eq!(b.get_handle(moved), None);
+    
+    let again = b.push_with_handle(3, 'c');
+    assert_eq!(b.get_handle(moved), None);
+    assert_eq!(b.get_handle(again), Some((&3, &'c')));
+}
+
+#[test]
+fn handles_are_stale_after_clear() {
+    let mut heap = FibonacciHeap::new();
+    let handles: Vec<_> = (0..10).map(|i| heap.push_with_handle(i, i)).collect();
+    heap.clear();
+    
+    for i in 0..10 {
+        heap.insert(i, i);
+    }
+    for handle in handles {
+        assert_eq!(heap.get_handle(handle), None);
+    }
+}
+
+#[test]
+fn union_keeps_the_handles_of_self_and_not_those_of_other() {
+    let mut a = FibonacciHeap::new();
+    let mut b = FibonacciHeap::new();
+    let kept = a.push_with_handle(1, 'a');
+    let replaced = a.push_with_handle(2, 'x');
+    let moved = b.push_with_handle(3, 'b');
+    let duplicate = b.push_with_handle(4, 'x');
+    
+    a.union(b);
+    assert_eq!(a.len(), 3);
+    assert_eq!(a.get_handle(kept), Some((&1, &'a')));
+    // The entry of self is deleted in favour of the one of other, so its handle goes stale with it
+    assert_eq!(a.get_handle(replaced), None);
+    assert_eq!(a.get_handle(moved), None);
+    assert_eq!(a.get_handle(duplicate), None);
+    
+    // Moved entries can be reached through new handles
+    let moved = a.handle(&'b').unwrap();
+    assert_eq!(a.decrease_key_handle(moved, 0), Ok(3));
+    assert_eq!(a.get_handle(moved), Some((&0, &'b')));
+    assert_eq!(a.get_handle(kept), Some((&1, &'a')));
+    assert_eq!(a.extract_min(), Some((0, 'b')));
+    assert_eq!(a.get_handle(moved), None);
+}
+
+#[test]
+fn union_with_an_empty_heap_keeps_every_handle() {
+    let mut a = FibonacciHeap::new();
+    let handles: Vec<_> = (0..10).map(|i| a.push_with_handle(i, i)).collect();
+    
+    a.union(FibonacciHeap::new());
+    for (i, &handle) in handles.iter().enumerate() {
+        assert_eq!(a.get_handle(handle), Some((&i, &i)));
+    }
+    
+    // Into an empty heap, the entries of other get handles of the heap they moved to
+    let mut b = FibonacciHeap::new();
+    b.union(a);
+    for (i, &handle) in handles.iter().enumerate() {
+        assert_eq!(b.get_handle(handle), None);
+        let new = b.handle(&i).unwrap();
+        assert_eq!(b.get_handle(new), Some((&i, &i)));
+    }
+    assert_eq!(b.check_invariants(), Ok(()));
+}
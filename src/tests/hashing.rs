@@ -0,0 +1,76 @@
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::vec::Vec;
+
+use super::Rng;
+use FibonacciHeap;
+
+type Shape = Vec<(u64, u64, usize, bool)>;
+
+// A value that hashes its id mixed with a salt, so heaps of values salted differently file them under unrelated
+// hashes, as heaps in two processes would. Equality only looks at the id
+struct Salted {
+    id: u64,
+    salt: u64
+}
+
+impl PartialEq for Salted {
+    fn eq(&self, other: &Salted) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Salted { }
+
+impl Hash for Salted {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (self.id ^ self.salt).hash(state);
+    }
+}
+
+// Runs the same operations on a heap of values salted from the hasher, returning every entry that came out and the
+// shape of the heap at the end
+fn run(hasher: &RandomState) -> (Vec<(u64, u64)>, Shape) {
+    let salt = hasher.hash_one(0_u64);
+    let value = |id| Salted { id, salt };
+    let mut rng = Rng::new(17);
+    let mut heap = FibonacciHeap::new();
+    let mut out = Vec::new();
+    
+    for _ in 0..3000 {
+        // Few distinct keys, so most extractions pick among ties
+        let id = rng.below(500);
+        match rng.below(5) {
+            0 | 1 => {
+                heap.insert(rng.below(20), value(id));
+            },
+            2 => {
+                let _ = heap.decrease_key(value(id), rng.below(10));
+            },
+            3 => {
+                if let Some(key) = heap.delete(&value(id)) {
+                    out.push((key, id));
+                }
+            },
+            _ => {
+                out.extend(heap.extract_min().map(|(key, value)| (key, value.id)));
+            }
+        }
+    }
+    
+    let mut shape = Vec::new();
+    heap.visit(|&key, value, info| shape.push((key, value.id, info.depth, info.marked)));
+    
+    (out, shape)
+}
+
+#[test]
+fn different_hasher_seeds_give_the_same_extractions_and_trees() {
+    let (first, second) = (RandomState::new(), RandomState::new());
+    assert_ne!(first.hash_one(0_u64), second.hash_one(0_u64));
+    
+    let (out, shape) = run(&first);
+    assert!(out.len() > 500);
+    assert!(shape.iter().any(|&(_, _, depth, _)| depth > 1));
+    assert_eq!(run(&second), (out, shape));
+}
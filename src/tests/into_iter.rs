@@ -0,0 +1,136 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use super::{Counted, Rng};
+use FibonacciHeap;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn into_iter_yields_the_sorted_input() {
+    for seed in 0..10 {
+        let mut rng = Rng::new(seed);
+        let mut entries: Vec<_> = (0..1000).map(|i| (rng.below(100), i)).collect();
+        let heap: FibonacciHeap<_, _> = entries.iter().cloned().collect();
+        
+        let mut iter = heap.into_iter();
+        assert_eq!(iter.len(), 1000);
+        iter.next();
+        assert_eq!(iter.size_hint(), (999, Some(999)));
+        
+        // Equal keys may come out in any order, so only the keys are compared in order
+        let mut drained: Vec<_> = iter.collect();
+        entries.sort();
+        assert!(drained.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert_eq!(drained.iter().map(|&(key, _)| key).collect::<Vec<_>>(),
+                   entries[1..].iter().map(|&(key, _)| key).collect::<Vec<_>>());
+        
+        drained.sort();
+        assert!(drained.iter().all(|entry| entries.binary_search(entry).is_ok()));
+    }
+}
+
+#[test]
+fn for_loop_over_the_heap() {
+    let mut heap = FibonacciHeap::new();
+    for &(key, value) in &[(3, 'c'), (1, 'a'), (2, 'b')] {
+        heap.insert(key, value);
+    }
+    
+    let mut values = Vec::new();
+    for (_, value) in heap {
+        values.push(value);
+    }
+    assert_eq!(values, ['a', 'b', 'c']);
+}
+
+#[test]
+fn dropping_a_partly_consumed_iterator_drops_every_value() {
+    let drops = Rc::new(Cell::new(0));
+    let mut heap = FibonacciHeap::new();
+    for i in 0..500 {
+        heap.insert(i % 37, Counted::new(i, &drops));
+    }
+    // Links the nodes into trees, so the rest are dropped from deep inside them
+    heap.extract_min();
+    assert_eq!(drops.get(), 1);
+    
+    let mut iter = heap.into_iter();
+    for _ in 0..100 {
+        iter.next();
+    }
+    assert_eq!(drops.get(), 101);
+    
+    drop(iter);
+    assert_eq!(drops.get(), 500);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn into_sorted_vec_matches_sorting_the_input() {
+    for seed in 0..10 {
+        let mut rng = Rng::new(seed);
+        let mut entries: Vec<_> = (0..1000).map(|i| (rng.below(100), i)).collect();
+        let mut heap: FibonacciHeap<_, _> = entries.iter().cloned().collect();
+        // Consolidates and cuts so the nodes are spread over trees of several shapes
+        heap.extract_min();
+        for _ in 0..50 {
+            let value = rng.below(1000);
+            if let Ok(old) = heap.decrease_key(value, 0) {
+                let i = entries.iter().position(|&entry| entry == (old, value)).unwrap();
+                entries[i].0 = 0;
+            }
+        }
+        entries.sort();
+        entries.remove(entries.iter().position(|entry| !heap.contains(&entry.1)).unwrap());
+        
+        let mut sorted = heap.into_sorted_vec();
+        assert!(sorted.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert_eq!(sorted.iter().map(|&(key, _)| key).collect::<Vec<_>>(),
+                   entries.iter().map(|&(key, _)| key).collect::<Vec<_>>());
+        sorted.sort();
+        assert_eq!(sorted, entries);
+    }
+}
+
+#[test]
+fn into_vec_and_into_values_move_every_entry_out() {
+    let drops = Rc::new(Cell::new(0));
+    let mut rng = Rng::new(46);
+    let build = |rng: &mut Rng| {
+        let mut heap = FibonacciHeap::new();
+        for i in 0..300 {
+            heap.insert(rng.below(100), Counted::new(i, &drops));
+        }
+        // Links the nodes into trees and cuts some out again
+        heap.extract_min();
+        for _ in 0..30 {
+            let _ = heap.decrease_key(Counted::new(rng.below(300), &drops), 0);
+        }
+        
+        let mut entries: Vec<_> = heap.iter().map(|(&key, value)| (key, value.id)).collect();
+        entries.sort();
+        (heap, entries)
+    };
+    
+    // Counted is not Clone, and no value is dropped on the way out
+    let (heap, expected) = build(&mut rng);
+    let dropped = drops.get();
+    let entries = heap.into_vec();
+    assert_eq!(drops.get(), dropped);
+    assert_eq!(entries.len(), expected.len());
+    let mut moved: Vec<_> = entries.iter().map(|(key, value)| (*key, value.id)).collect();
+    moved.sort();
+    assert_eq!(moved, expected);
+    
+    let (heap, expected) = build(&mut rng);
+    let dropped = drops.get();
+    let values = heap.into_values();
+    assert_eq!(drops.get(), dropped);
+    assert_eq!(values.len(), expected.len());
+    let mut ids: Vec<_> = values.iter().map(|value| value.id).collect();
+    let mut expected: Vec<_> = expected.into_iter().map(|(_, id)| id).collect();
+    ids.sort();
+    expected.sort();
+    assert_eq!(ids, expected);
+}
@@ -0,0 +1,144 @@
+use std::vec;
+use std::vec::Vec;
+
+use fibonacci_heap::Corruption;
+use {FibonacciHeap, HeapSnapshot, InvariantViolation, SnapshotNode};
+
+// Root 0 with children 1, 2 and 3, where 2 has the child 4, and root 5. Each node holds its index as key and value
+fn heap() -> FibonacciHeap<u64, u64> {
+    let node = |i: u64, children: Vec<usize>| SnapshotNode {
+        key: i,
+        value: i,
+        degree: children.len(),
+        marked: false,
+        children
+    };
+    let nodes = vec![node(0, vec![1, 2, 3]), node(1, vec![]), node(2, vec![4]), node(3, vec![]), node(4, vec![]),
+                     node(5, vec![])];
+    
+    FibonacciHeap::from_snapshot(HeapSnapshot { nodes, roots: vec![0, 5] }).unwrap()
+}
+
+// Each corruption with the message of the violation it causes once 10 is inserted with key 3
+fn corruptions() -> Vec<(Corruption<u64>, &'static str)> {
+    vec![
+        (Corruption::SelfLink(1), "the links of node 1 are broken"),
+        (Corruption::DetachChild(0), "node 0 has degree 3 but 2 linked children"),
+        (Corruption::SwapWithParent(4), "node 4 has a key that comes before the key of its parent 2"),
+        (Corruption::MarkRoot(5), "root 5 is marked"),
+        (Corruption::GrowSize, "the heap has size 8 but holds 7 values"),
+        (Corruption::DetachRoot(5), "node 5 is not reachable from the roots"),
+        (Corruption::SwapLookup(1, 3), "the value of node 1 is not found by the lookup"),
+        (Corruption::MoveMin(5), "the minimum is not a smallest root, root 0 is"),
+        (Corruption::MoveMax(1), "the maximum is not a largest node, node 5 is")
+    ]
+}
+
+#[cfg(feature = "debug-invariants")]
+#[test]
+fn corruption_is_caught_by_the_next_operation() {
+    use std::panic::{self, AssertUnwindSafe};
+    use std::string::String;
+    
+    for (corruption, message) in corruptions() {
+        let mut heap = heap();
+        // The maximum is only checked while it is known
+        heap.find_max();
+        heap.corrupt(corruption);
+        
+        let panic = panic::catch_unwind(AssertUnwindSafe(|| heap.insert(3, 10))).unwrap_err();
+        let panic = panic.downcast::<String>().unwrap();
+        assert_eq!(panic.strip_prefix("invariant violated: "), Some(message));
+    }
+}
+
+#[cfg(not(feature = "debug-invariants"))]
+#[test]
+fn corruption_goes_unnoticed_without_debug_invariants() {
+    for (corruption, _) in corruptions() {
+        let mut heap = heap();
+        heap.find_max();
+        heap.corrupt(corruption);
+        
+        heap.insert(3, 10);
+        assert!(heap.check_invariants().is_err());
+    }
+}
+
+// Checks the fixture after the corruption, with the maximum known so it is checked too
+fn violation(corruption: Corruption<u64>) -> Result<(), InvariantViolation> {
+    let mut heap = heap();
+    heap.find_max();
+    heap.corrupt(corruption);
+    heap.check_invariants()
+}
+
+#[test]
+fn intact_heaps_have_no_violation() {
+    let mut heap = heap();
+    assert_eq!(heap.check_invariants(), Ok(()));
+    heap.find_max();
+    assert_eq!(heap.check_invariants(), Ok(()));
+    
+    heap.extract_min();
+    heap.decrease_key(4, 0).unwrap();
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(FibonacciHeap::<u64, u64>::new().check_invariants(), Ok(()));
+}
+
+#[test]
+fn broken_link() {
+    assert_eq!(violation(Corruption::SelfLink(1)), Err(InvariantViolation::BrokenLink(Some(1))));
+}
+
+#[test]
+fn degree_mismatch() {
+    assert_eq!(violation(Corruption::DetachChild(0)),
+               Err(InvariantViolation::DegreeMismatch { node: 0, expected: 3, found: 2 }));
+}
+
+#[test]
+fn heap_order_violated() {
+    assert_eq!(violation(Corruption::SwapWithParent(4)),
+               Err(InvariantViolation::HeapOrderViolated { parent: 2, child: 4 }));
+}
+
+#[test]
+fn marked_root() {
+    assert_eq!(violation(Corruption::MarkRoot(5)), Err(InvariantViolation::MarkedRoot(5)));
+}
+
+#[test]
+fn size_mismatch() {
+    assert_eq!(violation(Corruption::GrowSize), Err(InvariantViolation::SizeMismatch { expected: 7, found: 6 }));
+}
+
+#[test]
+fn unreachable_entry() {
+    assert_eq!(violation(Corruption::DetachRoot(5)), Err(InvariantViolation::UnreachableEntry(5)));
+}
+
+#[test]
+fn lookup_mismatch() {
+    assert_eq!(violation(Corruption::SwapLookup(1, 3)), Err(InvariantViolation::LookupMismatch(1)));
+}
+
+#[test]
+fn min_not_minimal() {
+    assert_eq!(violation(Corruption::MoveMin(5)), Err(InvariantViolation::MinNotMinimal(Some(0))));
+}
+
+#[test]
+fn max_not_maximal() {
+    assert_eq!(violation(Corruption::MoveMax(1)), Err(InvariantViolation::MaxNotMaximal(Some(5))));
+}
+
+#[test]
+fn nodes_are_named_by_their_position_among_the_stored_nodes() {
+    let mut heap = heap();
+    heap.delete(&3);
+    heap.corrupt(Corruption::MarkRoot(5));
+    
+    // Node 5 comes fourth once node 3 is gone
+    assert_eq!(heap.check_invariants(), Err(InvariantViolation::MarkedRoot(4)));
+}
@@ -0,0 +1,94 @@
+use std::cell::Cell;
+use std::vec::Vec;
+
+use super::Rng;
+use FibonacciHeap;
+
+// Inserts, extractions and decreases, so the heap holds trees that were both linked and cut
+fn worked(seed: u64) -> FibonacciHeap<u64, u64> {
+    let mut rng = Rng::new(seed);
+    let mut heap = FibonacciHeap::new();
+    for _ in 0..3000 {
+        match rng.below(4) {
+            0 | 1 => heap.insert(rng.below(1000), rng.below(2000)),
+            2 => {
+                heap.extract_min();
+            },
+            _ => {
+                let _ = heap.decrease_key(rng.below(2000), rng.below(100));
+            }
+        }
+    }
+    assert!(heap.stats().max_degree > 1 && heap.stats().marked > 0);
+    
+    heap
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn keys_and_values_borrow_what_a_drain_would_yield() {
+    for seed in 0..5 {
+        let mut heap = worked(seed);
+        assert_eq!(heap.keys().count(), heap.len());
+        assert_eq!(heap.values().count(), heap.len());
+        let sum: u64 = heap.keys().sum();
+        let mut keys: Vec<_> = heap.keys().cloned().collect();
+        let mut values: Vec<_> = heap.values().cloned().collect();
+        
+        let mut drained: Vec<_> = heap.drain().collect();
+        drained.sort();
+        keys.sort();
+        values.sort();
+        assert_eq!(keys, drained.iter().map(|&(key, _)| key).collect::<Vec<_>>());
+        assert_eq!(sum, keys.iter().sum());
+        let mut drained_values: Vec<_> = drained.iter().map(|&(_, value)| value).collect();
+        drained_values.sort();
+        assert_eq!(values, drained_values);
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn iter_sorted_matches_into_sorted_vec_and_leaves_the_heap_alone() {
+    for seed in 0..5 {
+        let heap = worked(seed);
+        let shape = |heap: &FibonacciHeap<u64, u64>| {
+            let mut shape = Vec::new();
+            heap.visit(|&key, &value, info| shape.push((key, value, info.depth, info.degree, info.marked)));
+            shape
+        };
+        let before = shape(&heap);
+        
+        let mut sorted: Vec<_> = heap.iter_sorted().map(|(&key, &value)| (key, value)).collect();
+        let mut expected = heap.clone().into_sorted_vec();
+        assert_eq!(sorted.iter().map(|&(key, _)| key).collect::<Vec<_>>(),
+                   expected.iter().map(|&(key, _)| key).collect::<Vec<_>>());
+        // Equal keys may come out in either order
+        sorted.sort();
+        expected.sort();
+        assert_eq!(sorted, expected);
+        
+        assert_eq!(shape(&heap), before);
+        assert_eq!(heap.minimum().map(|(&key, _)| key), expected.first().map(|&(key, _)| key));
+    }
+}
+
+#[test]
+fn taking_the_first_entries_of_iter_sorted_is_cheap() {
+    let n = if cfg!(miri) || cfg!(feature = "debug-invariants") { 1000 } else { 100_000 };
+    let comparisons = Cell::new(0);
+    let mut heap = FibonacciHeap::new_by(|a: &u64, b: &u64| {
+        comparisons.set(comparisons.get() + 1);
+        a.cmp(b)
+    });
+    for i in 0..n {
+        heap.insert(i, i);
+    }
+    heap.extract_min();
+    
+    // The walk only looks at the roots and the children of what it yields, not at the whole heap
+    comparisons.set(0);
+    let first: Vec<_> = heap.iter_sorted().take(3).map(|(&key, _)| key).collect();
+    assert_eq!(first, [1, 2, 3]);
+    assert!(comparisons.get() < 200, "{} comparisons", comparisons.get());
+}
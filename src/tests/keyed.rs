@@ -0,0 +1,68 @@
+use std::vec;
+use std::rc::Rc;
+use std::vec::Vec;
+
+use {DecreaseKeyError, HeapError, KeyedFibonacciHeap};
+
+// An id that cannot be cloned, so the heap has to keep the only copy of it
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Id(u32);
+
+#[test]
+fn ids_and_payloads_need_not_be_clone() {
+    let mut heap = KeyedFibonacciHeap::new();
+    for i in 0..20 {
+        assert!(heap.insert_with_payload(i, Id(i), Vec::<u32>::new()).is_none());
+    }
+    heap.payload_mut(&Id(3)).unwrap().push(30);
+    
+    assert_eq!(heap.decrease_key(Id(7), 0), Ok(7));
+    assert_eq!(heap.decrease_key(Id(8), 9), Err(DecreaseKeyError::KeyGreaterThanCurrent(9)));
+    assert_eq!(heap.decrease_key(Id(50), 0), Err(DecreaseKeyError::NotFound(0)));
+    assert_eq!(heap.change_priority(&Id(0), 100), Ok(0));
+    assert_eq!(heap.change_priority(&Id(50), 1), Err(HeapError::NotFound));
+    
+    assert_eq!(heap.minimum(), Some((&0, &Id(7), &Vec::new())));
+    assert_eq!(heap.extract_min(), Some((0, Id(7), Vec::new())));
+    assert_eq!(heap.payload(&Id(3)), Some(&vec![30]));
+    assert_eq!(heap.delete(&Id(3)), Some((3, vec![30])));
+    assert_eq!(heap.delete(&Id(3)), None);
+    assert_eq!(heap.payload(&Id(3)), None);
+    assert!(heap.payload_mut(&Id(3)).is_none());
+    
+    assert_eq!(heap.len(), 18);
+    let ids: Vec<_> = (0..18).map(|_| heap.extract_min().unwrap().1 .0).collect();
+    assert_eq!(ids, [1, 2, 4, 5, 6, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 0]);
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn inserting_an_id_again_replaces_its_priority_and_payload() {
+    let mut heap = KeyedFibonacciHeap::new();
+    heap.insert_with_payload(5, "a", 'x');
+    heap.insert_with_payload(3, "b", 'y');
+    
+    assert_eq!(heap.insert_with_payload(1, "a", 'z'), Some('x'));
+    assert_eq!(heap.len(), 2);
+    assert_eq!(heap.minimum(), Some((&1, &"a", &'z')));
+    assert_eq!(heap.insert_with_payload(9, "a", 'w'), Some('z'));
+    assert_eq!(heap.get_priority(&"a"), Some(&9));
+    assert_eq!(heap.minimum(), Some((&3, &"b", &'y')));
+}
+
+#[test]
+fn every_payload_is_dropped_once() {
+    let payload = Rc::new(());
+    {
+        let mut heap = KeyedFibonacciHeap::new();
+        for i in 0..50 {
+            heap.insert_with_payload(i, i, payload.clone());
+        }
+        heap.insert_with_payload(0, 0, payload.clone());
+        heap.extract_min();
+        heap.delete(&10);
+        assert_eq!(Rc::strong_count(&payload), 49);
+    }
+    
+    assert_eq!(Rc::strong_count(&payload), 1);
+}
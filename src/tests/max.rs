@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use super::Rng;
+use FibonacciHeap;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn find_max_matches_a_map_under_random_operations() {
+    for seed in 0..20 {
+        let mut rng = Rng::new(seed);
+        let mut heap = FibonacciHeap::new();
+        let mut model = HashMap::new();
+        
+        for _ in 0..2000 {
+            let value = rng.below(48);
+            let key = rng.below(1000);
+            match rng.below(7) {
+                0 | 1 => {
+                    heap.insert(key, value);
+                    model.insert(value, key);
+                },
+                2 => {
+                    if let Some((key, value)) = heap.extract_min() {
+                        assert_eq!(model.remove(&value), Some(key));
+                    }
+                },
+                3 => {
+                    if let Some((key, value)) = heap.extract_max() {
+                        assert_eq!(model.remove(&value), Some(key));
+                    }
+                },
+                4 => {
+                    if heap.decrease_key(value, key).is_ok() {
+                        model.insert(value, key);
+                    }
+                },
+                5 => {
+                    if heap.increase_key(&value, key).is_ok() {
+                        model.insert(value, key);
+                    }
+                },
+                _ => assert_eq!(heap.delete(&value), model.remove(&value))
+            }
+            
+            assert!(heap.check_invariants().is_ok());
+            assert_eq!(heap.find_max().map(|(&key, _)| key), model.values().max().cloned());
+        }
+    }
+}
+
+#[test]
+fn max_is_found_again_after_removing_everything_else() {
+    let mut heap: FibonacciHeap<_, _> = (0..100).map(|i| (i, i)).collect();
+    
+    assert_eq!(heap.extract_max(), Some((99, 99)));
+    assert_eq!(heap.find_max(), Some((&98, &98)));
+    
+    while heap.len() > 1 {
+        heap.extract_min();
+    }
+    assert_eq!(heap.find_max(), Some((&98, &98)));
+    assert_eq!(heap.extract_min(), Some((98, 98)));
+    assert_eq!(heap.find_max(), None);
+}
+
+#[test]
+fn max_decreased_below_every_other_key() {
+    let mut heap = FibonacciHeap::new();
+    for i in 10..20 {
+        heap.insert(i, i);
+    }
+    heap.extract_min();
+    
+    heap.decrease_key(19, 0).unwrap();
+    assert_eq!(heap.find_max(), Some((&18, &18)));
+    assert_eq!(heap.minimum(), Some((&0, &19)));
+    
+    heap.decrease_key(18, 1).unwrap();
+    assert_eq!(heap.extract_max(), Some((17, 17)));
+    assert_eq!(heap.find_max(), Some((&16, &16)));
+}
+
+#[test]
+fn inserts_after_the_max_is_forgotten_are_not_missed() {
+    let mut heap = FibonacciHeap::new();
+    for i in 0..50 {
+        heap.insert(i, i);
+    }
+    
+    // The maximum is forgotten and only looked for once find_max is called
+    heap.delete(&49);
+    heap.insert(100, 100);
+    heap.insert(60, 60);
+    assert_eq!(heap.find_max(), Some((&100, &100)));
+    
+    heap.decrease_key(100, 0).unwrap();
+    heap.insert(70, 70);
+    assert_eq!(heap.find_max(), Some((&70, &70)));
+    
+    heap.increase_key(&5, 80).unwrap();
+    assert_eq!(heap.find_max(), Some((&80, &5)));
+}
+
+#[test]
+fn clone_and_append_keep_the_max() {
+    let mut a: FibonacciHeap<_, _> = (0..10).map(|i| (i, i)).collect();
+    let mut b: FibonacciHeap<_, _> = (10..20).map(|i| (i, i)).collect();
+    b.extract_max();
+    
+    let c = b.clone();
+    assert_eq!(c.find_max(), Some((&18, &18)));
+    
+    a.append(&mut b);
+    assert_eq!(a.find_max(), Some((&18, &18)));
+    assert_eq!(b.find_max(), None);
+}
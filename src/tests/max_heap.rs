@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use super::Rng;
+use FibonacciMaxHeap;
+use HeapError;
+use IncreaseKeyError;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn max_heap_matches_a_map_under_random_operations() {
+    for seed in 0..20 {
+        let mut rng = Rng::new(seed);
+        let mut heap = FibonacciMaxHeap::new();
+        let mut model = HashMap::new();
+        
+        for _ in 0..2000 {
+            let value = rng.below(48);
+            let key = rng.below(1000);
+            match rng.below(5) {
+                0 | 1 => {
+                    heap.insert(key, value);
+                    model.insert(value, key);
+                },
+                2 => {
+                    if let Some((key, value)) = heap.extract_max() {
+                        assert_eq!(Some(&key), model.values().max());
+                        assert_eq!(model.remove(&value), Some(key));
+                    }
+                },
+                3 => {
+                    match heap.increase_key(value, key) {
+                        Ok(old) => assert_eq!(model.insert(value, key), Some(old)),
+                        Err(IncreaseKeyError::NotFound(_)) => assert!(!model.contains_key(&value)),
+                        Err(IncreaseKeyError::KeyLessThanCurrent(_)) => assert!(key < model[&value])
+                    }
+                },
+                _ => {
+                    match heap.decrease_key(&value, key) {
+                        Ok(()) => assert!(model.insert(value, key).is_some()),
+                        Err(HeapError::NotFound) => assert!(!model.contains_key(&value)),
+                        Err(HeapError::KeyGreaterThanCurrent) => assert!(key > model[&value]),
+                        Err(error) => panic!("unexpected {:?}", error)
+                    }
+                }
+            }
+            
+            assert_eq!(heap.len(), model.len());
+            assert_eq!(heap.maximum().map(|(&key, _)| key), model.values().max().cloned());
+        }
+    }
+}
+
+#[test]
+fn increase_key_rejects_a_smaller_key() {
+    let mut heap = FibonacciMaxHeap::new();
+    heap.insert(5, "a");
+    
+    assert_eq!(heap.increase_key("a", 3), Err(IncreaseKeyError::KeyLessThanCurrent(3)));
+    assert_eq!(heap.increase_key("b", 3), Err(IncreaseKeyError::NotFound(3)));
+    assert_eq!(heap.increase_key("a", 9), Ok(5));
+    assert_eq!(heap.maximum(), Some((&9, &"a")));
+}
+
+#[test]
+fn decrease_key_rejects_a_larger_key() {
+    let mut heap = FibonacciMaxHeap::new();
+    heap.insert(5, "a");
+    heap.insert(4, "b");
+    
+    assert_eq!(heap.decrease_key(&"a", 7), Err(HeapError::KeyGreaterThanCurrent));
+    assert_eq!(heap.decrease_key(&"c", 1), Err(HeapError::NotFound));
+    assert_eq!(heap.decrease_key(&"a", 1), Ok(()));
+    assert_eq!(heap.extract_max(), Some((4, "b")));
+    assert_eq!(heap.extract_max(), Some((1, "a")));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn increase_key_cuts_and_cascades_in_deep_trees() {
+    let mut heap = FibonacciMaxHeap::new();
+    for i in 0..1024 {
+        heap.insert(i, i);
+    }
+    heap.extract_max();
+    
+    // Moves every other node above the whole heap, cutting it and then marked parents out of their trees
+    for (n, i) in (0..1023).step_by(2).enumerate() {
+        assert_eq!(heap.increase_key(i, 2000 + n), Ok(i));
+    }
+    
+    let mut last = usize::MAX;
+    while let Some((key, _)) = heap.extract_max() {
+        assert!(key <= last);
+        last = key;
+    }
+}
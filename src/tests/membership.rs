@@ -0,0 +1,179 @@
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::format;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+use std::string::ToString;
+use std::vec::Vec;
+
+use super::Rng;
+use DecreaseKeyError;
+use FibonacciHeap;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn contains_matches_a_set_under_random_operations() {
+    for seed in 0..20 {
+        let mut rng = Rng::new(seed);
+        let mut heap = FibonacciHeap::new();
+        let mut model = HashSet::new();
+        
+        for _ in 0..2000 {
+            let value = rng.below(64);
+            match rng.below(4) {
+                0 | 1 => {
+                    heap.insert(rng.below(1000), value);
+                    model.insert(value);
+                },
+                2 => {
+                    if let Some((_, value)) = heap.extract_min() {
+                        assert!(model.remove(&value));
+                    }
+                },
+                _ => {
+                    match heap.decrease_key(value, 0) {
+                        Ok(_) => assert!(model.contains(&value)),
+                        Err(DecreaseKeyError::NotFound(_)) => assert!(!model.contains(&value)),
+                        Err(error) => panic!("unexpected {:?}", error)
+                    }
+                }
+            }
+            
+            for value in 0..64 {
+                assert_eq!(heap.contains(&value), model.contains(&value));
+            }
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn contains_finds_values_deep_in_consolidated_trees() {
+    let mut heap = FibonacciHeap::new();
+    for i in 0..1024 {
+        heap.insert(i, i);
+    }
+    heap.extract_min();
+    
+    assert!(heap.stats().max_degree > 1);
+    assert!(!heap.contains(&0));
+    for i in 1..1024 {
+        assert!(heap.contains(&i));
+    }
+}
+
+#[test]
+fn contains_is_false_once_a_value_is_removed() {
+    let mut heap = FibonacciHeap::new();
+    heap.insert(1, "a");
+    heap.insert(2, "b");
+    heap.insert(3, "c");
+    
+    heap.extract_min();
+    heap.delete(&"c");
+    
+    assert!(!heap.contains(&"a"));
+    assert!(heap.contains(&"b"));
+    assert!(!heap.contains(&"c"));
+}
+
+#[test]
+fn values_are_found_by_a_borrowed_form() {
+    let mut heap = FibonacciHeap::new();
+    heap.insert(2, "two".to_string());
+    heap.insert(1, "one".to_string());
+    
+    assert!(heap.contains("one"));
+    assert_eq!(heap.get_priority("two"), Some(&2));
+    assert_eq!(heap.handle("one").and_then(|handle| heap.get_handle(handle)), Some((&1, &"one".to_string())));
+    assert_eq!(heap.change_priority("two", 0), Ok(2));
+    assert_eq!(heap.delete("one"), Some(1));
+    assert!(!heap.contains("one"));
+    
+    // A change that keeps the hash and equality of the value leaves it where it is found
+    heap.get_value_mut("two").unwrap().shrink_to_fit();
+    assert!(heap.get_value_mut("one").is_none());
+    assert_eq!(heap.extract_min(), Some((0, "two".to_string())));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn long_string_values_are_found_after_cuts_and_consolidations() {
+    let long = |i: u64| format!("{:0>100}", i);
+    let mut rng = Rng::new(19);
+    let mut heap = FibonacciHeap::new();
+    let mut model = HashMap::new();
+    let mut handles = Vec::new();
+    for i in 0..2000 {
+        handles.push(heap.push_with_handle(10_000 + i, long(i)));
+        model.insert(long(i), 10_000 + i);
+    }
+    
+    // Decreases cut nodes from their trees and extractions link the roots again, moving nodes around the lookup
+    for round in 0..3000 {
+        let handle = handles[rng.below(2000) as usize];
+        if let Some((&key, value)) = heap.get_handle(handle) {
+            let (value, key) = (value.clone(), key - 1 - rng.below(10));
+            heap.decrease_key_handle(handle, key).unwrap();
+            model.insert(value, key);
+        }
+        if round % 100 == 0 {
+            let (_, value) = heap.extract_min().unwrap();
+            model.remove(&value);
+        }
+    }
+    assert!(heap.stats().marked > 0);
+    
+    assert_eq!(heap.len(), model.len());
+    for i in 0..2000 {
+        let value = long(i);
+        assert_eq!(heap.get_priority(value.as_str()), model.get(&value));
+    }
+}
+
+// A value that counts how often it is hashed
+struct HashCounted {
+    id: u64,
+    hashes: Rc<Cell<usize>>
+}
+
+impl PartialEq for HashCounted {
+    fn eq(&self, other: &HashCounted) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for HashCounted { }
+
+impl Hash for HashCounted {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hashes.set(self.hashes.get() + 1);
+        self.id.hash(state);
+    }
+}
+
+#[test]
+fn moving_nodes_never_hashes_a_value_again() {
+    let hashes = Rc::new(Cell::new(0));
+    let mut rng = Rng::new(20);
+    let mut heap = FibonacciHeap::new();
+    let handles: Vec<_> = (0..1000).map(|id| {
+        heap.push_with_handle(10_000 + id, HashCounted { id, hashes: hashes.clone() })
+    }).collect();
+    // Checking the heap after every operation looks each value up, hashing it as well
+    if !cfg!(feature = "debug-invariants") {
+        assert_eq!(hashes.get(), 1000);
+    }
+    
+    // Cutting, linking, promoting children, growing and compacting the lookup all reuse the hash kept in the node
+    heap.extract_min();
+    for round in 0..1000 {
+        let _ = heap.decrease_key_handle(handles[rng.below(1000) as usize], 5000 - round);
+    }
+    heap.reserve(10_000);
+    heap.shrink_to_fit();
+    while heap.extract_min().is_some() { }
+    if !cfg!(feature = "debug-invariants") {
+        assert_eq!(hashes.get(), 1000);
+    }
+}
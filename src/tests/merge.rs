@@ -0,0 +1,90 @@
+use core::cmp::Ordering;
+use std::vec;
+use std::vec::Vec;
+
+use itertools::kmerge;
+
+use super::Rng;
+use kway_merge;
+
+// Sorted streams of small numbers, so items repeat within and across streams, of lengths from empty to about `longest`
+fn streams(rng: &mut Rng, count: u64, longest: u64) -> Vec<Vec<u64>> {
+    (0..count).map(|_| {
+        let len = rng.below(longest + 1) >> rng.below(8);
+        let mut stream: Vec<_> = (0..len).map(|_| rng.below(32)).collect();
+        stream.sort();
+        stream
+    }).collect()
+}
+
+#[test]
+fn kway_merge_matches_itertools_kmerge() {
+    let (seeds, longest) = if cfg!(miri) { (4, 16) } else { (200, 400) };
+    for seed in 0..seeds {
+        let mut rng = Rng::new(seed);
+        let count = rng.below(24);
+        let streams = streams(&mut rng, count, longest);
+        
+        let merged: Vec<_> = kway_merge(streams.iter().map(|stream| stream.iter().cloned()).collect()).collect();
+        let expected: Vec<_> = kmerge(streams.iter().map(|stream| stream.iter().cloned())).collect();
+        
+        assert_eq!(merged, expected, "seed {}", seed);
+    }
+}
+
+// An item that compares by its key only, and remembers the stream it came from
+#[derive(Debug, PartialEq, Eq)]
+struct Tagged {
+    key: u64,
+    stream: usize
+}
+
+impl PartialOrd for Tagged {
+    fn partial_cmp(&self, other: &Tagged) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Tagged {
+    fn cmp(&self, other: &Tagged) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+#[test]
+fn kway_merge_keeps_equal_items_in_stream_order() {
+    for seed in 0..20 {
+        let mut rng = Rng::new(seed);
+        let streams = streams(&mut rng, 16, 64);
+        let tagged = |index: usize, stream: &Vec<u64>| {
+            stream.iter().map(move |&key| Tagged { key, stream: index }).collect::<Vec<_>>()
+        };
+        
+        let merged: Vec<_> = kway_merge(streams.iter().enumerate().map(|(index, stream)| {
+            tagged(index, stream).into_iter()
+        }).collect()).collect();
+        
+        // The stable sort of the streams one after another puts equal keys in stream order as well
+        let mut expected: Vec<_> = streams.iter().enumerate().flat_map(|(index, stream)| tagged(index, stream)).collect();
+        expected.sort_by_key(|item| item.key);
+        
+        assert_eq!(merged, expected, "seed {}", seed);
+    }
+}
+
+#[test]
+fn kway_merge_handles_empty_and_lopsided_inputs() {
+    let none: Vec<vec::IntoIter<u64>> = Vec::new();
+    assert_eq!(kway_merge(none).count(), 0);
+    
+    let empty = vec![Vec::<u64>::new().into_iter(), Vec::new().into_iter()];
+    assert_eq!(kway_merge(empty).count(), 0);
+    
+    let lopsided = vec![(0..1000).collect::<Vec<u64>>().into_iter(), vec![500].into_iter(), Vec::new().into_iter()];
+    let merged = kway_merge(lopsided);
+    assert_eq!(merged.size_hint(), (1001, Some(1001)));
+    
+    let mut expected: Vec<u64> = (0..1000).collect();
+    expected.insert(501, 500);
+    assert_eq!(merged.collect::<Vec<_>>(), expected);
+}
@@ -0,0 +1,112 @@
+// Tests of the crate, one module per area. They drive the heap through its public API, reaching into the crate only
+// where a test has to break the heap on purpose. Tests that run millions of operations or build trees a million nodes
+// deep are skipped under Miri, which would take hours on them
+
+use std::cell::Cell;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+mod add_to_all_keys;
+mod append;
+mod arbitrary;
+mod auto_traits;
+mod bounded;
+mod bulk;
+mod capacity;
+mod comparators;
+mod compat;
+mod counters;
+mod debug;
+mod decrease_keys;
+mod deep;
+mod dot;
+mod drain;
+mod dump;
+mod duplicates;
+mod entry;
+mod errors;
+mod events;
+mod float_key;
+mod from_entries;
+mod from_sorted_iter;
+mod graph;
+mod handles;
+mod hashing;
+mod into_iter;
+mod invariants;
+mod iter;
+mod keyed;
+mod max;
+mod max_heap;
+mod membership;
+mod merge;
+mod monotone;
+mod parallel;
+mod peek;
+mod pop;
+mod priorities;
+mod roots;
+mod size;
+mod snapshot;
+mod sort;
+mod split_off;
+mod stable;
+mod stress;
+mod values;
+mod visit;
+
+// A value that counts how often values sharing its counter are dropped. Equality and hashing only look at the id
+pub struct Counted {
+    pub id: u64,
+    drops: Rc<Cell<usize>>
+}
+
+impl Counted {
+    pub fn new(id: u64, drops: &Rc<Cell<usize>>) -> Counted {
+        Counted {
+            id,
+            drops: drops.clone()
+        }
+    }
+}
+
+impl PartialEq for Counted {
+    fn eq(&self, other: &Counted) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for Counted { }
+
+impl Hash for Counted {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        self.drops.set(self.drops.get() + 1);
+    }
+}
+
+// A xorshift generator, so the randomized tests need no dependency and run the same operations every time
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+    
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+    
+    // A number in 0..n
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
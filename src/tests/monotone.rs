@@ -0,0 +1,111 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::string::String;
+use std::vec::Vec;
+
+use super::Rng;
+use FibonacciHeap;
+
+// A heap that extracted 5, holding 7 and 9
+fn extracted_five() -> FibonacciHeap<u64, u64> {
+    let mut heap: FibonacciHeap<_, _> = [(5, 5), (9, 9), (7, 7)].iter().cloned().collect();
+    heap.assert_monotone(true);
+    assert_eq!(heap.extract_min(), Some((5, 5)));
+    
+    heap
+}
+
+// Runs f on the heap, which has to panic, and returns the panic message
+fn panic_message<F>(heap: &mut FibonacciHeap<u64, u64>, f: F) -> String
+    where F: FnOnce(&mut FibonacciHeap<u64, u64>)
+{
+    let panic = panic::catch_unwind(AssertUnwindSafe(|| f(heap))).unwrap_err();
+    *panic.downcast::<String>().unwrap()
+}
+
+#[test]
+fn a_run_that_never_goes_below_the_last_extracted_key_passes() {
+    let mut rng = Rng::new(23);
+    let mut heap = FibonacciHeap::new();
+    heap.assert_monotone(true);
+    heap.insert(0, 0);
+    
+    // Like Dijkstra, every key pushed or decreased to is at least the key just extracted, and often equal to it
+    let mut extracted = Vec::new();
+    let mut next = 1;
+    while let Some((key, _)) = heap.extract_min() {
+        extracted.push(key);
+        for _ in 0..if next < 2000 { 3 } else { 0 } {
+            heap.insert(key + rng.below(3), next);
+            next += 1;
+        }
+        let value = rng.below(next);
+        if let Some(&current) = heap.get_priority(&value) {
+            let _ = heap.decrease_key(value, key + rng.below(current - key + 1));
+        }
+    }
+    
+    assert!(extracted.len() >= 2000);
+    assert!(extracted.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert!(extracted.windows(2).filter(|pair| pair[0] == pair[1]).count() > 100);
+}
+
+#[test]
+fn keys_equal_to_the_last_extracted_key_are_accepted() {
+    let mut heap = extracted_five();
+    
+    heap.insert(5, 1);
+    assert_eq!(heap.decrease_key(9, 5), Ok(9));
+    assert_eq!(heap.change_priority(&7, 5), Ok(7));
+    assert_eq!(heap.extract_min().map(|(key, _)| key), Some(5));
+    assert_eq!(heap.extract_min().map(|(key, _)| key), Some(5));
+    assert_eq!(heap.extract_min().map(|(key, _)| key), Some(5));
+    assert_eq!(heap.extract_min(), None);
+}
+
+#[test]
+fn pushing_below_the_last_extracted_key_panics() {
+    let mut heap = extracted_five();
+    
+    let message = panic_message(&mut heap, |heap| heap.insert(4, 4));
+    assert_eq!(message, "a key below the last extracted key was inserted");
+    let message = panic_message(&mut heap, |heap| heap.bulk_insert().insert(4, 4));
+    assert_eq!(message, "a key below the last extracted key was inserted");
+    
+    // The key is rejected before the heap changes
+    assert_eq!(heap.len(), 2);
+    assert!(!heap.contains(&4));
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn decreasing_below_the_last_extracted_key_panics() {
+    let mut heap = extracted_five();
+    
+    let message = panic_message(&mut heap, |heap| {
+        let _ = heap.decrease_key(9, 4);
+    });
+    assert_eq!(message, "a key was decreased below the last extracted key");
+    let message = panic_message(&mut heap, |heap| {
+        let _ = heap.change_priority(&7, 0);
+    });
+    assert_eq!(message, "a key was decreased below the last extracted key");
+    
+    assert_eq!(heap.get_priority(&9), Some(&9));
+    assert_eq!(heap.get_priority(&7), Some(&7));
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn turning_the_mode_off_accepts_any_key() {
+    let mut heap = extracted_five();
+    heap.assert_monotone(false);
+    
+    heap.insert(4, 4);
+    assert_eq!(heap.decrease_key(9, 0), Ok(9));
+    assert_eq!(heap.extract_min(), Some((0, 9)));
+    
+    // Turning it on again starts from no extracted key
+    heap.assert_monotone(true);
+    heap.insert(1, 1);
+    assert_eq!(heap.extract_min(), Some((1, 1)));
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "rayon")]
+
+use std::vec::Vec;
+
+use rayon::iter::{IntoParallelRefIterator, ParallelExtend, ParallelIterator};
+
+use FibonacciHeap;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn parallel_build_of_millions_matches_the_sequential_one() {
+    let n: u64 = if cfg!(feature = "debug-invariants") { 2000 } else { 2_000_000 };
+    // The keys are distinct so the order of extraction is fixed. Values repeat, and both builds keep the last priority
+    // of each
+    let entries: Vec<(u64, u64)> = (0..n).map(|i| (i.wrapping_mul(0x9e37_79b9_7f4a_7c15), i % (n / 2 + 7))).collect();
+    
+    let mut parallel: FibonacciHeap<_, _> = entries.par_iter().cloned().collect();
+    let mut sequential: FibonacciHeap<_, _> = entries.iter().cloned().collect();
+    assert_eq!(parallel.check_invariants(), Ok(()));
+    assert_eq!(parallel.len(), sequential.len());
+    assert_eq!(parallel.minimum(), sequential.minimum());
+    
+    // The trees differ, but the same entries come out in the same order
+    for _ in 0..1000 {
+        assert_eq!(parallel.extract_min(), sequential.extract_min());
+    }
+    assert_eq!(parallel.check_invariants(), Ok(()));
+    assert_eq!(parallel.into_sorted_vec(), sequential.into_sorted_vec());
+}
+
+#[test]
+fn par_extend_adds_to_the_entries_already_there() {
+    let mut heap: FibonacciHeap<_, _> = (0..10).map(|i| (i, i)).collect();
+    let more: Vec<(u64, u64)> = (5..1000).map(|i| (1000 - i, i)).collect();
+    heap.par_extend(more.par_iter().cloned());
+    
+    assert_eq!(heap.len(), 1000);
+    assert_eq!(heap.get_priority(&5), Some(&995));
+    assert_eq!(heap.get_priority(&4), Some(&4));
+    assert_eq!(heap.extract_min(), Some((0, 0)));
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
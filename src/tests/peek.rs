@@ -0,0 +1,117 @@
+use std::vec;
+use std::vec::Vec;
+
+use {FibonacciHeap, PeekMut};
+
+#[test]
+fn smallest_n_returns_the_smallest_in_order_without_changing_the_heap() {
+    let mut heap: FibonacciHeap<_, _> = (0..100).map(|i| ((i * 37) % 100, i)).collect();
+    heap.extract_min();
+    heap.decrease_key(50, -1).unwrap();
+    let roots = heap.num_roots();
+    
+    let keys: Vec<_> = heap.smallest_n(4).into_iter().map(|(&key, _)| key).collect();
+    assert_eq!(keys, [-1, 1, 2, 3]);
+    assert_eq!(heap.len(), 99);
+    assert_eq!(heap.num_roots(), roots);
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn smallest_n_at_and_past_the_bounds() {
+    let heap: FibonacciHeap<_, _> = vec![(3, 'c'), (1, 'a'), (2, 'b')].into_iter().collect();
+    
+    assert!(heap.smallest_n(0).is_empty());
+    assert_eq!(heap.smallest_n(3), [(&1, &'a'), (&2, &'b'), (&3, &'c')]);
+    assert_eq!(heap.smallest_n(10), heap.smallest_n(3));
+    
+    let empty: FibonacciHeap<i32, char> = FibonacciHeap::new();
+    assert!(empty.smallest_n(0).is_empty());
+    assert!(empty.smallest_n(5).is_empty());
+}
+
+// A consolidated heap of the keys 1 to 99, each its own value
+fn consolidated() -> FibonacciHeap<i32, i32> {
+    let mut heap: FibonacciHeap<_, _> = (0..100).map(|i| (i, i)).collect();
+    heap.extract_min();
+    heap
+}
+
+#[test]
+fn raising_the_key_through_peek_mut_restores_the_order_on_drop() {
+    let mut heap = consolidated();
+    assert_eq!(heap.find_max(), Some((&99, &99)));
+    
+    {
+        let mut min = heap.peek_mut().unwrap();
+        assert_eq!(*min, 1);
+        assert_eq!(min.value(), &1);
+        *min = 50;
+    }
+    
+    assert_eq!(heap.minimum(), Some((&2, &2)));
+    assert_eq!(heap.get_priority(&1), Some(&50));
+    assert_eq!(heap.check_invariants(), Ok(()));
+    
+    // Raised past every other key, it becomes the maximum
+    *heap.peek_mut().unwrap() = 1000;
+    assert_eq!(heap.minimum(), Some((&3, &3)));
+    assert_eq!(heap.find_max(), Some((&1000, &2)));
+    assert_eq!(heap.check_invariants(), Ok(()));
+    
+    let keys: Vec<_> = heap.into_iter().map(|(key, _)| key).collect();
+    let mut expected: Vec<_> = (3..100).chain(vec![50, 1000]).collect();
+    expected.sort();
+    assert_eq!(keys, expected);
+}
+
+#[test]
+fn lowering_the_key_through_peek_mut_keeps_it_the_minimum() {
+    let mut heap = consolidated();
+    
+    *heap.peek_mut().unwrap() -= 10;
+    
+    assert_eq!(heap.minimum(), Some((&-9, &1)));
+    assert_eq!(heap.len(), 99);
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(heap.extract_min(), Some((-9, 1)));
+    assert_eq!(heap.extract_min(), Some((2, 2)));
+}
+
+#[test]
+fn peek_mut_without_a_change_leaves_the_heap_alone() {
+    let mut heap = consolidated();
+    let roots = heap.num_roots();
+    
+    assert_eq!(heap.peek_mut().map(|min| *min), Some(1));
+    assert_eq!(heap.num_roots(), roots);
+    
+    let mut empty: FibonacciHeap<i32, i32> = FibonacciHeap::new();
+    assert!(empty.peek_mut().is_none());
+}
+
+#[test]
+fn peek_mut_pop_removes_the_entry_with_its_changed_key() {
+    let mut heap = consolidated();
+    
+    let min = heap.peek_mut().unwrap();
+    assert_eq!(PeekMut::pop(min), (1, 1));
+    assert_eq!(heap.minimum(), Some((&2, &2)));
+    assert_eq!(heap.len(), 98);
+    
+    // Raised above the known maximum and then popped, the entry must not be left behind as the maximum
+    assert_eq!(heap.find_max(), Some((&99, &99)));
+    let mut min = heap.peek_mut().unwrap();
+    *min = 500;
+    assert_eq!(PeekMut::pop(min), (500, 2));
+    assert!(!heap.contains(&2));
+    assert_eq!(heap.find_max(), Some((&99, &99)));
+    assert_eq!(heap.minimum(), Some((&3, &3)));
+    assert_eq!(heap.check_invariants(), Ok(()));
+    
+    let mut single = FibonacciHeap::new();
+    single.insert(7, 'a');
+    assert_eq!(PeekMut::pop(single.peek_mut().unwrap()), (7, 'a'));
+    assert!(single.is_empty());
+    assert!(single.minimum().is_none());
+}
@@ -0,0 +1,214 @@
+use std::panic::{self, AssertUnwindSafe};
+use std::vec;
+use std::vec::Vec;
+
+use FibonacciHeap;
+
+#[test]
+fn push_pop_on_an_empty_heap_hands_the_entry_back() {
+    let mut heap: FibonacciHeap<i32, &str> = FibonacciHeap::new();
+    
+    assert_eq!(heap.push_pop(3, "a"), (3, "a"));
+    assert!(heap.is_empty());
+    assert!(heap.minimum().is_none());
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn push_pop_of_a_larger_key_extracts_the_minimum() {
+    let mut heap: FibonacciHeap<_, _> = vec![(1, "a"), (5, "b")].into_iter().collect();
+    
+    assert_eq!(heap.push_pop(3, "c"), (1, "a"));
+    assert_eq!(heap.len(), 2);
+    assert_eq!(heap.minimum(), Some((&3, &"c")));
+    assert_eq!(heap.check_invariants(), Ok(()));
+    
+    // A key equal to the minimum is not larger, so it is handed back
+    assert_eq!(heap.push_pop(3, "d"), (3, "d"));
+    assert_eq!(heap.push_pop(2, "e"), (2, "e"));
+    assert_eq!(heap.len(), 2);
+    assert!(!heap.contains(&"d"));
+    
+    let rest: Vec<_> = heap.into_iter().collect();
+    assert_eq!(rest, [(3, "c"), (5, "b")]);
+}
+
+#[test]
+fn push_pop_of_a_value_in_the_heap_replaces_it_first() {
+    let mut heap: FibonacciHeap<_, _> = vec![(1, "a"), (5, "b")].into_iter().collect();
+    
+    assert_eq!(heap.push_pop(0, "b"), (0, "b"));
+    assert_eq!(heap.len(), 1);
+    assert_eq!(heap.push_pop(7, "a"), (7, "a"));
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn replace_min_on_an_empty_heap_inserts() {
+    let mut heap = FibonacciHeap::new();
+    
+    assert_eq!(heap.replace_min(4, "a"), None);
+    assert_eq!(heap.len(), 1);
+    assert_eq!(heap.minimum(), Some((&4, &"a")));
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn replace_min_with_a_smaller_key_makes_it_the_minimum() {
+    let mut heap: FibonacciHeap<_, _> = (1..20).map(|i| (i * 10, i)).collect();
+    heap.extract_min();
+    
+    assert_eq!(heap.replace_min(5, 100), Some((20, 2)));
+    assert_eq!(heap.len(), 18);
+    assert_eq!(heap.minimum(), Some((&5, &100)));
+    assert!(!heap.contains(&2));
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn replace_min_with_a_larger_key_ranks_it_below_the_minimum() {
+    let mut heap: FibonacciHeap<_, _> = (1..20).map(|i| (i * 10, i)).collect();
+    heap.extract_min();
+    
+    // The new key is larger than the next minimum, so the entry does not become the minimum
+    assert_eq!(heap.replace_min(45, 100), Some((20, 2)));
+    assert_eq!(heap.len(), 18);
+    assert_eq!(heap.minimum(), Some((&30, &3)));
+    assert_eq!(heap.check_invariants(), Ok(()));
+    
+    let keys: Vec<_> = heap.into_iter().map(|(key, _)| key).take(4).collect();
+    assert_eq!(keys, [30, 40, 45, 50]);
+}
+
+#[test]
+fn drain_below_stops_before_the_bound() {
+    let mut heap: FibonacciHeap<_, _> = vec![(3, 'c'), (1, 'a'), (2, 'b'), (2, 'x'), (4, 'd')].into_iter().collect();
+    
+    let keys: Vec<_> = heap.drain_below(&2).map(|(key, _)| key).collect();
+    assert_eq!(keys, [1]);
+    assert_eq!(heap.len(), 4);
+    assert_eq!(heap.minimum().map(|(&key, _)| key), Some(2));
+    
+    let keys: Vec<_> = heap.drain_below(&4).map(|(key, _)| key).collect();
+    assert_eq!(keys, [2, 2, 3]);
+    assert_eq!(heap.minimum(), Some((&4, &'d')));
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn drain_at_or_below_includes_the_bound() {
+    let mut heap: FibonacciHeap<_, _> = vec![(3, 'c'), (1, 'a'), (2, 'b'), (2, 'x'), (4, 'd')].into_iter().collect();
+    
+    let keys: Vec<_> = heap.drain_at_or_below(&2).map(|(key, _)| key).collect();
+    assert_eq!(keys, [1, 2, 2]);
+    assert_eq!(heap.minimum(), Some((&3, &'c')));
+    
+    // A bound below the minimum drains nothing, one above every key drains everything
+    assert_eq!(heap.drain_at_or_below(&0).count(), 0);
+    assert_eq!(heap.len(), 2);
+    assert_eq!(heap.drain_at_or_below(&10).count(), 2);
+    assert!(heap.is_empty());
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn draining_up_to_a_bound_leaves_an_empty_heap_alone() {
+    let mut heap: FibonacciHeap<i32, char> = FibonacciHeap::new();
+    
+    assert_eq!(heap.drain_below(&10).count(), 0);
+    assert_eq!(heap.drain_at_or_below(&10).count(), 0);
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn dropping_a_drain_up_to_a_bound_keeps_the_rest() {
+    let mut heap: FibonacciHeap<_, _> = (0..10).map(|i| (i, i)).collect();
+    
+    assert_eq!(heap.drain_below(&8).take(3).collect::<Vec<_>>(), [(0, 0), (1, 1), (2, 2)]);
+    assert_eq!(heap.len(), 7);
+    assert_eq!(heap.minimum(), Some((&3, &3)));
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn pop_if_extracts_only_when_the_predicate_holds() {
+    let mut heap: FibonacciHeap<_, _> = vec![(2, 'b'), (1, 'a')].into_iter().collect();
+    
+    assert_eq!(heap.pop_if(|&key, _| key > 1), None);
+    assert_eq!(heap.pop_if(|_, &value| value == 'a'), Some((1, 'a')));
+    assert_eq!(heap.len(), 1);
+    
+    heap.clear();
+    assert_eq!(heap.pop_if(|_, _| panic!("called on an empty heap")), None);
+}
+
+#[test]
+fn pop_if_leaves_the_heap_unchanged_when_the_predicate_panics() {
+    let mut heap: FibonacciHeap<_, _> = (0..50).map(|i| (i, i)).collect();
+    heap.extract_min();
+    
+    let result = panic::catch_unwind(AssertUnwindSafe(|| heap.pop_if(|_, _| panic!("predicate"))));
+    
+    assert!(result.is_err());
+    assert_eq!(heap.len(), 49);
+    assert_eq!(heap.peek(), Some((&1, &1)));
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(heap.pop_if(|_, _| true), Some((1, 1)));
+}
+
+#[test]
+fn pop_batch_of_nothing_leaves_the_heap_alone() {
+    let mut heap: FibonacciHeap<_, _> = (0..10).map(|i| (i, i)).collect();
+    let roots = heap.num_roots();
+    
+    assert!(heap.pop_batch(0).is_empty());
+    assert_eq!(heap.len(), 10);
+    assert_eq!(heap.num_roots(), roots);
+    
+    let mut empty: FibonacciHeap<i32, i32> = FibonacciHeap::new();
+    assert!(empty.pop_batch(0).is_empty());
+    assert!(empty.pop_batch(5).is_empty());
+}
+
+#[test]
+fn pop_batch_of_more_than_the_length_extracts_everything() {
+    let mut heap: FibonacciHeap<_, _> = (0..10).rev().map(|i| (i, i)).collect();
+    heap.extract_min();
+    
+    let batch = heap.pop_batch(100);
+    assert_eq!(batch, (1..10).map(|i| (i, i)).collect::<Vec<_>>());
+    assert!(heap.is_empty());
+    assert!(heap.minimum().is_none());
+    assert_eq!(heap.check_invariants(), Ok(()));
+    
+    // Exactly the length is the same as more
+    heap.extend((0..5).map(|i| (i, i)));
+    assert_eq!(heap.pop_batch(5).len(), 5);
+    assert!(heap.is_empty());
+}
+
+#[test]
+fn pop_all_min_extracts_every_entry_with_the_minimum_key() {
+    let mut heap: FibonacciHeap<_, _> = (0..40).map(|i| (i / 8, i)).collect();
+    // Consolidates, so some of the minimum keys end up below others
+    heap.extract_min();
+    heap.insert(0, 100);
+    
+    let mut min = heap.pop_all_min();
+    min.sort();
+    assert_eq!(min, (1..8).chain(Some(100)).map(|i| (0, i)).collect::<Vec<_>>());
+    assert_eq!(heap.len(), 32);
+    assert_eq!(heap.minimum().map(|(&key, _)| key), Some(1));
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn pop_all_min_with_a_single_or_no_minimum() {
+    let mut heap: FibonacciHeap<_, _> = vec![(1, 'a'), (2, 'b'), (2, 'c')].into_iter().collect();
+    
+    assert_eq!(heap.pop_all_min(), [(1, 'a')]);
+    assert_eq!(heap.pop_all_min().len(), 2);
+    assert!(heap.is_empty());
+    assert!(heap.pop_all_min().is_empty());
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
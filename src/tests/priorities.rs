@@ -0,0 +1,137 @@
+use core::cmp;
+use std::panic::{self, AssertUnwindSafe};
+use std::vec::Vec;
+
+use super::Rng;
+use {FibonacciHeap, HeapError};
+
+#[test]
+fn update_key_with_moves_the_value_either_way() {
+    let mut heap: FibonacciHeap<_, _> = (0..32).map(|i| (i * 2, i)).collect();
+    heap.extract_min();
+    
+    assert_eq!(heap.update_key_with(&20, |&key| key - 100), Ok(()));
+    assert_eq!(heap.minimum(), Some((&-60, &20)));
+    assert_eq!(heap.update_key_with(&20, |&key| key + 200), Ok(()));
+    assert_eq!(heap.minimum(), Some((&2, &1)));
+    assert_eq!(heap.get_priority(&20), Some(&140));
+    
+    // An equal key leaves everything in place
+    assert_eq!(heap.update_key_with(&1, |&key| key), Ok(()));
+    assert_eq!(heap.minimum(), Some((&2, &1)));
+    
+    assert_eq!(heap.len(), 31);
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn update_key_with_reports_a_missing_value_without_calling_the_closure() {
+    let mut heap = FibonacciHeap::new();
+    assert_eq!(heap.update_key_with(&1, |_| panic!("value is missing")), Err(HeapError::NotFound));
+    
+    heap.insert(1, 1);
+    heap.extract_min();
+    assert_eq!(heap.update_key_with(&1, |_| panic!("value is missing")), Err(HeapError::NotFound));
+}
+
+#[test]
+fn update_key_with_leaves_the_heap_as_it_was_when_the_closure_panics() {
+    let mut heap: FibonacciHeap<_, _> = (0..32).map(|i| (i, i)).collect();
+    heap.extract_min();
+    
+    let result = panic::catch_unwind(AssertUnwindSafe(|| heap.update_key_with(&1, |_| panic!("closure"))));
+    
+    assert!(result.is_err());
+    assert_eq!(heap.check_invariants(), Ok(()));
+    let keys: Vec<_> = heap.into_iter().map(|(key, _)| key).collect();
+    assert_eq!(keys, (1..32).collect::<Vec<_>>());
+}
+
+#[test]
+fn map_priorities_matches_a_heap_built_with_the_new_keys() {
+    let mut rng = Rng::new(5);
+    let mut heap = FibonacciHeap::new();
+    for value in 0..200u64 {
+        heap.insert(rng.below(1000) as i64, value);
+    }
+    // Leaves trees, marks and decreased keys behind, so the remap starts from a consolidated heap
+    for _ in 0..50 {
+        heap.extract_min();
+    }
+    for value in (100..200).step_by(3) {
+        let _ = heap.decrease_key(value, -(value as i64));
+    }
+    
+    let remap = |key: &i64, value: &u64| (key * 7 + *value as i64) % 101;
+    let mut expected = FibonacciHeap::new();
+    for (key, value) in heap.iter() {
+        expected.insert(remap(key, value), *value);
+    }
+    
+    let len = heap.len();
+    heap.map_priorities(remap);
+    assert_eq!(heap.len(), len);
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert!(heap == expected);
+    
+    // Extracting one entry and decreasing the key of another each step, as Dijkstra does
+    while let Some((key, _)) = heap.extract_min() {
+        assert_eq!(expected.extract_min().map(|(key, _)| key), Some(key));
+        let next = heap.iter().next().map(|(&key, &value)| (key, value));
+        if let Some((next, value)) = next {
+            let lower = cmp::max(key, next - 5);
+            assert_eq!(heap.decrease_key(value, lower).is_ok(), expected.decrease_key(value, lower).is_ok());
+        }
+    }
+    assert!(expected.is_empty());
+}
+
+#[test]
+fn map_priorities_that_reverses_every_key() {
+    let mut heap: FibonacciHeap<_, _> = (0..100).map(|i| (i, i)).collect();
+    heap.extract_min();
+    
+    heap.map_priorities(|&key, _| -key);
+    
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(heap.len(), 99);
+    assert_eq!(heap.minimum(), Some((&-99, &99)));
+    let values: Vec<_> = heap.into_iter().map(|(_, value)| value).collect();
+    assert_eq!(values, (1..100).rev().collect::<Vec<_>>());
+}
+
+#[test]
+fn map_priorities_of_an_empty_heap_does_nothing() {
+    let mut heap: FibonacciHeap<i32, i32> = FibonacciHeap::new();
+    
+    heap.map_priorities(|_, _| panic!("no entries to map"));
+    
+    assert!(heap.is_empty());
+    assert!(heap.minimum().is_none());
+    assert_eq!(heap.check_invariants(), Ok(()));
+}
+
+#[test]
+fn map_priorities_leaves_a_valid_heap_when_the_closure_panics() {
+    let mut heap: FibonacciHeap<_, _> = (0..100).map(|i| (i, i)).collect();
+    heap.extract_min();
+    
+    let mut calls = 0;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        heap.map_priorities(|&key, _| {
+            calls += 1;
+            if calls == 50 {
+                panic!("closure");
+            }
+            key + 1000
+        })
+    }));
+    
+    assert!(result.is_err());
+    assert_eq!(heap.len(), 99);
+    assert_eq!(heap.check_invariants(), Ok(()));
+    // Half the keys were mapped before the panic, so the minimum is one of those that were not
+    let mapped = heap.iter().filter(|&(&key, _)| key >= 1000).count();
+    assert_eq!(mapped, 49);
+    assert!(*heap.minimum().unwrap().0 < 1000);
+}
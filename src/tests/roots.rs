@@ -0,0 +1,59 @@
+use std::collections::HashSet;
+use std::vec::Vec;
+
+use super::Rng;
+use FibonacciHeap;
+
+#[test]
+fn every_entry_is_a_root_after_plain_inserts() {
+    let mut heap = FibonacciHeap::new();
+    for i in 0..100 {
+        heap.insert(i, i);
+        assert_eq!(heap.num_roots(), heap.len());
+    }
+    
+    let mut roots: Vec<_> = heap.roots().map(|(&key, &value)| (key, value)).collect();
+    roots.sort();
+    assert_eq!(roots, (0..100).map(|i| (i, i)).collect::<Vec<_>>());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn an_extraction_leaves_at_most_one_root_per_degree() {
+    let n = if cfg!(feature = "debug-invariants") { 1000 } else { 10_000 };
+    for seed in 0..10 {
+        let mut rng = Rng::new(seed);
+        let mut heap = FibonacciHeap::new();
+        for i in 0..n {
+            heap.insert(rng.below(1_000_000), i);
+        }
+        heap.extract_min();
+        
+        // The degree of a node in a heap of n entries is at most log_phi(n), below 1.45 log2(n)
+        let log2 = 64 - heap.len().leading_zeros() as usize;
+        assert!(heap.num_roots() <= log2 * 3 / 2, "{} roots", heap.num_roots());
+        assert_eq!(heap.roots().count(), heap.num_roots());
+        
+        let mut degrees = HashSet::new();
+        heap.visit(|_, _, info| {
+            if info.depth == 0 {
+                assert!(degrees.insert(info.degree), "two roots of degree {}", info.degree);
+            }
+        });
+        assert_eq!(degrees.len(), heap.num_roots());
+        
+        // The roots are the entries without a parent, and the minimum is one of them
+        let mut parentless = Vec::new();
+        heap.visit(|&key, &value, info| {
+            if info.parent_key.is_none() {
+                parentless.push((key, value));
+            }
+        });
+        let mut roots: Vec<_> = heap.roots().map(|(&key, &value)| (key, value)).collect();
+        roots.sort();
+        parentless.sort();
+        assert_eq!(roots, parentless);
+        let (&key, &value) = heap.minimum().unwrap();
+        assert!(roots.contains(&(key, value)));
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use super::Rng;
+use FibonacciHeap;
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn len_matches_a_map_under_random_operations() {
+    for seed in 0..20 {
+        let mut rng = Rng::new(seed);
+        let mut heap = FibonacciHeap::new();
+        let mut model = HashMap::new();
+        
+        for _ in 0..2000 {
+            let value = rng.below(48);
+            let key = rng.below(1000);
+            match rng.below(8) {
+                0 | 1 => {
+                    // Also inserts values that are already in the heap
+                    heap.insert(key, value);
+                    model.insert(value, key);
+                },
+                2 => {
+                    if let Some((key, value)) = heap.extract_min() {
+                        assert_eq!(model.remove(&value), Some(key));
+                    }
+                },
+                3 => {
+                    // Fails for a missing value or a larger key, neither of which may change the size
+                    if heap.decrease_key(value, key).is_ok() {
+                        model.insert(value, key);
+                    }
+                },
+                4 => {
+                    if heap.increase_key(&value, key).is_ok() {
+                        model.insert(value, key);
+                    }
+                },
+                5 => assert_eq!(heap.delete(&value), model.remove(&value)),
+                6 => {
+                    for (key, value) in heap.pop_batch(rng.below(4) as usize) {
+                        assert_eq!(model.remove(&value), Some(key));
+                    }
+                },
+                _ => {
+                    let bound = rng.below(1000);
+                    heap.retain(|&key, _| key < bound);
+                    model.retain(|_, &mut key| key < bound);
+                }
+            }
+            
+            assert_eq!(heap.len(), model.len());
+            assert_eq!(heap.is_empty(), model.is_empty());
+            assert_eq!(heap.is_empty(), heap.minimum().is_none());
+            assert_eq!(heap.iter().count(), model.len());
+        }
+    }
+}
+
+#[test]
+fn default_heap_is_empty() {
+    let heap: FibonacciHeap<u32, u32> = FibonacciHeap::default();
+    
+    assert_eq!(heap.len(), 0);
+    assert!(heap.is_empty());
+    assert!(heap.minimum().is_none());
+}
+
+#[test]
+fn clear_and_drain_empty_the_heap() {
+    let mut heap: FibonacciHeap<_, _> = (0..100).map(|i| (i, i)).collect();
+    heap.extract_min();
+    
+    assert_eq!(heap.drain().count(), 99);
+    assert!(heap.is_empty());
+    
+    heap.extend((0..10).map(|i| (i, i)));
+    assert_eq!(heap.len(), 10);
+    heap.clear();
+    assert!(heap.is_empty());
+    assert!(heap.minimum().is_none());
+}
@@ -0,0 +1,92 @@
+#![cfg(feature = "bincode")]
+
+use super::Rng;
+use {FibonacciHeap, SnapshotError};
+
+// Inserts, extractions and decreases, so the heap holds linked trees with marked nodes
+fn worked(seed: u64) -> FibonacciHeap<u64, u64> {
+    let mut rng = Rng::new(seed);
+    let mut heap = FibonacciHeap::new();
+    for _ in 0..2000 {
+        match rng.below(4) {
+            0 | 1 => heap.insert(rng.below(1000), rng.below(1000)),
+            2 => {
+                heap.extract_min();
+            },
+            _ => {
+                let _ = heap.decrease_key(rng.below(1000), rng.below(100));
+            }
+        }
+    }
+    assert!(heap.stats().marked > 0);
+    
+    heap
+}
+
+#[test]
+fn bytes_round_trip_the_exact_trees() {
+    for seed in 0..5 {
+        let heap = worked(seed);
+        let restored: FibonacciHeap<u64, u64> = FibonacciHeap::from_bytes(&heap.to_bytes()).unwrap();
+        
+        assert_eq!(restored.check_invariants(), Ok(()));
+        assert_eq!(restored.to_snapshot(), heap.to_snapshot());
+        assert_eq!(restored.minimum(), heap.minimum());
+    }
+    
+    let empty: FibonacciHeap<u64, u64> = FibonacciHeap::new();
+    let restored: FibonacciHeap<u64, u64> = FibonacciHeap::from_bytes(&empty.to_bytes()).unwrap();
+    assert!(restored.is_empty());
+}
+
+#[test]
+#[cfg(feature = "instrument")]
+fn a_restored_heap_does_the_same_work_as_the_original() {
+    for seed in 0..5 {
+        let mut heap = worked(seed);
+        let mut restored: FibonacciHeap<u64, u64> = FibonacciHeap::from_bytes(&heap.to_bytes()).unwrap();
+        heap.reset_counters();
+        restored.reset_counters();
+        
+        let mut rng = Rng::new(seed + 100);
+        for _ in 0..200 {
+            let (value, key) = (rng.below(1000), rng.below(100));
+            assert_eq!(heap.decrease_key(value, key), restored.decrease_key(value, key));
+            assert_eq!(heap.extract_min(), restored.extract_min());
+        }
+        
+        let (counters, restored_counters) = (heap.counters(), restored.counters());
+        assert!(counters.links() > 0 && counters.cuts() > 0);
+        assert_eq!(restored_counters.links(), counters.links());
+        assert_eq!(restored_counters.cuts(), counters.cuts());
+        assert_eq!(restored_counters.cascading_cuts(), counters.cascading_cuts());
+        // Checking the heap after every operation compares keys as well
+        if !cfg!(feature = "debug-invariants") {
+            assert_eq!(restored_counters.comparisons(), counters.comparisons());
+        }
+        assert_eq!(restored_counters.root_insertions(), counters.root_insertions());
+    }
+}
+
+#[test]
+fn bad_bytes_are_rejected() {
+    let result: Result<FibonacciHeap<u64, u64>, _> = FibonacciHeap::from_bytes(&[1, 2, 3]);
+    assert_eq!(result.err(), Some(SnapshotError::Undecodable));
+    
+    // Decodes, but a child gets a smaller key than its parent, which stays no larger than its own children
+    let mut snapshot = worked(0).to_snapshot();
+    let nodes = &snapshot.nodes;
+    let (parent, child) = (0..nodes.len())
+        .flat_map(|parent| nodes[parent].children.iter().map(move |&child| (parent, child)))
+        .find(|&(parent, child)| nodes[parent].key > 0 && nodes[child].key > nodes[parent].key)
+        .unwrap();
+    snapshot.nodes[child].key = snapshot.nodes[parent].key - 1;
+    let bytes = bincode::serialize(&snapshot).unwrap();
+    let result: Result<FibonacciHeap<u64, u64>, _> = FibonacciHeap::from_bytes(&bytes);
+    assert_eq!(result.err(), Some(SnapshotError::HeapOrder { parent, child }));
+    
+    let mut bytes = worked(1).to_bytes();
+    bytes.truncate(bytes.len() / 2);
+    let result: Result<FibonacciHeap<u64, u64>, _> = FibonacciHeap::from_bytes(&bytes);
+    assert_eq!(result.err(), Some(SnapshotError::Undecodable));
+}
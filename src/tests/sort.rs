@@ -0,0 +1,54 @@
+use core::cmp::Ordering;
+use std::vec;
+use std::vec::Vec;
+
+use super::Rng;
+use {heapsort, HeapSortExt};
+
+// Orders entries by key only, leaving equal keys to the stability of the sort
+fn by_key(a: &(u64, u64), b: &(u64, u64)) -> Ordering {
+    a.0.cmp(&b.0)
+}
+
+#[test]
+fn heapsort_matches_a_stable_sort_by() {
+    let (seeds, longest) = if cfg!(miri) { (4, 32) } else { (200, 2000) };
+    for seed in 0..seeds {
+        let mut rng = Rng::new(seed);
+        let len = rng.below(longest + 1) >> rng.below(8);
+        // Few distinct keys, so most keys repeat and the order of equal keys shows
+        let range = rng.below(64) + 1;
+        let items: Vec<_> = (0..len).map(|i| (rng.below(range), i)).collect();
+        
+        let mut expected = items.clone();
+        expected.sort_by(by_key);
+        
+        assert_eq!(heapsort(items), expected, "seed {}", seed);
+    }
+}
+
+#[test]
+fn sorted_via_fib_heap_matches_sort_by() {
+    let (seeds, longest) = if cfg!(miri) { (4, 32) } else { (200, 2000) };
+    for seed in 0..seeds {
+        let mut rng = Rng::new(seed);
+        let len = rng.below(longest + 1);
+        let items: Vec<_> = (0..len).map(|_| rng.below(100) as i64 - 50).collect();
+        
+        let mut expected = items.clone();
+        expected.sort_by(Ord::cmp);
+        
+        assert_eq!(items.into_iter().sorted_via_fib_heap().collect::<Vec<_>>(), expected, "seed {}", seed);
+    }
+}
+
+#[test]
+fn heapsort_keeps_values_that_are_equal_or_not_hashable() {
+    let items = vec![(2, 0.5), (1, f64::NAN), (2, 0.5), (0, 1.0)];
+    let sorted = heapsort(items);
+    
+    assert_eq!(sorted.iter().map(|&(key, _)| key).collect::<Vec<_>>(), [0, 1, 2, 2]);
+    assert!(sorted[1].1.is_nan());
+    assert_eq!(sorted[2].1, 0.5);
+    assert!(heapsort(Vec::<(u8, ())>::new()).is_empty());
+}
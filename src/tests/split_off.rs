@@ -0,0 +1,83 @@
+use std::vec::Vec;
+
+use FibonacciHeap;
+
+// A consolidated heap of the keys 1 to 99, each its own value
+fn heap() -> FibonacciHeap<i32, i32> {
+    let mut heap: FibonacciHeap<_, _> = (0..100).map(|i| (i, i)).collect();
+    heap.extract_min();
+    heap
+}
+
+fn keys(heap: FibonacciHeap<i32, i32>) -> Vec<i32> {
+    heap.into_iter().map(|(key, _)| key).collect()
+}
+
+#[test]
+fn split_off_at_a_boundary_key_moves_only_the_side_it_names() {
+    let mut heap = heap();
+    
+    let mut below = heap.split_off(|&key, _| key < 50);
+    let mut at = heap.split_off(|&key, _| key <= 50);
+    
+    assert_eq!(below.len(), 49);
+    assert_eq!(at.len(), 1);
+    assert_eq!(heap.len(), 49);
+    for part in [&below, &at, &heap] {
+        assert_eq!(part.check_invariants(), Ok(()));
+    }
+    
+    // Both sides keep working on their own
+    assert_eq!(below.decrease_key(30, -1), Ok(30));
+    assert_eq!(heap.decrease_key(70, 0), Ok(70));
+    assert_eq!(at.decrease_key(50, 0), Ok(50));
+    assert!(!heap.contains(&50) && !below.contains(&50));
+    
+    assert_eq!(keys(below)[..3], [-1, 1, 2]);
+    assert_eq!(keys(heap)[..3], [0, 51, 52]);
+}
+
+#[test]
+fn split_off_of_everything_or_nothing() {
+    let mut heap = heap();
+    
+    let none = heap.split_off(|_, _| false);
+    assert!(none.is_empty());
+    assert_eq!(heap.len(), 99);
+    
+    let all = heap.split_off(|_, _| true);
+    assert!(heap.is_empty());
+    assert!(heap.minimum().is_none());
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(all.check_invariants(), Ok(()));
+    assert_eq!(keys(all), (1..100).collect::<Vec<_>>());
+    
+    let mut empty: FibonacciHeap<i32, i32> = FibonacciHeap::new();
+    assert!(empty.split_off(|_, _| true).is_empty());
+}
+
+#[test]
+fn split_off_of_the_minimum_alone() {
+    let mut heap = heap();
+    
+    let min = heap.split_off(|&key, _| key == 1);
+    
+    assert_eq!(min.minimum(), Some((&1, &1)));
+    assert_eq!(min.len(), 1);
+    assert_eq!(heap.minimum(), Some((&2, &2)));
+    assert_eq!(heap.check_invariants(), Ok(()));
+    assert_eq!(keys(heap), (2..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn split_off_keeps_the_comparator() {
+    let mut heap = FibonacciHeap::new_by(|a: &i32, b: &i32| b.cmp(a));
+    heap.extend((0..10).map(|i| (i, i)));
+    
+    let mut high = heap.split_off(|&key, _| key >= 5);
+    
+    assert_eq!(high.minimum(), Some((&9, &9)));
+    assert_eq!(heap.minimum(), Some((&4, &4)));
+    high.insert(20, 20);
+    assert_eq!(high.extract_min(), Some((20, 20)));
+}
@@ -0,0 +1,43 @@
+use std::vec::Vec;
+
+use StableFibonacciHeap;
+
+#[test]
+fn a_thousand_equal_keys_come_out_in_insertion_order() {
+    let mut heap = StableFibonacciHeap::new();
+    let mut inserted = Vec::new();
+    for job in 0..1000 {
+        heap.insert(5, job);
+        inserted.push(job);
+        if job % 10 == 0 {
+            heap.insert(9, 10_000 + job);
+            inserted.push(10_000 + job);
+        }
+        
+        // Extracting an urgent entry consolidates the roots, linking the jobs into trees
+        if job % 100 == 99 {
+            heap.insert(0, 20_000 + job);
+            assert_eq!(heap.extract_min(), Some((0, 20_000 + job)));
+        }
+        
+        // Some of the other entries are decreased to the key of the jobs, and some below it
+        if job % 30 == 15 {
+            let other = 10_000 + job - 5;
+            let key = if job % 60 == 15 { 5 } else { 1 };
+            assert_eq!(heap.decrease_key(other, key), Ok(9));
+        }
+    }
+    
+    // A decreased entry keeps the place it got when first inserted, so entries with equal keys come out first in,
+    // first out
+    let key = |value: u64| heap.get_priority(&value).cloned().unwrap();
+    let mut expected: Vec<(u64, u64)> = inserted.iter().map(|&value| (key(value), value)).collect();
+    expected.sort_by_key(|&(key, _)| key);
+    
+    let mut extracted = Vec::new();
+    while let Some(entry) = heap.extract_min() {
+        extracted.push(entry);
+    }
+    assert_eq!(extracted, expected);
+    assert_eq!(extracted.iter().filter(|&&(key, value)| key == 5 && value < 1000).count(), 1000);
+}
@@ -0,0 +1,81 @@
+use std::collections::{BTreeSet, HashMap};
+
+use super::Rng;
+use FibonacciHeap;
+
+// Runs the operations against an ordered set of (key, value) and a map from value to key
+fn stress(seed: u64, operations: usize, values: u64) {
+    // With debug-invariants every operation checks the whole heap, so only a small part of the run fits in a test
+    let operations = if cfg!(feature = "debug-invariants") { operations / 100 } else { operations };
+    let mut rng = Rng::new(seed);
+    let mut heap = FibonacciHeap::new();
+    let mut order = BTreeSet::new();
+    let mut keys = HashMap::new();
+    
+    for step in 0..operations {
+        let value = rng.below(values);
+        match rng.below(10) {
+            0..=3 => {
+                let key = rng.below(1 << 30);
+                heap.insert(key, value);
+                if let Some(old) = keys.insert(value, key) {
+                    order.remove(&(old, value));
+                }
+                order.insert((key, value));
+            },
+            4..=6 => {
+                // Keys can repeat, so only the key of the minimum is certain
+                let expected = order.iter().next().cloned();
+                match heap.extract_min() {
+                    Some((key, value)) => {
+                        assert_eq!(Some(key), expected.map(|(key, _)| key));
+                        assert!(order.remove(&(key, value)));
+                        keys.remove(&value);
+                    },
+                    None => assert!(expected.is_none())
+                }
+            },
+            7 | 8 => {
+                if let Some(&old) = keys.get(&value) {
+                    let key = old - rng.below(old + 1);
+                    assert_eq!(heap.decrease_key(value, key), Ok(old));
+                    order.remove(&(old, value));
+                    order.insert((key, value));
+                    keys.insert(value, key);
+                }
+            },
+            _ => {
+                let removed = heap.delete(&value);
+                assert_eq!(removed, keys.remove(&value));
+                if let Some(key) = removed {
+                    order.remove(&(key, value));
+                }
+            }
+        }
+        
+        assert_eq!(heap.len(), keys.len());
+        if step % 100_000 == 0 {
+            assert!(heap.check_invariants().is_ok());
+        }
+    }
+    
+    assert!(heap.check_invariants().is_ok());
+    while let Some((key, value)) = heap.extract_min() {
+        assert_eq!(order.iter().next().map(|&(key, _)| key), Some(key));
+        assert!(order.remove(&(key, value)));
+    }
+    assert!(order.is_empty());
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn millions_of_operations_on_a_large_heap() {
+    stress(1, 2_000_000, 1 << 20);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn millions_of_operations_on_few_values() {
+    // Most operations hit values already in the heap, so decreases and deletes cut deep into the trees
+    stress(2, 1_000_000, 1000);
+}
@@ -0,0 +1,192 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::vec;
+use std::vec::Vec;
+
+use super::{Counted, Rng};
+use {DecreaseKeyError, FibonacciHeap};
+
+// Neither K nor V is Clone, so this only builds if no operation used here needs to copy a value
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct Resource(u32);
+
+#[test]
+fn values_need_not_be_clone() {
+    let mut heap = FibonacciHeap::new();
+    for i in 0..100 {
+        heap.insert(100 - i, Resource(i));
+    }
+    
+    assert_eq!(heap.decrease_key(Resource(0), 0), Ok(100));
+    assert!(heap.contains(&Resource(5)));
+    assert_eq!(heap.delete(&Resource(5)), Some(95));
+    assert_eq!(heap.extract_min(), Some((0, Resource(0))));
+    assert_eq!(heap.extract_min(), Some((1, Resource(99))));
+    assert_eq!(heap.into_iter().map(|(_, Resource(i))| i).collect::<Vec<_>>().len(), 97);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn each_value_is_dropped_exactly_once() {
+    let drops = Rc::new(Cell::new(0));
+    let mut created = 0;
+    let mut rng = Rng::new(11);
+    let mut heap = FibonacciHeap::new();
+    
+    for _ in 0..5000 {
+        let id = rng.below(200);
+        match rng.below(6) {
+            0 | 1 => {
+                // A value already in the heap is dropped in its place, or the old one is
+                created += 1;
+                heap.insert(rng.below(1000), Counted::new(id, &drops));
+            },
+            2 => {
+                heap.extract_min();
+            },
+            3 => {
+                created += 1;
+                let _ = heap.decrease_key(Counted::new(id, &drops), rng.below(1000));
+            },
+            4 => {
+                created += 1;
+                let probe = Counted::new(id, &drops);
+                heap.delete(&probe);
+            },
+            _ => {
+                created += 1;
+                heap.pop_push(rng.below(1000), Counted::new(1000 + id, &drops));
+            }
+        }
+        
+        assert_eq!(drops.get() + heap.len(), created);
+    }
+    
+    drop(heap);
+    assert_eq!(drops.get(), created);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn every_value_is_dropped_after_cuts() {
+    let drops = Rc::new(Cell::new(0));
+    let mut rng = Rng::new(12);
+    let mut heap = FibonacciHeap::new();
+    for i in 0..4096 {
+        heap.insert(1_000_000 + i, Counted::new(i, &drops));
+    }
+    heap.extract_min();
+    
+    // Decreases cut nodes out of the trees and cascade through marked parents, extractions link them again
+    let mut key = 1_000_000;
+    for round in 0..2000 {
+        key -= 1;
+        let probe = Counted::new(rng.below(4096), &drops);
+        let _ = heap.decrease_key(probe, key);
+        if round % 200 == 0 {
+            heap.extract_min();
+        }
+    }
+    assert!(heap.stats().marked > 0);
+    
+    // The decreases made as many probe values, each dropped in place of the equal value in the heap
+    assert_eq!(drops.get() + heap.len(), 4096 + 2000);
+    drop(heap);
+    assert_eq!(drops.get(), 4096 + 2000);
+}
+
+// The key, depth, degree, mark and parent key of every entry in visit order, along with its value
+fn shape(heap: &FibonacciHeap<u32, u32>) -> Vec<(u32, u32, usize, usize, bool, Option<u32>)> {
+    let mut shape = Vec::new();
+    heap.visit(|&key, &value, info| {
+        shape.push((key, value, info.depth, info.degree, info.marked, info.parent_key.cloned()));
+    });
+    
+    shape
+}
+
+#[test]
+fn rekey_value_keeps_the_place_of_a_root_a_deep_child_and_the_minimum() {
+    let mut heap = FibonacciHeap::new();
+    for i in 0..33 {
+        heap.insert(i, i);
+    }
+    heap.extract_min();
+    let _ = heap.decrease_key(20, 0);
+    
+    let before = shape(&heap);
+    let &(_, root, ..) = before.iter().find(|&&(key, _, depth, ..)| depth == 0 && key != 0).unwrap();
+    let &(_, deep, depth, ..) = before.iter().max_by_key(|&&(_, _, depth, ..)| depth).unwrap();
+    assert!(depth >= 3);
+    let minimum = *heap.minimum().unwrap().1;
+    
+    for &old in &[root, deep, minimum] {
+        let handle = heap.handle(&old).unwrap();
+        assert_eq!(heap.rekey_value(&old, old + 1000), Ok(()));
+        assert!(!heap.contains(&old));
+        assert_eq!(heap.get_handle(handle).map(|(_, &value)| value), Some(old + 1000));
+    }
+    
+    let renamed = |value: u32| if value == root || value == deep || value == minimum { value + 1000 } else { value };
+    let expected: Vec<_> = before.iter().map(|&(key, value, depth, degree, marked, parent)| {
+        (key, renamed(value), depth, degree, marked, parent)
+    }).collect();
+    assert_eq!(shape(&heap), expected);
+    assert_eq!(heap.minimum(), Some((&0, &(minimum + 1000))));
+    
+    // The new names are found by every operation, the old ones by none
+    for &old in &[root, deep, minimum] {
+        assert_eq!(heap.decrease_key(old, 0), Err(DecreaseKeyError::NotFound(0)));
+    }
+    assert_eq!(heap.decrease_key(deep + 1000, 0), Ok(deep));
+    assert_eq!(heap.decrease_key(root + 1000, 0), Ok(root));
+    let mut zeros: Vec<_> = (0..3).map(|_| heap.extract_min().unwrap()).collect();
+    zeros.sort();
+    let mut names = vec![(0, root + 1000), (0, deep + 1000), (0, minimum + 1000)];
+    names.sort();
+    assert_eq!(zeros, names);
+}
+
+#[test]
+fn rekey_value_rejects_a_missing_old_value_or_a_taken_new_one() {
+    let mut heap = FibonacciHeap::new();
+    heap.insert(1, 1);
+    heap.insert(2, 2);
+    
+    assert_eq!(heap.rekey_value(&3, 4), Err(4));
+    assert_eq!(heap.rekey_value(&1, 2), Err(2));
+    assert_eq!(heap.rekey_value(&1, 1), Ok(()));
+    assert_eq!(heap.get_priority(&1), Some(&1));
+    assert_eq!(heap.get_priority(&2), Some(&2));
+    assert!(!heap.contains(&4));
+}
+
+#[test]
+fn dropping_the_heap_drops_every_value_after_cascading_cuts() {
+    let drops = Rc::new(Cell::new(0));
+    let mut rng = Rng::new(13);
+    let mut heap = FibonacciHeap::new();
+    let handles: Vec<_> = (0..2048).map(|i| heap.push_with_handle(10_000 + i, Counted::new(i, &drops))).collect();
+    heap.extract_min();
+    
+    // Handles reach the nodes without making probe values, so every drop is one of the inserted values
+    let mut removed = 1;
+    for round in 0..1000 {
+        let handle = handles[rng.below(2048) as usize];
+        if round % 100 == 99 {
+            removed += heap.delete_handle(handle).is_some() as usize;
+        } else {
+            let _ = heap.decrease_key_handle(handle, 5000 - round);
+        }
+        assert_eq!(drops.get(), removed);
+    }
+    assert!(heap.stats().marked > 0);
+    #[cfg(feature = "instrument")]
+    {
+        assert!(heap.counters().cuts() > 0);
+        assert!(heap.counters().cascading_cuts() > 0);
+    }
+    
+    drop(heap);
+    assert_eq!(drops.get(), 2048);
+}
@@ -0,0 +1,80 @@
+use std::vec;
+use std::vec::Vec;
+
+use super::Rng;
+use {FibonacciHeap, HeapSnapshot, SnapshotNode};
+
+type Visited = Vec<(u64, u64, usize, usize, bool, Option<u64>)>;
+
+fn visited(heap: &FibonacciHeap<u64, u64>) -> Visited {
+    let mut visited = Vec::new();
+    heap.visit(|&key, &value, info| {
+        visited.push((key, value, info.depth, info.degree, info.marked, info.parent_key.cloned()));
+    });
+    
+    visited
+}
+
+fn node(key: u64, marked: bool, children: Vec<usize>) -> SnapshotNode<u64, u64> {
+    SnapshotNode { key, value: key, degree: children.len(), marked, children }
+}
+
+#[test]
+fn visit_walks_each_tree_in_preorder() {
+    // 0 ── 1 ── 4       3
+    //   └─ 2 (marked)
+    let snapshot = HeapSnapshot {
+        nodes: vec![node(0, false, vec![1, 2]), node(1, false, vec![4]), node(2, true, vec![]), node(3, false, vec![]),
+                    node(4, false, vec![])],
+        roots: vec![0, 3]
+    };
+    let heap = FibonacciHeap::from_snapshot(snapshot).unwrap();
+    
+    assert_eq!(visited(&heap), [
+        (0, 0, 0, 2, false, None),
+        (1, 1, 1, 1, false, Some(0)),
+        (4, 4, 2, 0, false, Some(1)),
+        (2, 2, 1, 0, true, Some(0)),
+        (3, 3, 0, 0, false, None)
+    ]);
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn visit_order_follows_the_snapshot_and_repeats_for_the_same_operations() {
+    let worked = |seed| {
+        let mut rng = Rng::new(seed);
+        let mut heap = FibonacciHeap::new();
+        for _ in 0..2000 {
+            match rng.below(4) {
+                0 | 1 => heap.insert(rng.below(1000), rng.below(1000)),
+                2 => {
+                    heap.extract_min();
+                },
+                _ => {
+                    let _ = heap.decrease_key(rng.below(1000), rng.below(100));
+                }
+            }
+        }
+        
+        heap
+    };
+    
+    for seed in 0..5 {
+        let heap = worked(seed);
+        let visits = visited(&heap);
+        assert_eq!(visits.len(), heap.len());
+        assert_eq!(visited(&worked(seed)), visits);
+        
+        // A preorder walk of the snapshot, which lists the roots and the children of each node in order
+        let snapshot = heap.to_snapshot();
+        let mut expected = Visited::new();
+        let mut stack: Vec<_> = snapshot.roots.iter().rev().map(|&root| (root, 0, None)).collect();
+        while let Some((x, depth, parent)) = stack.pop() {
+            let node = &snapshot.nodes[x];
+            expected.push((node.key, node.value, depth, node.degree, node.marked, parent));
+            stack.extend(node.children.iter().rev().map(|&child| (child, depth + 1, Some(node.key))));
+        }
+        assert_eq!(visits, expected);
+    }
+}
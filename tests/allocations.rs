@@ -0,0 +1,213 @@
+// Checking the heap after every operation allocates, so the counts only mean something without debug-invariants
+#![cfg(not(feature = "debug-invariants"))]
+
+extern crate fibonacci_heap;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use std::ptr;
+
+use fibonacci_heap::{FibonacciHeap, HeapSnapshot, SnapshotNode, TryPushError, TryReserveError};
+
+// Counts the allocations of each thread, so tests running side by side do not see each other's. A thread can also cap
+// its allocations, after which every further one fails
+struct Counting;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+    static CAPPED: Cell<bool> = const { Cell::new(false) };
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if CAPPED.with(Cell::get) {
+            return ptr::null_mut();
+        }
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+    
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+    
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if CAPPED.with(Cell::get) {
+            return ptr::null_mut();
+        }
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+// Runs f and returns what it returned with the number of allocations it made, reallocations included
+fn allocations<F, R>(f: F) -> (R, usize)
+    where F: FnOnce() -> R
+{
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    (result, ALLOCATIONS.with(Cell::get) - before)
+}
+
+// Runs f with every allocation it tries failing. Anything that aborts on a failed allocation aborts the test
+fn capped<F, R>(f: F) -> R
+    where F: FnOnce() -> R
+{
+    CAPPED.with(|capped| capped.set(true));
+    let result = f();
+    CAPPED.with(|capped| capped.set(false));
+    result
+}
+
+// A heap of one root with every other entry as its child
+fn star(children: u64) -> FibonacciHeap<u64, u64> {
+    let mut nodes = vec![SnapshotNode {
+        key: 0,
+        value: 0,
+        degree: children as usize,
+        marked: false,
+        children: (1..=children as usize).collect()
+    }];
+    nodes.extend((1..=children).map(|i| {
+        SnapshotNode { key: i, value: i, degree: 0, marked: false, children: Vec::new() }
+    }));
+    
+    FibonacciHeap::from_snapshot(HeapSnapshot { nodes, roots: vec![0] }).unwrap()
+}
+
+#[test]
+fn extract_min_promotes_children_without_allocating_for_each() {
+    // Miri takes minutes to build the largest star
+    let sizes: &[u64] = if cfg!(miri) { &[10, 1000] } else { &[10, 1000, 100_000] };
+    for &children in sizes {
+        let mut heap = star(children);
+        let (min, count) = allocations(|| heap.extract_min());
+        assert_eq!(min, Some((0, 0)));
+        // Only the scratch space of consolidate grows, doubling up to the largest degree after linking
+        assert!(count <= 6, "{} allocations to promote {} children", count, children);
+        assert_eq!(heap.len(), children as usize);
+    }
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn streaming_inserts_and_extractions_do_not_allocate_once_warm() {
+    let mut heap = FibonacciHeap::new();
+    heap.set_pool_limit(64);
+    for i in 0..1000u64 {
+        heap.insert(i.wrapping_mul(0x9e37_79b9) % 10_000, i);
+    }
+    
+    let mut next = 1000u64;
+    let mut step = |heap: &mut FibonacciHeap<u64, u64>| {
+        let (key, _) = heap.extract_min().unwrap();
+        heap.insert(key + next.wrapping_mul(0x9e37_79b9) % 10_000, next);
+        next += 1;
+    };
+    // Warming up grows the free list and the lookup, and links trees up to the largest degree the stream reaches so the
+    // scratch space of consolidate has room for every degree
+    for _ in 0..200_000 {
+        step(&mut heap);
+    }
+    
+    let ((), count) = allocations(|| {
+        for _ in 0..100_000 {
+            step(&mut heap);
+        }
+    });
+    assert_eq!(count, 0);
+    assert_eq!(heap.len(), 1000);
+}
+
+#[test]
+fn extracting_every_entry_allocates_o_log_n_times() {
+    let sizes: &[u64] = if cfg!(miri) { &[1000] } else { &[1000, 100_000] };
+    for &n in sizes {
+        let mut heap = FibonacciHeap::with_capacity(n as usize);
+        for i in 0..n {
+            heap.insert(i.wrapping_mul(0x9e37_79b9) % n, i);
+        }
+        
+        let (extracted, count) = allocations(|| {
+            let mut extracted = 0;
+            while heap.extract_min().is_some() {
+                extracted += 1;
+            }
+            extracted
+        });
+        assert_eq!(extracted, n);
+        // No extraction allocates for itself. The free slots the removed nodes leave double their list as it fills,
+        // and the scratch space of consolidate only grows when a larger degree appears, both O(log n) times
+        let log = 64 - n.leading_zeros() as usize;
+        assert!(count <= 2 * log, "{} allocations to extract {} entries", count, n);
+    }
+}
+
+#[test]
+fn consolidating_a_long_root_list_again_reuses_the_scratch_space() {
+    let n: u64 = if cfg!(miri) { 1000 } else { 100_000 };
+    let mut heap = FibonacciHeap::with_capacity(n as usize);
+    for i in 0..n {
+        heap.insert(i.wrapping_mul(0x9e37_79b9) % n, i);
+    }
+    let (_, first) = allocations(|| heap.extract_min());
+    assert!(first > 0);
+    
+    // Refilling reuses the slots the extractions left, and every new entry is a root of its own again
+    for _ in 0..n / 2 {
+        heap.extract_min();
+    }
+    for i in n..n + n / 2 {
+        heap.insert(i.wrapping_mul(0x9e37_79b9) % n, i);
+    }
+    assert!(heap.num_roots() > n as usize / 2);
+    
+    let (min, count) = allocations(|| heap.extract_min());
+    assert!(min.is_some());
+    assert_eq!(count, 0, "{} allocations to consolidate {} roots", count, heap.num_roots());
+}
+
+#[test]
+fn try_push_hands_the_entry_back_when_the_allocator_fails() {
+    let mut heap = FibonacciHeap::with_capacity(0);
+    assert_eq!(capped(|| heap.try_reserve(1)), Err(TryReserveError::AllocError));
+    let error = capped(|| heap.try_push(1, 1)).unwrap_err();
+    assert_eq!(error, TryPushError { key: 1, value: 1, error: TryReserveError::AllocError });
+    assert!(heap.is_empty());
+    
+    for i in 0..100 {
+        heap.try_push(i, i).unwrap();
+    }
+    heap.extract_min();
+    heap.extract_min();
+    let len = heap.len();
+    
+    // Changing the priority of a value in the heap, filling the slots extractions left and the room the storage and the
+    // lookup have grown need no new memory, the entry after that does
+    let (changed, filled, pushed, error) = capped(|| {
+        let changed = heap.try_push(0, 50);
+        let filled = heap.try_push(0, 100);
+        let mut pushed = 0;
+        let error = loop {
+            match heap.try_push(200 + pushed, 200 + pushed) {
+                Ok(()) => pushed += 1,
+                Err(error) => break error
+            }
+        };
+        (changed, filled, pushed, error)
+    });
+    assert_eq!((changed, filled), (Ok(()), Ok(())));
+    assert_eq!(error, TryPushError { key: 200 + pushed, value: 200 + pushed, error: TryReserveError::AllocError });
+    assert_eq!(heap.len(), len + 1 + pushed as usize);
+    assert_eq!(heap.peek(), Some((&0, &50)));
+    assert_eq!(heap.get_priority(&100), Some(&0));
+    assert!(!heap.contains(&(200 + pushed)));
+    assert_eq!(heap.check_invariants(), Ok(()));
+    
+    assert_eq!(heap.try_push(-1, error.value), Ok(()));
+    assert_eq!(heap.extract_min(), Some((-1, 200 + pushed)));
+}
@@ -0,0 +1,30 @@
+// Builds the crate without its default features, so a use of std outside the std feature fails the test suite rather
+// than only the no_std users. Each build gets its own target directory so it does not wait on the one running the tests
+
+use std::path::Path;
+use std::process::Command;
+
+fn check(features: &str) {
+    let manifest = env!("CARGO_MANIFEST_DIR");
+    let target = Path::new(manifest).join("target").join("no-default-features");
+    let output = Command::new(env!("CARGO"))
+        .args(["check", "--lib", "--offline", "--no-default-features", "--features", features])
+        .arg("--manifest-path").arg(Path::new(manifest).join("Cargo.toml"))
+        .arg("--target-dir").arg(target)
+        .output()
+        .expect("cargo runs");
+    
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn builds_without_std() {
+    check("");
+}
+
+#[test]
+#[cfg_attr(miri, ignore)]
+fn builds_without_std_with_every_feature_that_allows_it() {
+    check("serde,tracing,instrument,debug-invariants");
+}
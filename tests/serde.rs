@@ -0,0 +1,125 @@
+#![cfg(feature = "serde")]
+
+extern crate bincode;
+extern crate fibonacci_heap;
+extern crate serde;
+extern crate serde_json;
+
+use std::hash::Hash;
+
+use fibonacci_heap::{FibonacciHeap, MaxComparator};
+use serde::Serialize;
+
+fn entries<K, V>(heap: &FibonacciHeap<K, V>) -> Vec<(K, V)>
+    where K: Clone + Ord,
+          V: Clone + Eq + Hash + Ord
+{
+    let mut entries: Vec<_> = heap.iter().map(|(key, value)| (key.clone(), value.clone())).collect();
+    entries.sort();
+    entries
+}
+
+fn consolidated() -> FibonacciHeap<u32, String> {
+    let mut heap = FibonacciHeap::new();
+    for i in 0..200 {
+        heap.insert(i * 7 % 101, format!("value {}", i));
+    }
+    heap.extract_min();
+    heap.decrease_key("value 150".to_string(), 0).unwrap();
+    heap
+}
+
+#[test]
+fn round_trip_through_json() {
+    let heap = consolidated();
+    let json = serde_json::to_string(&heap).unwrap();
+    let back: FibonacciHeap<u32, String> = serde_json::from_str(&json).unwrap();
+    
+    assert!(back.check_invariants().is_ok());
+    assert_eq!(back.len(), heap.len());
+    assert_eq!(back.minimum(), heap.minimum());
+    assert_eq!(entries(&back), entries(&heap));
+}
+
+#[test]
+fn round_trip_through_bincode() {
+    let heap = consolidated();
+    let bytes = bincode::serialize(&heap).unwrap();
+    let back: FibonacciHeap<u32, String> = bincode::deserialize(&bytes).unwrap();
+    
+    assert!(back.check_invariants().is_ok());
+    assert_eq!(entries(&back), entries(&heap));
+    assert_eq!(back.into_sorted_vec(), heap.into_sorted_vec());
+}
+
+#[test]
+fn empty_heap_round_trips() {
+    let heap: FibonacciHeap<u32, String> = FibonacciHeap::new();
+    
+    let json = serde_json::to_string(&heap).unwrap();
+    assert_eq!(json, "[]");
+    let back: FibonacciHeap<u32, String> = serde_json::from_str(&json).unwrap();
+    assert!(back.is_empty());
+    assert!(back.minimum().is_none());
+    
+    let bytes = bincode::serialize(&heap).unwrap();
+    let back: FibonacciHeap<u32, String> = bincode::deserialize(&bytes).unwrap();
+    assert!(back.is_empty());
+    assert!(back.check_invariants().is_ok());
+}
+
+#[test]
+fn zero_sized_values_and_keys_round_trip() {
+    // Every () is the same value, so a heap holds at most one of them
+    let mut heap = FibonacciHeap::new();
+    heap.insert(3u32, ());
+    heap.insert(1u32, ());
+    
+    let back: FibonacciHeap<u32, ()> = serde_json::from_str(&serde_json::to_string(&heap).unwrap()).unwrap();
+    assert_eq!(back.minimum(), Some((&1, &())));
+    let back: FibonacciHeap<u32, ()> = bincode::deserialize(&bincode::serialize(&heap).unwrap()).unwrap();
+    assert_eq!(back.len(), 1);
+    
+    let heap: FibonacciHeap<(), u32> = (0..50).map(|i| ((), i)).collect();
+    let back: FibonacciHeap<(), u32> = bincode::deserialize(&bincode::serialize(&heap).unwrap()).unwrap();
+    assert!(back.check_invariants().is_ok());
+    assert_eq!(entries(&back), entries(&heap));
+}
+
+#[test]
+fn comparator_comes_from_the_target_type() {
+    let heap: FibonacciHeap<_, _> = (0..10).map(|i| (i, i)).collect();
+    let json = serde_json::to_string(&heap).unwrap();
+    
+    let back: FibonacciHeap<i32, i32, MaxComparator> = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.minimum(), Some((&9, &9)));
+}
+
+#[test]
+fn serializing_needs_no_bound_on_the_comparator() {
+    // Only the entries are written, so a writer generic over the comparator does not have to be able to compare
+    fn to_json<K, V, C>(heap: &FibonacciHeap<K, V, C>) -> String
+        where K: Serialize,
+              V: Eq + Hash + Serialize
+    {
+        serde_json::to_string(heap).unwrap()
+    }
+    
+    let mut heap = FibonacciHeap::with_comparator(MaxComparator);
+    heap.insert(2, 'b');
+    heap.insert(1, 'a');
+    
+    let back: FibonacciHeap<i32, char, MaxComparator> = serde_json::from_str(&to_json(&heap)).unwrap();
+    assert_eq!(back.minimum(), Some((&2, &'b')));
+}
+
+#[test]
+fn repeated_values_are_rejected() {
+    let result: Result<FibonacciHeap<u32, String>, _> = serde_json::from_str(r#"[[1, "a"], [2, "b"], [3, "a"]]"#);
+    let error = result.unwrap_err().to_string();
+    assert!(error.contains("value appears more than once at entry 2"), "{}", error);
+    
+    let bytes = bincode::serialize(&vec![(1u32, 'a'), (2, 'a')]).unwrap();
+    let result: Result<FibonacciHeap<u32, char>, _> = bincode::deserialize(&bytes);
+    assert!(result.is_err());
+}
@@ -0,0 +1,159 @@
+#![cfg(feature = "tracing")]
+
+extern crate fibonacci_heap;
+extern crate tracing;
+
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use fibonacci_heap::FibonacciHeap;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+// Collects every event and span as its name or message followed by its fields, with the events inside the open span
+// prefixed by the span name
+#[derive(Clone, Default)]
+struct Collect {
+    lines: Arc<Mutex<Vec<String>>>,
+    spans: Arc<Mutex<Vec<(&'static str, String)>>>,
+    entered: Arc<Mutex<Vec<usize>>>
+}
+
+struct Fields(String);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0.insert_str(0, &format!("{:?}", value));
+        } else {
+            self.0.push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+}
+
+impl Subscriber for Collect {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        *metadata.level() == Level::TRACE
+    }
+    
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields(String::new());
+        span.record(&mut fields);
+        let mut spans = self.spans.lock().unwrap();
+        spans.push((span.metadata().name(), fields.0));
+        Id::from_u64(spans.len() as u64)
+    }
+    
+    fn record(&self, _: &Id, _: &Record<'_>) { }
+    
+    fn record_follows_from(&self, _: &Id, _: &Id) { }
+    
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(String::new());
+        event.record(&mut fields);
+        let line = match self.entered.lock().unwrap().last() {
+            Some(&span) => format!("{}: {}", self.spans.lock().unwrap()[span - 1].0, fields.0),
+            None => fields.0
+        };
+        self.lines.lock().unwrap().push(line);
+    }
+    
+    fn enter(&self, span: &Id) {
+        let index = span.into_u64() as usize;
+        let (name, ref fields) = self.spans.lock().unwrap()[index - 1];
+        self.lines.lock().unwrap().push(format!("enter {}{}", name, fields));
+        self.entered.lock().unwrap().push(index);
+    }
+    
+    fn exit(&self, _: &Id) {
+        let index = self.entered.lock().unwrap().pop().unwrap();
+        self.lines.lock().unwrap().push(format!("exit {}", self.spans.lock().unwrap()[index - 1].0));
+    }
+}
+
+// A value that panics when formatted, so any event that formats a value fails the test
+#[derive(PartialEq, Eq, Hash)]
+struct Opaque(u64);
+
+impl fmt::Debug for Opaque {
+    fn fmt(&self, _: &mut fmt::Formatter) -> fmt::Result {
+        panic!("value was formatted")
+    }
+}
+
+// Runs f with a subscriber collecting what the heap traces
+fn traced<F>(f: F) -> Vec<String>
+    where F: FnOnce()
+{
+    let collect = Collect::default();
+    tracing::subscriber::with_default(collect.clone(), f);
+    let lines = collect.lines.lock().unwrap().clone();
+    lines
+}
+
+#[test]
+fn operations_emit_events_with_their_counts() {
+    let mut heap = FibonacciHeap::new();
+    let lines = traced(|| {
+        heap.insert(3, Opaque(3));
+        heap.insert(1, Opaque(1));
+        heap.insert(2, Opaque(2));
+        heap.insert(2, Opaque(2));
+    });
+    assert_eq!(lines, [
+        "insert len=1 replaced=false",
+        "insert len=2 replaced=false",
+        "insert len=3 replaced=false",
+        "insert len=3 replaced=true"
+    ]);
+    
+    let lines = traced(|| {
+        heap.extract_min();
+    });
+    assert_eq!(lines, [
+        "enter consolidate roots=2",
+        "consolidate: consolidated roots=1",
+        "exit consolidate",
+        "extract_min len=2 roots=1"
+    ]);
+    
+    // 3 is the child of 2, so lowering it below 2 cuts it
+    let lines = traced(|| {
+        heap.decrease_key(Opaque(3), 0).unwrap();
+    });
+    assert_eq!(lines, ["cut roots=2", "decrease_key len=2 cuts=1"]);
+}
+
+#[test]
+fn nothing_is_traced_below_trace_level() {
+    struct Silent;
+    
+    impl Subscriber for Silent {
+        fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+            *metadata.level() < Level::TRACE
+        }
+        
+        fn new_span(&self, _: &Attributes<'_>) -> Id {
+            panic!("span was created")
+        }
+        
+        fn record(&self, _: &Id, _: &Record<'_>) { }
+        
+        fn record_follows_from(&self, _: &Id, _: &Id) { }
+        
+        fn event(&self, _: &Event<'_>) {
+            panic!("event was emitted")
+        }
+        
+        fn enter(&self, _: &Id) { }
+        
+        fn exit(&self, _: &Id) { }
+    }
+    
+    tracing::subscriber::with_default(Silent, || {
+        let mut heap: FibonacciHeap<_, _> = (0..100u64).map(|i| (i, Opaque(i))).collect();
+        heap.extract_min();
+        heap.decrease_key(Opaque(99), 0).unwrap();
+    });
+}